@@ -0,0 +1,259 @@
+//! Parsing of the helper attributes accepted by the derive macros, e.g. `#[inspectable(ignore)]`.
+//!
+//! Every option is validated; unknown keys and malformed values are reported as spanned errors
+//! instead of being silently ignored.
+
+/// Options that can be given to a field via `#[inspectable(...)]`.
+#[derive(Default)]
+pub struct InspectableFieldOptions {
+    /// `#[inspectable(ignore)]`: Do not show the field in the inspector.
+    pub ignore: bool,
+    /// `#[inspectable(color)]`: Edit the field as a color, e.g. for `[f32; 4]` fields.
+    pub color: bool,
+}
+
+impl InspectableFieldOptions {
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        const KNOWN: &[&str] = &["ignore", "color"];
+
+        let mut options = Self::default();
+        for_each_option(attrs, "inspectable", |meta| {
+            match option_name(&meta)?.as_str() {
+                "ignore" => {
+                    expect_flag(&meta)?;
+                    options.ignore = true;
+                    Ok(())
+                }
+                "color" => {
+                    expect_flag(&meta)?;
+                    options.color = true;
+                    Ok(())
+                }
+                _ => Err(unknown_option("inspectable", &meta, KNOWN)),
+            }
+        })?;
+        Ok(options)
+    }
+}
+
+/// Options that can be given to a struct or enum via `#[inspectable(...)]`.
+#[derive(Default)]
+pub struct InspectableTypeOptions {
+    /// `#[inspectable(buttons(method, ...))]`: Show a button per method given, which calls it when
+    /// clicked. The methods must not take any arguments other than `&mut self`.
+    pub buttons: Vec<syn::Ident>,
+}
+
+impl InspectableTypeOptions {
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for_each_option(attrs, "inspectable", |meta| {
+            match option_name(&meta)?.as_str() {
+                "buttons" => {
+                    options.buttons.extend(expect_idents(&meta)?);
+                    Ok(())
+                }
+                _ => Err(unknown_option("inspectable", &meta, &["buttons"])),
+            }
+        })?;
+        Ok(options)
+    }
+}
+
+/// Options that can be given to a field via `#[tree_node(...)]`.
+#[derive(Default)]
+pub struct TreeNodeFieldOptions {
+    /// `#[tree_node(skip)]`: Do not show the field in the tree.
+    pub skip: bool,
+    /// `#[tree_node(rename = "...")]`: Show the field in the tree with another name.
+    pub rename: Option<syn::LitStr>,
+    /// `#[tree_node(flatten)]`: Show the children of the field as if they were part of the parent.
+    pub flatten: bool,
+    /// `#[tree_node(leaf)]`: Show the field in the tree, but not its children.
+    pub leaf: bool,
+}
+
+impl TreeNodeFieldOptions {
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        const KNOWN: &[&str] = &["skip", "rename", "flatten", "leaf"];
+
+        let mut options = Self::default();
+        // Keep track of the option spans to point conflicting options out
+        let mut given = Vec::<(String, syn::Path)>::new();
+        for_each_option(attrs, "tree_node", |meta| {
+            let name = option_name(&meta)?;
+            match name.as_str() {
+                "skip" => {
+                    expect_flag(&meta)?;
+                    options.skip = true;
+                }
+                "rename" => options.rename = Some(expect_str(&meta)?),
+                "flatten" => {
+                    expect_flag(&meta)?;
+                    options.flatten = true;
+                }
+                "leaf" => {
+                    expect_flag(&meta)?;
+                    options.leaf = true;
+                }
+                _ => return Err(unknown_option("tree_node", &meta, KNOWN)),
+            }
+            given.push((name, meta.path().clone()));
+            Ok(())
+        })?;
+
+        let conflicts = [
+            ("skip", "rename"),
+            ("skip", "flatten"),
+            ("skip", "leaf"),
+            ("flatten", "rename"),
+            ("flatten", "leaf"),
+        ];
+        for (a, b) in conflicts {
+            let has = |name: &str| given.iter().find(|(given, _)| given == name);
+            if let (Some(_), Some((_, path))) = (has(a), has(b)) {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!("`{}` cannot be combined with `{}`", b, a),
+                ));
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Errors on any `#[attr_name(...)]` found, for places where no options are supported.
+pub fn forbid_options(attrs: &[syn::Attribute], attr_name: &str) -> syn::Result<()> {
+    for_each_option(attrs, attr_name, |meta| {
+        Err(syn::Error::new_spanned(
+            meta,
+            format!("`#[{}]` options are not supported here", attr_name),
+        ))
+    })
+}
+
+/// Calls `f` for each option inside every `#[attr_name(...)]` attribute given.
+///
+/// Errors are accumulated so that all of them are reported at once.
+fn for_each_option(
+    attrs: &[syn::Attribute],
+    attr_name: &str,
+    mut f: impl FnMut(syn::Meta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    let mut push_error = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(attr_name)) {
+        let list = match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list,
+            Ok(meta) => {
+                push_error(syn::Error::new_spanned(
+                    meta,
+                    format!("expected a list of options: `#[{}(...)]`", attr_name),
+                ));
+                continue;
+            }
+            Err(error) => {
+                push_error(error);
+                continue;
+            }
+        };
+
+        for nested in list.nested {
+            let result = match nested {
+                syn::NestedMeta::Meta(meta) => f(meta),
+                syn::NestedMeta::Lit(lit) => Err(syn::Error::new_spanned(
+                    lit,
+                    "expected an option name, found a literal",
+                )),
+            };
+            if let Err(error) = result {
+                push_error(error);
+            }
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+fn option_name(meta: &syn::Meta) -> syn::Result<String> {
+    meta.path()
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .ok_or_else(|| syn::Error::new_spanned(meta.path(), "expected an option name"))
+}
+
+fn unknown_option(attr_name: &str, meta: &syn::Meta, known: &[&str]) -> syn::Error {
+    let known = known
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    syn::Error::new_spanned(
+        meta.path(),
+        format!("unknown `{}` option; expected one of: {}", attr_name, known),
+    )
+}
+
+/// Ensures the option given is a plain flag like `ignore`, without any value or arguments.
+fn expect_flag(meta: &syn::Meta) -> syn::Result<()> {
+    match meta {
+        syn::Meta::Path(_) => Ok(()),
+        syn::Meta::List(list) => Err(syn::Error::new_spanned(
+            &list.nested,
+            format!("`{}` does not take any arguments", option_name(meta)?),
+        )),
+        syn::Meta::NameValue(name_value) => Err(syn::Error::new_spanned(
+            &name_value.lit,
+            format!("`{}` does not take a value", option_name(meta)?),
+        )),
+    }
+}
+
+/// Ensures the option given is a list of identifiers like `buttons(reset, respawn)`, and returns
+/// them.
+fn expect_idents(meta: &syn::Meta) -> syn::Result<Vec<syn::Ident>> {
+    let list = match meta {
+        syn::Meta::List(list) => list,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                format!("`{}` expects a list: `{0}(...)`", option_name(meta)?),
+            ))
+        }
+    };
+    list.nested
+        .iter()
+        .map(|nested| match nested {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.get_ident().is_some() => {
+                Ok(path.get_ident().unwrap().clone())
+            }
+            _ => Err(syn::Error::new_spanned(nested, "expected a method name")),
+        })
+        .collect()
+}
+
+/// Ensures the option given has a string value like `rename = "..."`, and returns it.
+fn expect_str(meta: &syn::Meta) -> syn::Result<syn::LitStr> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Ok(lit.clone()),
+        syn::Meta::NameValue(name_value) => Err(syn::Error::new_spanned(
+            &name_value.lit,
+            format!("`{}` expects a string value", option_name(meta)?),
+        )),
+        _ => Err(syn::Error::new_spanned(
+            meta,
+            format!("`{}` expects a value: `{0} = \"...\"`", option_name(meta)?),
+        )),
+    }
+}
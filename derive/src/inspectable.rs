@@ -1,98 +1,139 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
 
-use crate::{specialization::Specialization, usages, util};
+use crate::{attrs, specialization::Specialization, usages, util};
 
 pub fn derive(input: TokenStream) -> TokenStream {
     let DeriveInput {
+        attrs,
         ident,
         data,
         generics,
         ..
     } = parse_macro_input!(input as DeriveInput);
 
-    let where_clause = &generics.where_clause;
-
-    match data {
-        syn::Data::Struct(r#struct) => derive_struct(r#struct, &generics, &ident, where_clause),
+    let result = attrs::InspectableTypeOptions::from_attrs(&attrs).and_then(|options| match data {
+        syn::Data::Struct(r#struct) => derive_struct(r#struct, &generics, &ident, &options),
+        syn::Data::Enum(r#enum) => derive_enum(r#enum, &generics, &ident, &options),
+        syn::Data::Union(union) => Err(syn::Error::new_spanned(
+            union.union_token,
+            "unions are currently not supported as inspectables",
+        )),
+    });
 
-        syn::Data::Enum(r#enum) => derive_enum(r#enum, &generics, &ident, where_clause),
-        syn::Data::Union(_union) => panic!("Unions are currently not supported as inspectables"),
-    }
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
 }
 
 fn derive_enum(
     r#enum: syn::DataEnum,
     generics: &syn::Generics,
     ident: &proc_macro2::Ident,
-    where_clause: &Option<syn::WhereClause>,
-) -> TokenStream {
+    options: &attrs::InspectableTypeOptions,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let inspectable = usages::inspectable_trait();
     let ui = usages::egui_ui();
 
-    let checkbox_variants_ui =
-        r#enum
-            .variants
-            .iter()
-            .fold(proc_macro2::TokenStream::new(), |tokens, variant| {
-                let variant_ident = &variant.ident;
-                let variant_default = match variant.fields {
-                    syn::Fields::Unnamed(_) => {
-                        let fields = variant.fields.iter().fold(proc_macro2::TokenStream::new(), |tokens, _| {
-                            quote! {
-                                #tokens
-                                Default::default(),
-                            }
-                        });
-                        quote! {
-                            Self:: #variant_ident ( #fields )
-                        }
-                    }
-                    syn::Fields::Named(_) => {
-                        let fields = variant.fields.iter().fold(proc_macro2::TokenStream::new(), |tokens, field| {
-                            let field_ident = field.ident.as_ref().unwrap();
-                            quote! {
-                                #tokens
-                                #field_ident: Default::default(),
-                            }
-                        });
-                        quote! {
-                            Self:: #variant_ident { #fields }
-                        }
-                    }
-                    syn::Fields::Unit => {
-                        quote!{ Self:: #variant_ident }
-                    }
-                };
-                quote! {
-                    #tokens
-                    let mut response = ui.add(::guiedit::egui::SelectableLabel::new(matches!(self, Self:: #variant_ident{..}), stringify!(#variant_ident)));
-                    if response.clicked() {
-                        *self = #variant_default;
-                        response.mark_changed();
-                    }
+    let mut default_specialization = Specialization::new();
+    default_specialization
+        .default_case_for(
+            syn::parse_quote!(VariantDefault<T>),
+            syn::parse_quote!(::std::marker::PhantomData<T>),
+            quote! {
+                fn has_default(&self) -> bool {
+                    false
                 }
-            });
 
-    let variants_ui =
-        r#enum
-            .variants
-            .iter()
-            .fold(proc_macro2::TokenStream::new(), |tokens, variant| {
-                let variant_ident = &variant.ident;
-                let (destructure, fields_idents) = util::destructure_fields(
-                    syn::parse_quote! { #ident::#variant_ident },
-                    &variant.fields,
-                );
+                fn default_value(&self) -> T {
+                    unreachable!("variant fields without a default value cannot be constructed")
+                }
+            },
+        )
+        .add_case_for_bounds(
+            syn::parse_quote!(::std::default::Default),
+            quote! {
+                fn has_default(&self) -> bool {
+                    true
+                }
 
-                let ui = inspect_ui_for_idents(ident, fields_idents.iter());
+                fn default_value(&self) -> T {
+                    T::default()
+                }
+            },
+        );
+    let default_specialization = default_specialization.build();
 
-                quote!(
-                    #tokens
-                    #destructure => { #ui },
-                )
+    let checkbox_variants_ui = r#enum.variants.iter().fold(
+        quote! {
+            trait VariantDefault<T> {
+                fn has_default(&self) -> bool;
+                fn default_value(&self) -> T;
+            }
+            #default_specialization
+        },
+        |tokens, variant| {
+            let variant_ident = &variant.ident;
+            // Variants can only be switched to if all of their fields can be default-constructed
+            let field_default = |ty: &syn::Type| {
+                quote! { Wrap(Wrap(::std::marker::PhantomData::<#ty>)).default_value() }
+            };
+            let has_default = variant.fields.iter().fold(quote! { true }, |tokens, field| {
+                let ty = &field.ty;
+                quote! { #tokens && Wrap(Wrap(::std::marker::PhantomData::<#ty>)).has_default() }
             });
+            let variant_default = match &variant.fields {
+                syn::Fields::Unnamed(fields) => {
+                    let fields = fields.unnamed.iter().map(|field| field_default(&field.ty));
+                    quote! { Self:: #variant_ident ( #(#fields,)* ) }
+                }
+                syn::Fields::Named(fields) => {
+                    let fields = fields.named.iter().map(|field| {
+                        let field_ident = field.ident.as_ref().unwrap();
+                        let default = field_default(&field.ty);
+                        quote! { #field_ident: #default }
+                    });
+                    quote! { Self:: #variant_ident { #(#fields,)* } }
+                }
+                syn::Fields::Unit => quote! { Self:: #variant_ident },
+            };
+            quote! {
+                #tokens
+                let mut response = ui.add_enabled(
+                    #has_default,
+                    ::guiedit::egui::SelectableLabel::new(matches!(self, Self:: #variant_ident{..}), stringify!(#variant_ident)),
+                );
+                if response.clicked() {
+                    *self = #variant_default;
+                    response.mark_changed();
+                }
+            }
+        },
+    );
+
+    let mut variants_ui = proc_macro2::TokenStream::new();
+    let mut variants_visit = proc_macro2::TokenStream::new();
+    for variant in &r#enum.variants {
+        attrs::forbid_options(&variant.attrs, "inspectable")?;
+
+        let variant_ident = &variant.ident;
+        let (destructure, fields) = util::destructure_fields(
+            syn::parse_quote! { #ident::#variant_ident },
+            &variant.fields,
+        )?;
+
+        let ui = inspect_ui_for_fields(ident, &fields, generics, quote! {});
+        let visit = visit_fields_for_fields(&fields);
+
+        variants_ui = quote!(
+            #variants_ui
+            #destructure => { #ui },
+        );
+        variants_visit = quote!(
+            #variants_visit
+            #destructure => { #visit },
+        );
+    }
 
     let selected_text_patterns =
         r#enum
@@ -107,10 +148,14 @@ fn derive_enum(
                 }
             });
 
-    quote! {
+    let buttons = Buttons::new(&options.buttons);
+    let (declare_buttons, buttons_ui, call_buttons) =
+        (&buttons.declare, &buttons.ui, &buttons.call);
+    Ok(quote! {
         #[automatically_derived]
-        impl #generics #inspectable for #ident #generics #where_clause {
+        impl #impl_generics #inspectable for #ident #ty_generics #where_clause {
             fn inspect_ui(&mut self, ui: &mut #ui) {
+                #declare_buttons
                 ui.group(|ui| {
                     ui.label(stringify!(#ident));
 
@@ -124,47 +169,63 @@ fn derive_enum(
                     match self {
                         #variants_ui
                     }
+                    #buttons_ui
                 });
+                #call_buttons
+            }
+
+            fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn #inspectable)) {
+                match self {
+                    #variants_visit
+                }
             }
         }
-    }
-    .into()
+    })
 }
 
 fn derive_struct(
     syn::DataStruct { fields, .. }: syn::DataStruct,
     generics: &syn::Generics,
     ident: &proc_macro2::Ident,
-    where_clause: &Option<syn::WhereClause>,
-) -> TokenStream {
+    options: &attrs::InspectableTypeOptions,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let inspectable = usages::inspectable_trait();
     let ui = usages::egui_ui();
 
-    let (destructure_pat, fields_idents) =
-        util::destructure_fields(syn::parse_quote!( #ident ), &fields);
+    let (destructure_pat, fields) = util::destructure_fields(syn::parse_quote!( #ident ), &fields)?;
 
-    let fields_inspect_ui = inspect_ui_for_idents(ident, fields_idents.iter());
-    quote! {
+    let buttons = Buttons::new(&options.buttons);
+    let fields_inspect_ui = inspect_ui_for_fields(ident, &fields, generics, buttons.ui);
+    let fields_visit = visit_fields_for_fields(&fields);
+    let (declare_buttons, call_buttons) = (buttons.declare, buttons.call);
+    Ok(quote! {
         #[automatically_derived]
-        impl #generics #inspectable for #ident #generics #where_clause {
+        impl #impl_generics #inspectable for #ident #ty_generics #where_clause {
             fn inspect_ui_outside(&mut self, _name: &str, _ui: &mut #ui) {}
 
             fn inspect_ui(&mut self, ui: &mut #ui) {
+                #declare_buttons
                 let #destructure_pat = &mut *self;
                 #fields_inspect_ui
+                #call_buttons
+            }
+
+            fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn #inspectable)) {
+                let #destructure_pat = &mut *self;
+                #fields_visit
             }
         }
-    }
-    .into()
+    })
 }
 
-fn inspect_ui_for_idents<'i, IdentIter>(
+/// Generates code showing the fields given in a group, followed by `extra_ui`.
+fn inspect_ui_for_fields(
     parent_ident: &proc_macro2::Ident,
-    idents: IdentIter,
-) -> proc_macro2::TokenStream
-where
-    IdentIter: Iterator<Item = &'i syn::Ident>,
-{
+    fields: &[util::BoundField],
+    generics: &syn::Generics,
+    extra_ui: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     let inspectable = usages::inspectable_trait();
     let ui = usages::egui_ui();
 
@@ -190,13 +251,65 @@ where
                 }
             },
         );
+    // `'static` fields that do not implement Inspectable can still be inspected if the
+    // application registered an inspector for their type
+    specialization
+        .default_case_where(
+            syn::parse_quote!(InspectRegistered),
+            syn::parse_quote!(where T: 'static),
+            quote! {
+                fn inspect_registered_outside(&mut self, name: &str, ui: &mut #ui) {
+                    ::guiedit::inspectable::labeled_ui(name, ui, |ui| {
+                        if !::guiedit::registry::inspect(&mut *self.0, ui) {
+                            ui.add_enabled_ui(false, |ui| ui.label("Does not implement Inspectable"));
+                        }
+                    });
+                }
+            },
+        )
+        .add_case_for_bounds(
+            syn::parse_quote!(#inspectable),
+            quote! {
+                fn inspect_registered_outside(&mut self, name: &str, ui: &mut #ui) {
+                    self.0.0.inspect_ui_outside(name, ui);
+                }
+            },
+        );
     let specialization = specialization.build();
-    let fields_inspect_ui = idents.fold(specialization, |tokens, ident| {
+    let fields_inspect_ui = fields.iter().fold(
         quote! {
-            #tokens
-            Wrap(Wrap(#ident)).inspect_ui_outside(stringify!(#ident), ui);
-        }
-    });
+            trait InspectRegistered {
+                fn inspect_registered_outside(&mut self, name: &str, ui: &mut #ui);
+            }
+            #specialization
+        },
+        |tokens, util::BoundField { ident, ty, color }| {
+            if *color {
+                // Errors about the field not being a color point to its type
+                let inspect = quote_spanned! {ty.span()=>
+                    #inspectable::inspect_ui_outside(
+                        &mut ::guiedit::color::AsColor(#ident),
+                        stringify!(#ident),
+                        ui,
+                    );
+                };
+                quote! {
+                    #tokens
+                    #inspect
+                }
+            } else if util::is_static_type(ty, generics) {
+                quote! {
+                    #tokens
+                    Wrap(Wrap(#ident)).inspect_registered_outside(stringify!(#ident), ui);
+                }
+            } else {
+                quote! {
+                    #tokens
+                    Wrap(Wrap(#ident)).inspect_ui_outside(stringify!(#ident), ui);
+                }
+            }
+        },
+    );
 
     quote! {
         use #inspectable;
@@ -204,6 +317,95 @@ where
         ui.group(|ui| {
             ui.label(stringify!(#parent_ident));
             #fields_inspect_ui
+            #extra_ui
+        });
+    }
+}
+
+/// The code generated for `#[inspectable(buttons(...))]`.
+///
+/// Buttons only record whether they were clicked, and their methods are called once the UI is
+/// shown, since the fields of `self` are borrowed until then.
+struct Buttons {
+    /// Declares the variable holding which buttons were clicked.
+    declare: proc_macro2::TokenStream,
+    /// Shows the buttons.
+    ui: proc_macro2::TokenStream,
+    /// Calls the methods of the buttons clicked.
+    call: proc_macro2::TokenStream,
+}
+
+impl Buttons {
+    fn new(methods: &[syn::Ident]) -> Self {
+        if methods.is_empty() {
+            return Self {
+                declare: quote! {},
+                ui: quote! {},
+                call: quote! {},
+            };
+        }
+
+        let clicked = syn::Ident::new("clicked_buttons", proc_macro2::Span::mixed_site());
+        let count = methods.len();
+        let idx = 0..count;
+        let call = methods.iter().enumerate().map(|(idx, method)| {
+            // Errors about the method (e.g. if it does not exist) point to its name
+            let call = quote_spanned! {method.span()=> self.#method() };
+            quote! {
+                if #clicked[#idx] {
+                    let _ = #call;
+                }
+            }
         });
+        Self {
+            declare: quote! { let mut #clicked = [false; #count]; },
+            ui: quote! {
+                ui.horizontal_wrapped(|ui| {
+                    #(
+                        #clicked[#idx] =
+                            ::guiedit::inspectable::method_button(ui, stringify!(#methods));
+                    )*
+                });
+            },
+            call: quote! { #(#call)* },
+        }
     }
 }
+
+/// Generates code calling `f` with each of the bound fields that implement Inspectable.
+fn visit_fields_for_fields(fields: &[util::BoundField]) -> proc_macro2::TokenStream {
+    let inspectable = usages::inspectable_trait();
+
+    let mut specialization = Specialization::new();
+    specialization
+        .default_case(
+            syn::parse_quote!(VisitField),
+            quote! {
+                fn visit_field(&mut self, _: &str, _: &mut dyn FnMut(&str, &mut dyn #inspectable)) {}
+            },
+        )
+        .add_case_for_bounds(
+            syn::parse_quote!(#inspectable),
+            quote! {
+                fn visit_field(&mut self, name: &str, f: &mut dyn FnMut(&str, &mut dyn #inspectable)) {
+                    f(name, &mut *self.0.0);
+                }
+            },
+        );
+    let specialization = specialization.build();
+
+    fields.iter().fold(
+        quote! {
+            trait VisitField {
+                fn visit_field(&mut self, name: &str, f: &mut dyn FnMut(&str, &mut dyn #inspectable));
+            }
+            #specialization
+        },
+        |tokens, util::BoundField { ident, .. }| {
+            quote! {
+                #tokens
+                Wrap(Wrap(#ident)).visit_field(stringify!(#ident), f);
+            }
+        },
+    )
+}
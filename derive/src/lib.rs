@@ -1,17 +1,35 @@
 use proc_macro::TokenStream;
 
+mod attrs;
 mod inspectable;
 mod specialization;
 mod tree_node;
 mod usages;
 mod util;
 
+/// Derives `Inspectable` for a struct or enum, inspecting each of its fields.
+///
+/// The struct or enum accepts the following options:
+/// - `#[inspectable(buttons(method, ...))]`: Show a button per method given, calling it when
+///   clicked. The methods must not take any arguments other than `&mut self`.
+///
+/// Fields accept the following options:
+/// - `#[inspectable(ignore)]`: Do not show the field in the inspector.
+/// - `#[inspectable(color)]`: Edit the field as a color. The field must implement
+///   `guiedit::color::EditColor`, like `[f32; 3]`, `[f32; 4]` and `[u8; 4]` do.
 #[proc_macro_derive(Inspectable, attributes(inspectable))]
 pub fn derive_inspectable(input: TokenStream) -> TokenStream {
     inspectable::derive(input)
 }
 
-#[proc_macro_derive(TreeNode)]
+/// Derives `TreeNode` for a struct, showing every field that implements `TreeNode` as a child.
+///
+/// Fields accept the following options:
+/// - `#[tree_node(skip)]`: Do not show the field in the tree.
+/// - `#[tree_node(rename = "...")]`: Show the field in the tree with another name.
+/// - `#[tree_node(flatten)]`: Show the children of the field directly under this node.
+/// - `#[tree_node(leaf)]`: Show the field in the tree, but not its children.
+#[proc_macro_derive(TreeNode, attributes(tree_node))]
 pub fn derive_tree_node(input: TokenStream) -> TokenStream {
     tree_node::derive(input)
 }
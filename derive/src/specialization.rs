@@ -67,12 +67,55 @@ impl Specialization {
         &'s mut self,
         r#trait: syn::TypePath,
         contents: proc_macro2::TokenStream,
+    ) -> TypeSpecialization<'s> {
+        self.default_case_for(r#trait, syn::parse_quote! { &mut T }, contents)
+    }
+
+    /// Like [`Specialization::default_case`], but specializes over `Wrap<#wrapped>` instead of
+    /// `Wrap<&mut T>`, where `wrapped` is some type containing the `T` generic parameter.
+    ///
+    /// Useful for specializing over types without having a value of them, e.g. by wrapping
+    /// `PhantomData<T>`.
+    pub fn default_case_for<'s>(
+        &'s mut self,
+        r#trait: syn::TypePath,
+        wrapped: syn::Type,
+        contents: proc_macro2::TokenStream,
+    ) -> TypeSpecialization<'s> {
+        self.add_default_case(r#trait, wrapped, None, contents)
+    }
+
+    /// Like [`Specialization::default_case`], but only implemented for the types that satisfy the
+    /// where clause given, e.g. `where T: 'static`.
+    ///
+    /// Note that since trait selection does not take lifetimes into account, the method must not
+    /// be called on types that do not satisfy lifetime bounds.
+    pub fn default_case_where<'s>(
+        &'s mut self,
+        r#trait: syn::TypePath,
+        where_clause: syn::WhereClause,
+        contents: proc_macro2::TokenStream,
+    ) -> TypeSpecialization<'s> {
+        self.add_default_case(
+            r#trait,
+            syn::parse_quote! { &mut T },
+            Some(where_clause),
+            contents,
+        )
+    }
+
+    fn add_default_case<'s>(
+        &'s mut self,
+        r#trait: syn::TypePath,
+        wrapped: syn::Type,
+        where_clause: Option<syn::WhereClause>,
+        contents: proc_macro2::TokenStream,
     ) -> TypeSpecialization<'s> {
         let stream = &self.stream;
-        let wrap = quote! { Wrap<&mut T> };
+        let wrap = quote! { Wrap<#wrapped> };
         self.stream = quote! {
             #stream
-            impl<T> #r#trait for #wrap {
+            impl<T> #r#trait for #wrap #where_clause {
                 #contents
             }
         };
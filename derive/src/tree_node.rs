@@ -2,35 +2,63 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
-use crate::{specialization::Specialization, usages, util};
+use crate::{
+    attrs::{self, TreeNodeFieldOptions},
+    specialization::Specialization,
+    usages, util,
+};
 
 pub fn derive(input: TokenStream) -> TokenStream {
     let DeriveInput {
+        attrs,
         ident,
         data,
         generics,
         ..
     } = parse_macro_input!(input as DeriveInput);
 
-    let where_clause = &generics.where_clause;
-
-    match data {
-        syn::Data::Struct(r#struct) => derive_struct(r#struct, &generics, &ident, where_clause),
+    let result = attrs::forbid_options(&attrs, "tree_node").and_then(|()| match data {
+        syn::Data::Struct(r#struct) => derive_struct(r#struct, &generics, &ident),
 
         // TODO: TreeNode for Enum
-        syn::Data::Enum(_enum) => {
-            todo!("TreeNode derive not yet implemented for enums")
-        }
-        syn::Data::Union(_union) => panic!("Unions are currently not supported as inspectables"),
-    }
+        syn::Data::Enum(r#enum) => Err(syn::Error::new_spanned(
+            r#enum.enum_token,
+            "deriving TreeNode is not yet supported for enums; implement it manually instead",
+        )),
+        syn::Data::Union(union) => Err(syn::Error::new_spanned(
+            union.union_token,
+            "unions are currently not supported as tree nodes",
+        )),
+    });
+
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
 }
 
 fn derive_struct(
     r#struct: syn::DataStruct,
     generics: &syn::Generics,
     ident: &proc_macro2::Ident,
-    where_clause: &Option<syn::WhereClause>,
-) -> TokenStream {
+) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = r#struct
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let options = TreeNodeFieldOptions::from_attrs(&field.attrs)?;
+            let name = match &options.rename {
+                Some(rename) => rename.value(),
+                None => field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or(idx.to_string()),
+            };
+            let field = util::struct_field(field.ident.as_ref(), idx as u32);
+            Ok((field, name, options))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let tree_node = usages::tree_node_trait();
     let inspectable = usages::inspectable_trait();
     let ui = usages::egui_ui();
@@ -58,6 +86,10 @@ fn derive_struct(
         fn contents_ui(&mut self, id: u64, selected: &mut Option<u64>, ui: &mut #ui) {
             self.0.0.contents_ui(id, selected, ui)
         }
+
+        fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn #tree_node)) {
+            self.0.0.visit_children(id, f)
+        }
     });
     tree_node_specialization
         .default_case(
@@ -74,54 +106,131 @@ fn derive_struct(
                 }
             },
         );
+    // Fields marked with `#[tree_node(leaf)]` are shown as a node without any children
+    tree_node_specialization
+        .default_case(
+            syn::parse_quote!(LeafNode),
+            quote! {
+                fn leaf_node_ui(&mut self, _: &str, _: u64, _: &mut Option<u64>, _: &mut #ui) {}
+            },
+        )
+        .add_case_for_bounds(
+            syn::parse_quote!(#tree_node),
+            quote! {
+                fn leaf_node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut #ui) {
+                    ::guiedit::tree::default_node_ui(std::any::type_name::<T>(), name, id, selected, ui);
+                }
+            },
+        );
+    // Fields are given to `visit_children` as `&mut dyn TreeNode`, which requires knowing that
+    // they implement it
+    tree_node_specialization
+        .default_case(
+            syn::parse_quote!(VisitChild),
+            quote! {
+                fn visit_child(&mut self, _: &str, _: u64, _: &mut dyn FnMut(&str, u64, &mut dyn #tree_node)) {}
+
+                fn visit_leaf(&mut self, _: &str, _: u64, _: &mut dyn FnMut(&str, u64, &mut dyn #tree_node)) {}
+            },
+        )
+        .add_case_for_bounds(
+            syn::parse_quote!(#tree_node),
+            quote! {
+                fn visit_child(&mut self, name: &str, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn #tree_node)) {
+                    f(name, id, &mut *self.0.0);
+                }
+
+                fn visit_leaf(&mut self, name: &str, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn #tree_node)) {
+                    f(name, id, &mut ::guiedit::tree::Leaf(&mut *self.0.0));
+                }
+            },
+        );
     let tree_node_specialization = tree_node_specialization.build();
     let wrap_tree_elements_impl = quote! {
+        trait LeafNode {
+            fn leaf_node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut #ui);
+        }
+        trait VisitChild {
+            fn visit_child(&mut self, name: &str, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn #tree_node));
+            fn visit_leaf(&mut self, name: &str, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn #tree_node));
+        }
         #tree_node_specialization
 
         use ::std::hash::Hasher;
         let mut hasher = std::collections::hash_map::DefaultHasher::default();
         hasher.write_u64(id);
     };
-    let fields_tree_ui = r#struct.fields.iter().enumerate().fold(
+    // Every field is hashed, even skipped ones, so that IDs do not change when skipping fields
+    let fields_tree_ui = fields.iter().fold(
         wrap_tree_elements_impl.clone(),
-        |tokens, (idx, field)| {
-            let name = field
-                .ident
-                .as_ref()
-                .map(|ident| ident.to_string())
-                .unwrap_or(idx.to_string());
-            let field = util::struct_field(field.ident.as_ref(), idx as u32);
+        |tokens, (field, name, options)| {
+            let field_ui = if options.skip {
+                quote! {}
+            } else if options.leaf {
+                quote! { Wrap(Wrap(&mut #field)).leaf_node_ui(#name, hasher.clone().finish(), selected, ui); }
+            } else if options.flatten {
+                quote! { Wrap(Wrap(&mut #field)).contents_ui(hasher.clone().finish(), selected, ui); }
+            } else {
+                quote! { Wrap(Wrap(&mut #field)).node_ui(#name, hasher.clone().finish(), selected, ui); }
+            };
             quote! {
                 #tokens
                 hasher.write_u64(0);
-                Wrap(Wrap(&mut #field)).node_ui(#name, hasher.clone().finish(), selected, ui);
+                #field_ui
+            }
+        },
+    );
+    let fields_visit = fields.iter().fold(
+        wrap_tree_elements_impl.clone(),
+        |tokens, (field, name, options)| {
+            let field_visit = if options.skip {
+                quote! {}
+            } else if options.leaf {
+                quote! { Wrap(Wrap(&mut #field)).visit_leaf(#name, hasher.clone().finish(), f); }
+            } else if options.flatten {
+                quote! { Wrap(Wrap(&mut #field)).visit_children(hasher.clone().finish(), f); }
+            } else {
+                quote! { Wrap(Wrap(&mut #field)).visit_child(#name, hasher.clone().finish(), f); }
+            };
+            quote! {
+                #tokens
+                hasher.write_u64(0);
+                #field_visit
             }
         },
     );
     let fields_search =
-        r#struct
-            .fields
+        fields
             .iter()
-            .enumerate()
-            .fold(wrap_tree_elements_impl, |tokens, (idx, field)| {
-                let field = util::struct_field(field.ident.as_ref(), idx as u32);
+            .fold(wrap_tree_elements_impl, |tokens, (field, _, options)| {
+                let field_search = if options.skip {
+                    quote! {}
+                } else if options.leaf {
+                    quote! {
+                        if hasher.clone().finish() == search_id {
+                            Wrap(Wrap(&mut #field)).inspect_child(search_id, search_id, ui);
+                        }
+                    }
+                } else {
+                    quote! { Wrap(Wrap(&mut #field)).inspect_child(hasher.clone().finish(), search_id, ui); }
+                };
                 quote! {
                     #tokens
                     hasher.write_u64(0);
-                    Wrap(Wrap(&mut #field)).inspect_child(hasher.clone().finish(), search_id, ui);
+                    #field_search
                 }
             });
     let node_ui = {
-        let children_implement_tree_node = r#struct.fields.iter().enumerate().fold(
-            quote! { let mut has_children = false; },
-            |tokens, (idx, field)| {
-                let field = util::struct_field(field.ident.as_ref(), idx as u32);
-                quote! {
-                    #tokens
-                    if Wrap(Wrap(&mut #field)).implements_tree_node() { has_children = true; }
-                }
-            },
-        );
+        let children_implement_tree_node =
+            fields.iter().filter(|(_, _, options)| !options.skip).fold(
+                quote! { let mut has_children = false; },
+                |tokens, (field, _, _)| {
+                    quote! {
+                        #tokens
+                        if Wrap(Wrap(&mut #field)).implements_tree_node() { has_children = true; }
+                    }
+                },
+            );
         let mut implements_tree_node_specialization = Specialization::new();
         implements_tree_node_specialization
             .default_case(
@@ -169,9 +278,9 @@ fn derive_struct(
             }
         }
     };
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #generics #tree_node for #ident #generics #where_clause {
+        impl #impl_generics #tree_node for #ident #ty_generics #where_clause {
             fn inspect_child(&mut self, id: u64, search_id: u64, ui: &mut #ui) {
                 use #inspectable;
                 use #tree_node;
@@ -193,7 +302,13 @@ fn derive_struct(
 
                 #fields_tree_ui
             }
+
+            fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn #tree_node)) {
+                use #inspectable;
+                use #tree_node;
+
+                #fields_visit
+            }
         }
-    }
-    .into()
+    })
 }
@@ -1,5 +1,7 @@
 use proc_macro2::Ident;
 
+use crate::attrs::InspectableFieldOptions;
+
 pub fn struct_field(field_ident: Option<&syn::Ident>, index: u32) -> syn::Expr {
     field_ident
         .map(|ident| syn::parse_quote!(self.#ident))
@@ -12,7 +14,65 @@ pub fn struct_field(field_ident: Option<&syn::Ident>, index: u32) -> syn::Expr {
         })
 }
 
-/// Generates a pattern destructuring all fields given, along with the identifiers bound to them.
+/// Returns whether the type given is known to be `'static`, that is, whether it does not contain
+/// any lifetimes other than `'static` nor any of the generic type parameters given.
+///
+/// This check is purely syntactic, so it errs on the side of caution (e.g. type macros are never
+/// considered `'static`).
+pub fn is_static_type(ty: &syn::Type, generics: &syn::Generics) -> bool {
+    use syn::visit::Visit;
+
+    struct StaticVisitor<'g> {
+        generics: &'g syn::Generics,
+        is_static: bool,
+    }
+
+    impl<'ast> Visit<'ast> for StaticVisitor<'_> {
+        fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+            if lifetime.ident != "static" {
+                self.is_static = false;
+            }
+        }
+
+        fn visit_type_path(&mut self, path: &'ast syn::TypePath) {
+            let first_segment = path.path.segments.first();
+            if path.qself.is_none() && path.path.leading_colon.is_none() {
+                if let Some(segment) = first_segment {
+                    if self
+                        .generics
+                        .type_params()
+                        .any(|param| param.ident == segment.ident)
+                    {
+                        self.is_static = false;
+                    }
+                }
+            }
+            syn::visit::visit_type_path(self, path);
+        }
+
+        fn visit_type_macro(&mut self, _: &'ast syn::TypeMacro) {
+            self.is_static = false;
+        }
+    }
+
+    let mut visitor = StaticVisitor {
+        generics,
+        is_static: true,
+    };
+    visitor.visit_type(ty);
+    visitor.is_static
+}
+
+/// A field bound by the pattern generated by [`destructure_fields`].
+pub struct BoundField {
+    /// The identifier the field is bound to.
+    pub ident: Ident,
+    pub ty: syn::Type,
+    /// Whether the field is marked with `#[inspectable(color)]`.
+    pub color: bool,
+}
+
+/// Generates a pattern destructuring all fields given, along with the fields bound by it.
 ///
 /// Generates code equivalent to:
 /// ```ignore
@@ -20,42 +80,52 @@ pub fn struct_field(field_ident: Option<&syn::Ident>, index: u32) -> syn::Expr {
 /// ```
 ///
 /// # Important
-/// Ignores fields marked with the `inspectable(ignore)` attribute.
-pub fn destructure_fields(path: syn::Path, fields: &syn::Fields) -> (syn::Pat, Vec<Ident>) {
-    fn filter_ignored<'f, 'i>(
-        x: impl Iterator<Item = &'f syn::Field> + Clone + 'i,
-    ) -> impl Iterator<Item = &'f syn::Field> + Clone + 'i {
-        x.filter(|field| {
-            !field.attrs.iter().any(|attr| {
-                attr.path
-                    .get_ident()
-                    .map_or(false, |ident| &ident.to_string() == "inspectable")
-                    && &attr.tokens.to_string() == "(ignore)"
-            })
-        })
+/// Ignores fields marked with the `inspectable(ignore)` attribute, and errors on any invalid
+/// `inspectable` attribute.
+pub fn destructure_fields(
+    path: syn::Path,
+    fields: &syn::Fields,
+) -> syn::Result<(syn::Pat, Vec<BoundField>)> {
+    let ref_mut_binding = |ident: Ident| -> syn::Pat {
+        syn::PatIdent {
+            ident,
+            mutability: Some(syn::token::Mut {
+                span: proc_macro2::Span::mixed_site(),
+            }),
+            attrs: vec![],
+            by_ref: Some(syn::token::Ref {
+                span: proc_macro2::Span::mixed_site(),
+            }),
+            subpat: None,
+        }
+        .into()
+    };
+
+    let mut options = Vec::with_capacity(fields.len());
+    for field in fields {
+        options.push(InspectableFieldOptions::from_attrs(&field.attrs)?);
     }
 
     match fields {
         syn::Fields::Named(fields) => {
-            let idents = filter_ignored(fields.named.iter())
-                .map(|field| field.ident.as_ref().cloned().unwrap());
-            let fields = idents
-                .clone()
-                .map(|ident| syn::FieldPat {
-                    member: syn::Member::Named(ident.clone()),
+            let bound = fields
+                .named
+                .iter()
+                .zip(&options)
+                .filter(|(_, options)| !options.ignore)
+                .map(|(field, options)| BoundField {
+                    ident: field.ident.as_ref().cloned().unwrap(),
+                    ty: field.ty.clone(),
+                    color: options.color,
+                })
+                .collect::<Vec<_>>();
+            let fields = bound
+                .iter()
+                .map(|field| syn::FieldPat {
+                    member: syn::Member::Named(field.ident.clone()),
                     colon_token: None,
                     attrs: vec![],
-                    pat: Box::new(syn::Pat::Ident(syn::PatIdent {
-                        ident,
-                        mutability: Some(syn::token::Mut {
-                            span: proc_macro2::Span::mixed_site(),
-                        }),
-                        attrs: vec![],
-                        by_ref: Some(syn::token::Ref {
-                            span: proc_macro2::Span::mixed_site(),
-                        }),
-                        subpat: None,
-                    })),
+                    pat: Box::new(ref_mut_binding(field.ident.clone())),
                 })
                 .collect::<syn::punctuated::Punctuated<syn::FieldPat, syn::Token![,]>>();
 
@@ -67,32 +137,33 @@ pub fn destructure_fields(path: syn::Path, fields: &syn::Fields) -> (syn::Pat, V
                 path,
             });
 
-            (pat, idents.into_iter().collect())
+            Ok((pat, bound))
         }
         syn::Fields::Unnamed(fields) => {
-            let idents = filter_ignored(fields.unnamed.iter())
+            let mut bound = vec![];
+            // Ignored fields still need to take up their position in the pattern
+            let fields = fields
+                .unnamed
+                .iter()
+                .zip(&options)
                 .enumerate()
-                .map(|(idx, _)| {
-                    // I actually have no idea how this compiles. This string is decided at runtime
-                    // yet I can take a static reference to it. Huh??
-                    let name = format!("_{}", idx);
-                    syn::Ident::new(&name, proc_macro2::Span::mixed_site())
-                });
-            let fields = idents
-                .clone()
-                .map(|ident| {
-                    syn::PatIdent {
-                        ident,
-                        mutability: Some(syn::token::Mut {
-                            span: proc_macro2::Span::mixed_site(),
-                        }),
-                        attrs: vec![],
-                        by_ref: Some(syn::token::Ref {
-                            span: proc_macro2::Span::mixed_site(),
-                        }),
-                        subpat: None,
+                .map(|(idx, (field, options))| {
+                    if options.ignore {
+                        syn::PatWild {
+                            attrs: vec![],
+                            underscore_token: Default::default(),
+                        }
+                        .into()
+                    } else {
+                        let ident =
+                            syn::Ident::new(&format!("_{}", idx), proc_macro2::Span::mixed_site());
+                        bound.push(BoundField {
+                            ident: ident.clone(),
+                            ty: field.ty.clone(),
+                            color: options.color,
+                        });
+                        ref_mut_binding(ident)
                     }
-                    .into()
                 })
                 .chain(std::iter::once::<syn::Pat>(
                     syn::PatRest {
@@ -112,15 +183,15 @@ pub fn destructure_fields(path: syn::Path, fields: &syn::Fields) -> (syn::Pat, V
                 },
                 attrs: vec![],
             });
-            (pat, idents.into_iter().collect())
+            Ok((pat, bound))
         }
-        syn::Fields::Unit => (
+        syn::Fields::Unit => Ok((
             syn::Pat::Path(syn::PatPath {
                 attrs: vec![],
                 path,
                 qself: None,
             }),
             vec![],
-        ),
+        )),
     }
 }
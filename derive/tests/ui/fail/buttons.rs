@@ -0,0 +1,39 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+#[inspectable(buttons = "reset")]
+struct NotAList {
+    value: i32,
+}
+
+#[derive(Inspectable)]
+#[inspectable(buttons(reset = true, "respawn"))]
+struct NotMethodNames {
+    value: i32,
+}
+
+#[derive(Inspectable)]
+#[inspectable(buttons(reset, respawn))]
+struct MissingMethod {
+    value: i32,
+}
+
+impl MissingMethod {
+    fn reset(&mut self) {
+        self.value = 0;
+    }
+}
+
+#[derive(Inspectable)]
+#[inspectable(buttons(set))]
+struct MethodWithArguments {
+    value: i32,
+}
+
+impl MethodWithArguments {
+    fn set(&mut self, value: i32) {
+        self.value = value;
+    }
+}
+
+fn main() {}
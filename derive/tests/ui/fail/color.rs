@@ -0,0 +1,15 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+struct NotAColor {
+    #[inspectable(color)]
+    name: String,
+}
+
+#[derive(Inspectable)]
+struct ColorWithValue {
+    #[inspectable(color = "srgb")]
+    color: [u8; 4],
+}
+
+fn main() {}
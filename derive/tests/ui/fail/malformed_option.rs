@@ -0,0 +1,27 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+struct FlagWithValue {
+    #[inspectable(ignore = true)]
+    value: i32,
+}
+
+#[derive(Inspectable)]
+struct FlagWithArguments {
+    #[inspectable(ignore(always))]
+    value: i32,
+}
+
+#[derive(Inspectable)]
+struct LiteralOption {
+    #[inspectable("ignore")]
+    value: i32,
+}
+
+#[derive(Inspectable)]
+struct BareAttribute {
+    #[inspectable]
+    value: i32,
+}
+
+fn main() {}
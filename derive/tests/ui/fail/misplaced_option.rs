@@ -0,0 +1,15 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+#[inspectable(ignore)]
+struct OnContainer {
+    value: i32,
+}
+
+#[derive(Inspectable)]
+enum OnVariant {
+    #[inspectable(ignore)]
+    Variant,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode)]
+enum Enum {
+    A,
+    B(i32),
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode)]
+struct UnknownOption {
+    #[tree_node(hide)]
+    value: Vec<i32>,
+}
+
+#[derive(Inspectable, TreeNode)]
+struct RenameWithoutString {
+    #[tree_node(rename = 3)]
+    value: Vec<i32>,
+    #[tree_node(rename)]
+    other: Vec<i32>,
+}
+
+#[derive(Inspectable, TreeNode)]
+struct ConflictingOptions {
+    #[tree_node(flatten, leaf)]
+    value: Vec<i32>,
+    #[tree_node(skip, rename = "other")]
+    other: Vec<i32>,
+}
+
+#[derive(Inspectable, TreeNode)]
+#[tree_node(skip)]
+struct OnContainer {
+    value: Vec<i32>,
+}
+
+fn main() {}
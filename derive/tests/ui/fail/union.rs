@@ -0,0 +1,15 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable)]
+union InspectableUnion {
+    integer: u32,
+    float: f32,
+}
+
+#[derive(TreeNode)]
+union TreeNodeUnion {
+    integer: u32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+struct UnknownFieldOption {
+    #[inspectable(hidden)]
+    value: i32,
+}
+
+#[derive(Inspectable)]
+struct SeveralUnknownOptions {
+    #[inspectable(ignore, readonly, rename = "other")]
+    value: i32,
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+#[inspectable(buttons(reset, describe))]
+struct Player<'a> {
+    health: i32,
+    clicked_buttons: &'a mut u32,
+}
+
+impl Player<'_> {
+    fn reset(&mut self) {
+        self.health = 100;
+    }
+
+    fn describe(&self) -> String {
+        format!("{} health", self.health)
+    }
+}
+
+#[derive(Inspectable)]
+#[inspectable(buttons(kill), buttons(revive))]
+enum State {
+    Alive { health: i32 },
+    Dead,
+}
+
+impl State {
+    fn kill(&mut self) {
+        *self = State::Dead;
+    }
+
+    fn revive(&mut self) {
+        *self = State::Alive { health: 100 };
+    }
+}
+
+fn main() {
+    fn assert_inspectable<T: Inspectable>() {}
+
+    assert_inspectable::<Player<'static>>();
+    assert_inspectable::<State>();
+}
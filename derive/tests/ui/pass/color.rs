@@ -0,0 +1,34 @@
+use guiedit::Inspectable;
+
+#[derive(Inspectable)]
+struct Light {
+    #[inspectable(color)]
+    color: [f32; 3],
+    #[inspectable(color)]
+    tint: [f32; 4],
+    #[inspectable(color)]
+    outline: [u8; 4],
+    intensity: f32,
+}
+
+#[derive(Inspectable)]
+struct Tinted<'a>(#[inspectable(color)] &'a mut [u8; 4], i32);
+
+#[derive(Inspectable)]
+enum Sky {
+    Solid(#[inspectable(color)] [f32; 3]),
+    Gradient {
+        #[inspectable(color)]
+        top: [f32; 3],
+        #[inspectable(color)]
+        bottom: [f32; 3],
+    },
+}
+
+fn main() {
+    fn assert_inspectable<T: Inspectable>() {}
+
+    assert_inspectable::<Light>();
+    assert_inspectable::<Tinted<'static>>();
+    assert_inspectable::<Sky>();
+}
@@ -0,0 +1,27 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode)]
+struct Buffer<const N: usize> {
+    values: [i32; N],
+}
+
+#[derive(Inspectable, TreeNode)]
+struct Mixed<'a, T, const N: usize = 4> {
+    reference: &'a mut T,
+    values: [f32; N],
+}
+
+#[derive(Inspectable)]
+enum ConstEnum<const N: usize> {
+    Empty,
+    Full([u8; N]),
+}
+
+fn main() {
+    fn assert_tree_node<T: TreeNode>() {}
+    fn assert_inspectable<T: Inspectable>() {}
+
+    assert_tree_node::<Buffer<3>>();
+    assert_tree_node::<Mixed<'static, bool>>();
+    assert_inspectable::<ConstEnum<2>>();
+}
@@ -0,0 +1,27 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode)]
+struct Bounded<T: Clone + Default> {
+    value: T,
+}
+
+#[derive(Inspectable, TreeNode)]
+struct WhereClause<T>
+where
+    T: Clone,
+{
+    value: T,
+}
+
+#[derive(Inspectable)]
+enum BoundedEnum<T: Clone, U: Default> {
+    First(T),
+    Second { value: U },
+}
+
+fn main() {
+    fn assert_tree_node<T: TreeNode>() {}
+
+    assert_tree_node::<Bounded<i32>>();
+    assert_tree_node::<WhereClause<String>>();
+}
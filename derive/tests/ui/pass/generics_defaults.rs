@@ -0,0 +1,20 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode)]
+struct WithDefault<T: Clone = u8> {
+    value: T,
+}
+
+#[derive(Inspectable)]
+enum EnumWithDefault<T = String> {
+    Unit,
+    Value(T),
+}
+
+fn main() {
+    fn assert_tree_node<T: TreeNode>() {}
+    fn assert_inspectable<T: Inspectable>() {}
+
+    assert_tree_node::<WithDefault>();
+    assert_inspectable::<EnumWithDefault>();
+}
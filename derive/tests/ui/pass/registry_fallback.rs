@@ -0,0 +1,27 @@
+use guiedit::Inspectable;
+
+struct Foreign;
+
+// Only `'static` fields can be looked up in the registry; the rest must still compile
+#[derive(Inspectable)]
+struct Fields<'a, T> {
+    owned: Foreign,
+    static_ref: &'static Foreign,
+    borrowed: &'a mut Foreign,
+    generic: T,
+    generic_vec: Vec<T>,
+    boxed: Box<dyn Fn(&'a Foreign)>,
+}
+
+#[derive(Inspectable)]
+enum Variants<'a> {
+    Owned(Foreign),
+    Borrowed { foreign: &'a Foreign },
+}
+
+fn main() {
+    fn assert_inspectable<T: Inspectable>() {}
+
+    assert_inspectable::<Fields<'static, Foreign>>();
+    assert_inspectable::<Variants<'static>>();
+}
@@ -0,0 +1,43 @@
+use guiedit::{Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Transform {
+    position: [f32; 2],
+    rotation: f32,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Physics {
+    velocity: [f32; 2],
+    transform: Transform,
+}
+
+struct NotANode;
+
+#[derive(Inspectable, TreeNode)]
+struct Player {
+    #[tree_node(skip)]
+    cache: Vec<Transform>,
+    #[tree_node(rename = "Player transform")]
+    transform: Transform,
+    #[tree_node(flatten)]
+    physics: Physics,
+    #[tree_node(leaf)]
+    children: Vec<Player>,
+    #[tree_node(leaf)]
+    #[inspectable(ignore)]
+    not_a_node: NotANode,
+}
+
+#[derive(Inspectable, TreeNode)]
+struct Tuple(
+    #[tree_node(skip)] Transform,
+    #[tree_node(rename = "inner")] Physics,
+);
+
+fn main() {
+    fn assert_tree_node<T: TreeNode>() {}
+
+    assert_tree_node::<Player>();
+    assert_tree_node::<Tuple>();
+}
@@ -33,6 +33,9 @@ fn main() {
             .body(|ui| self.contents_ui(id, selected, ui));
         }
 
+        // The contents of this node are not nodes themselves, so there are no children to visit
+        fn visit_children(&mut self, _id: u64, _f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {}
+
         fn contents_ui(&mut self, _id: u64, _selected: &mut Option<u64>, ui: &mut egui::Ui) {
             ui.label("Custom content!");
             use egui::plot::{Line, PlotPoints};
@@ -34,6 +34,9 @@ fn main() {
                 self.inspect_ui(ui);
             }
         }
+
+        // The variants are edited from the inspector, so this node has no children to visit
+        fn visit_children(&mut self, _id: u64, _f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {}
     }
 
     impl Into<Color> for BgColorKind {
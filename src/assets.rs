@@ -0,0 +1,290 @@
+//! Watching the files assets are loaded from, so that they are reloaded when they change.
+//!
+//! Assets register the file they were loaded from in an [`AssetWatcher`], and reload themselves
+//! through [`Inspectable::reload_if_changed`] once it changes, which [`reload_changed`] calls for
+//! every value in a tree. See `guiedit::sfml::assets::Asset` for SFML textures, fonts and sound
+//! buffers.
+
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::inspectable::Inspectable;
+
+/// How long a file must go without changes before it is reloaded, since it may still be being
+/// written.
+pub const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A file assets were loaded from, as listed in the assets panel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchedAsset {
+    /// The absolute path of the file.
+    pub path: PathBuf,
+    /// The kind of asset loaded from the file, e.g. "Texture".
+    pub kind: &'static str,
+    /// The number of times the assets were reloaded from the file.
+    pub reloads: u32,
+    /// The error the last time the assets were loaded from the file, if it failed.
+    pub error: Option<String>,
+}
+
+/// Watches the files assets are loaded from for changes. Cloning it gives another handle to the
+/// same watcher.
+#[derive(Clone)]
+pub struct AssetWatcher {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    /// The watcher of the directories of the files, or the error it could not be created with.
+    ///
+    /// Directories are watched instead of the files themselves, since many programs save files by
+    /// replacing them.
+    watcher: Result<RecommendedWatcher, String>,
+    events: Receiver<notify::Result<notify::Event>>,
+    /// The number of assets loaded from each directory watched.
+    directories: BTreeMap<PathBuf, usize>,
+    files: BTreeMap<PathBuf, File>,
+    /// The number of changes to the files watched, and the number of them that were already
+    /// reported by [`AssetWatcher::take_changes`].
+    changes: u64,
+    changes_taken: u64,
+    last_change: Option<Instant>,
+}
+
+struct File {
+    asset: WatchedAsset,
+    /// The number of assets loaded from the file.
+    handles: usize,
+    /// The number of times the file changed since it was first loaded.
+    version: u64,
+    last_change: Option<Instant>,
+}
+
+impl Default for AssetWatcher {
+    fn default() -> Self {
+        let (sender, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(sender).map_err(|error| error.to_string());
+        Self {
+            state: Arc::new(Mutex::new(State {
+                watcher,
+                events,
+                directories: BTreeMap::new(),
+                files: BTreeMap::new(),
+                changes: 0,
+                changes_taken: 0,
+                last_change: None,
+            })),
+        }
+    }
+}
+
+impl AssetWatcher {
+    /// Starts watching the file at the path given, which an asset of the kind given was just
+    /// loaded from. The file is watched until the handle returned is dropped.
+    pub fn watch(&self, path: impl AsRef<Path>, kind: &'static str) -> io::Result<AssetHandle> {
+        let path = path.as_ref().canonicalize()?;
+        let directory = path.parent().unwrap_or(Path::new("/")).to_owned();
+
+        let mut state = self.state();
+        let state = &mut *state;
+        let directory_assets = state.directories.entry(directory.clone()).or_insert(0);
+        if *directory_assets == 0 {
+            if let Ok(watcher) = &mut state.watcher {
+                watcher
+                    .watch(&directory, RecursiveMode::NonRecursive)
+                    .map_err(io::Error::other)?;
+            }
+        }
+        *directory_assets += 1;
+
+        let file = state.files.entry(path.clone()).or_insert_with(|| File {
+            asset: WatchedAsset {
+                path: path.clone(),
+                kind,
+                reloads: 0,
+                error: None,
+            },
+            handles: 0,
+            version: 0,
+            last_change: None,
+        });
+        file.handles += 1;
+        Ok(AssetHandle {
+            path,
+            version: file.version,
+            state: self.state.clone(),
+        })
+    }
+
+    /// The files watched, sorted by path.
+    pub fn assets(&self) -> Vec<WatchedAsset> {
+        let state = self.state();
+        let files = state.files.values();
+        files.map(|file| file.asset.clone()).collect()
+    }
+
+    /// Returns whether any file watched changed since the last call, once it stopped changing for
+    /// [`DEBOUNCE`], meaning that the assets loaded from it should be reloaded.
+    pub fn take_changes(&self) -> bool {
+        let mut state = self.state();
+        if state.changes == state.changes_taken || !settled(state.last_change) {
+            return false;
+        }
+        state.changes_taken = state.changes;
+        true
+    }
+
+    /// The error the files could not be watched because of, if any. Assets can still be reloaded
+    /// manually then.
+    pub fn error(&self) -> Option<String> {
+        self.state().watcher.as_ref().err().cloned()
+    }
+
+    /// Shows the files watched along with the status of their assets.
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        if let Some(error) = self.error() {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!("Files cannot be watched: {}", error),
+            );
+        }
+        let assets = self.assets();
+        if assets.is_empty() {
+            ui.weak("No assets loaded");
+            return;
+        }
+        egui::Grid::new("guiedit assets")
+            .striped(true)
+            .show(ui, |ui| {
+                for asset in assets {
+                    ui.label(asset.kind);
+                    ui.label(asset.path.display().to_string());
+                    ui.label(format!("{} reloads", asset.reloads));
+                    match asset.error {
+                        Some(error) => ui.colored_label(ui.visuals().error_fg_color, error),
+                        None => ui.weak("Loaded"),
+                    };
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Locks the state after handling the events received since it was last locked.
+    fn state(&self) -> MutexGuard<'_, State> {
+        let mut state = self.state.lock().unwrap();
+        state.handle_events();
+        state
+    }
+}
+
+impl State {
+    fn handle_events(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            if event.kind.is_access() {
+                continue;
+            }
+            for path in &event.paths {
+                if let Some(file) = self.files.get_mut(path) {
+                    let now = Instant::now();
+                    file.version += 1;
+                    file.last_change = Some(now);
+                    self.changes += 1;
+                    self.last_change = Some(now);
+                }
+            }
+        }
+    }
+}
+
+/// The registration of an asset in an [`AssetWatcher`], which keeps the file it was loaded from
+/// watched while it lives.
+pub struct AssetHandle {
+    path: PathBuf,
+    /// The version of the file the asset was last loaded from.
+    version: u64,
+    state: Arc<Mutex<State>>,
+}
+
+impl AssetHandle {
+    /// The absolute path of the file the asset was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether the file changed since the asset was last loaded, and stopped changing for
+    /// [`DEBOUNCE`].
+    pub fn is_changed(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.handle_events();
+        state
+            .files
+            .get(&self.path)
+            .is_some_and(|file| file.version != self.version && settled(file.last_change))
+    }
+
+    /// Records that the asset was reloaded from the current version of the file, successfully or
+    /// with the error given.
+    pub fn reloaded(&mut self, result: Result<(), String>) {
+        let mut state = self.state.lock().unwrap();
+        state.handle_events();
+        if let Some(file) = state.files.get_mut(&self.path) {
+            self.version = file.version;
+            file.asset.reloads += result.is_ok() as u32;
+            file.asset.error = result.err();
+        }
+    }
+}
+
+impl Drop for AssetHandle {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        if let Some(file) = state.files.get_mut(&self.path) {
+            file.handles -= 1;
+            if file.handles == 0 {
+                state.files.remove(&self.path);
+            }
+        }
+
+        let directory = self.path.parent().unwrap_or(Path::new("/"));
+        if let Some(directory_assets) = state.directories.get_mut(directory) {
+            *directory_assets -= 1;
+            if *directory_assets == 0 {
+                state.directories.remove(directory);
+                if let Ok(watcher) = &mut state.watcher {
+                    let _ = watcher.unwatch(directory);
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether a file that last changed at the time given, if any, stopped changing for
+/// [`DEBOUNCE`].
+fn settled(last_change: Option<Instant>) -> bool {
+    match last_change {
+        Some(time) => time.elapsed() >= DEBOUNCE,
+        None => true,
+    }
+}
+
+/// Reloads every asset in the value given and its fields whose file changed, e.g. in the root node
+/// of a tree, returning the number of assets reloaded.
+pub fn reload_changed<T: Inspectable + ?Sized>(value: &mut T) -> usize {
+    let mut reloaded = value.reload_if_changed() as usize;
+    value.visit_fields(&mut |_, field| reloaded += reload_changed(field));
+    reloaded
+}
@@ -0,0 +1,209 @@
+//! Standalone viewer for applications serving their node tree with `guiedit::remote::Server`.
+//!
+//! Usage: `guiedit-viewer [ADDRESS]`, where the address defaults to
+//! `guiedit::remote::DEFAULT_ADDRESS`.
+
+use std::time::{Duration, Instant};
+
+use egui_sfml::SfEgui;
+use guiedit::{
+    egui,
+    inspectable::Value,
+    remote::{Client, Field, Node, DEFAULT_ADDRESS},
+    tree,
+};
+use sfml::{
+    graphics::{Color, RenderTarget, RenderWindow},
+    window::{Event, Style},
+};
+
+/// How often the tree and the fields shown are requested again.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Default)]
+struct Viewer {
+    address: String,
+    client: Option<Client>,
+    error: Option<String>,
+    tree: Option<Node>,
+    selected: Option<u64>,
+    fields: Vec<Field>,
+}
+
+impl Viewer {
+    fn connect(&mut self) {
+        self.tree = None;
+        self.fields.clear();
+        match Client::connect(&self.address) {
+            Ok(client) => {
+                self.client = Some(client);
+                self.error = None;
+                self.refresh();
+            }
+            Err(error) => self.fail(error),
+        }
+    }
+
+    fn refresh(&mut self) {
+        let Some(client) = &mut self.client else {
+            return;
+        };
+        let result = client.tree().and_then(|tree| {
+            self.tree = Some(tree);
+            match self.selected {
+                Some(selected) => client.fields(selected),
+                None => Ok(vec![]),
+            }
+        });
+        match result {
+            Ok(fields) => self.fields = fields,
+            Err(error) => self.fail(error),
+        }
+    }
+
+    fn set(&mut self, path: Vec<String>, value: Value) {
+        let (Some(client), Some(selected)) = (&mut self.client, self.selected) else {
+            return;
+        };
+        if let Err(error) = client.set(selected, path, value) {
+            if client.is_connected() {
+                self.error = Some(error.to_string());
+            } else {
+                self.fail(error);
+            }
+        }
+    }
+
+    fn fail(&mut self, error: std::io::Error) {
+        self.client = None;
+        self.error = Some(error.to_string());
+    }
+
+    fn ui(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("connection").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Address");
+                ui.text_edit_singleline(&mut self.address);
+                if ui.button("Connect").clicked() {
+                    self.connect();
+                }
+                match (&self.client, &self.error) {
+                    (_, Some(error)) => ui.colored_label(egui::Color32::RED, error),
+                    (Some(_), None) => ui.label("Connected"),
+                    (None, None) => ui.label("Disconnected"),
+                };
+            });
+        });
+
+        egui::SidePanel::left("tree").show(ctx, |ui| {
+            ui.vertical_centered(|ui| ui.heading("Tree"));
+            if let Some(root) = &self.tree {
+                let previous = self.selected;
+                egui::ScrollArea::vertical().show(ui, |ui| node_ui(root, &mut self.selected, ui));
+                if self.selected != previous {
+                    self.fields.clear();
+                    self.refresh();
+                }
+            }
+        });
+
+        let mut edits = vec![];
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| ui.heading("Inspector"));
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for field in &mut self.fields {
+                    field_ui(field, &mut vec![], &mut edits, ui);
+                }
+            });
+        });
+        for (path, value) in edits {
+            self.set(path, value);
+        }
+    }
+}
+
+fn node_ui(node: &Node, selected: &mut Option<u64>, ui: &mut egui::Ui) {
+    if node.children.is_empty() {
+        tree::default_node_ui("", &node.name, node.id, selected, ui);
+    } else {
+        tree::default_parent_node_ui("", &node.name, node.id, selected, ui, |_, selected, ui| {
+            for child in &node.children {
+                node_ui(child, selected, ui);
+            }
+        });
+    }
+}
+
+/// Shows a field and its own fields, collecting the edits done to any of them.
+fn field_ui(
+    field: &mut Field,
+    path: &mut Vec<String>,
+    edits: &mut Vec<(Vec<String>, Value)>,
+    ui: &mut egui::Ui,
+) {
+    path.push(field.name.clone());
+    if let Some(value) = &mut field.value {
+        ui.horizontal(|ui| {
+            ui.label(&field.name);
+            let changed = match value {
+                Value::Bool(value) => ui.checkbox(value, "").changed(),
+                Value::Int(value) => ui.add(egui::DragValue::new(value)).changed(),
+                Value::UInt(value) => ui.add(egui::DragValue::new(value)).changed(),
+                Value::Float(value) => ui.add(egui::DragValue::new(value)).changed(),
+                Value::String(value) => ui.text_edit_singleline(value).changed(),
+            };
+            if changed {
+                edits.push((path.clone(), value.clone()));
+            }
+        });
+    }
+    if !field.fields.is_empty() {
+        egui::CollapsingHeader::new(&field.name)
+            .id_source(&path)
+            .show(ui, |ui| {
+                for field in &mut field.fields {
+                    field_ui(field, path, edits, ui);
+                }
+            });
+    }
+    path.pop();
+}
+
+fn main() {
+    let mut viewer = Viewer {
+        address: std::env::args()
+            .nth(1)
+            .unwrap_or_else(|| DEFAULT_ADDRESS.to_owned()),
+        ..Default::default()
+    };
+    viewer.connect();
+
+    let mut window = RenderWindow::new(
+        (1024, 768),
+        "guiedit viewer",
+        Style::DEFAULT,
+        &Default::default(),
+    );
+    window.set_vertical_sync_enabled(true);
+    let mut egui_ctx = SfEgui::new(&window);
+    let mut last_refresh = Instant::now();
+
+    while window.is_open() {
+        while let Some(event) = window.poll_event() {
+            egui_ctx.add_event(&event);
+            if let Event::Closed = event {
+                window.close();
+            }
+        }
+
+        if last_refresh.elapsed() >= REFRESH_INTERVAL {
+            viewer.refresh();
+            last_refresh = Instant::now();
+        }
+
+        egui_ctx.do_frame(|ctx| viewer.ui(ctx)).unwrap();
+        window.clear(Color::BLACK);
+        egui_ctx.draw(&mut window, None);
+        window.display();
+    }
+}
@@ -0,0 +1,353 @@
+//! Captures of the frames rendered by the application, as PNG screenshots and as recordings made of
+//! a sequence of PNG files or a GIF.
+//!
+//! Backends read their viewport back into a [`Frame`] and hand it to [`Captures`], which does the
+//! captures requested from the editor or with a hotkey. Recordings are encoded and written on a
+//! background thread, so that the application keeps its framerate while recording. Frames are
+//! dropped from recordings when that thread falls behind, instead of piling up in memory.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    thread,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The maximum number of frames waiting to be written for a recording.
+const MAX_QUEUED_FRAMES: usize = 8;
+
+/// A frame rendered by the application, in unmultiplied RGBA and starting from its top row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Frame {
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_png(io::BufWriter::new(fs::File::create(path)?))
+    }
+
+    pub fn write_png(&self, writer: impl io::Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+}
+
+/// Returns a path in the directory given for a new capture with the extension given, named after
+/// the current time. An empty extension gives a path without any, e.g. for a directory.
+pub fn timestamped_path(dir: &Path, extension: &str) -> PathBuf {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let name = format!("capture-{}-{:03}", time.as_secs(), time.subsec_millis());
+    dir.join(name).with_extension(extension)
+}
+
+/// How the frames of a recording are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// Every frame is written to a numbered PNG file in a directory.
+    PngSequence,
+    /// The frames are written to an animated GIF, timed like they were recorded.
+    #[default]
+    Gif,
+}
+
+impl RecordingFormat {
+    /// Returns the format of a recording to the path given: A GIF if it has the `gif` extension,
+    /// or a sequence of PNG files in a directory otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("gif") => RecordingFormat::Gif,
+            _ => RecordingFormat::PngSequence,
+        }
+    }
+
+    /// The extension of the paths of recordings in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordingFormat::PngSequence => "",
+            RecordingFormat::Gif => "gif",
+        }
+    }
+}
+
+/// A recording in progress, whose frames are written on a background thread.
+pub struct Recording {
+    path: PathBuf,
+    format: RecordingFormat,
+    frames: usize,
+    dropped_frames: usize,
+    sender: SyncSender<(Frame, Instant)>,
+    writer: thread::JoinHandle<io::Result<()>>,
+}
+
+impl Recording {
+    /// Starts recording to the path given, in the format given by
+    /// [`RecordingFormat::from_path`].
+    pub fn start(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let format = RecordingFormat::from_path(&path);
+        let (sender, frames) = mpsc::sync_channel(MAX_QUEUED_FRAMES);
+        let writer = match format {
+            RecordingFormat::PngSequence => {
+                fs::create_dir_all(&path)?;
+                let dir = path.clone();
+                thread::spawn(move || write_png_sequence(&dir, frames))
+            }
+            RecordingFormat::Gif => {
+                let file = io::BufWriter::new(fs::File::create(&path)?);
+                thread::spawn(move || write_gif(file, frames))
+            }
+        };
+        Ok(Self {
+            path,
+            format,
+            frames: 0,
+            dropped_frames: 0,
+            sender,
+            writer,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn format(&self) -> RecordingFormat {
+        self.format
+    }
+
+    /// The number of frames recorded so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// The number of frames dropped so far because they were added faster than they could be
+    /// written.
+    pub fn dropped_frames(&self) -> usize {
+        self.dropped_frames
+    }
+
+    /// Adds a frame to the recording, timed from when it is added, unless too many frames are
+    /// still waiting to be written. Frames that are dropped make the previous one last longer in
+    /// GIFs.
+    pub fn push(&mut self, frame: Frame) {
+        match self.sender.try_send((frame, Instant::now())) {
+            Ok(()) => self.frames += 1,
+            Err(TrySendError::Full(_)) => self.dropped_frames += 1,
+            // The writer only stops early on errors, which are returned by `finish`
+            Err(TrySendError::Disconnected(_)) => {}
+        }
+    }
+
+    /// Waits for every frame to be written, and returns the path of the recording.
+    pub fn finish(self) -> io::Result<PathBuf> {
+        drop(self.sender);
+        self.writer
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("the recording writer panicked")))?;
+        Ok(self.path)
+    }
+}
+
+fn write_png_sequence(dir: &Path, frames: Receiver<(Frame, Instant)>) -> io::Result<()> {
+    for (idx, (frame, _)) in frames.into_iter().enumerate() {
+        frame.save_png(dir.join(format!("frame-{:05}.png", idx)))?;
+    }
+    Ok(())
+}
+
+fn write_gif(writer: impl io::Write, frames: Receiver<(Frame, Instant)>) -> io::Result<()> {
+    let gif_error = |error| match error {
+        gif::EncodingError::Io(error) => error,
+        error => io::Error::new(io::ErrorKind::InvalidData, error),
+    };
+
+    let mut frames = frames.into_iter().peekable();
+    let (width, height) = match frames.peek() {
+        Some((frame, _)) => (frame.width, frame.height),
+        None => return Ok(()),
+    };
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "frame too large for a GIF");
+    let size = (
+        u16::try_from(width).map_err(|_| too_large())?,
+        u16::try_from(height).map_err(|_| too_large())?,
+    );
+    let mut encoder = gif::Encoder::new(writer, size.0, size.1, &[]).map_err(gif_error)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(gif_error)?;
+
+    // A frame is shown until the next one was recorded
+    let mut last_delay = 0;
+    while let Some((mut frame, time)) = frames.next() {
+        if (frame.width, frame.height) != (width, height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the size of the frames recorded changed",
+            ));
+        }
+        if let Some((_, next_time)) = frames.peek() {
+            let centiseconds = (*next_time - time).as_millis() / 10;
+            last_delay = u16::try_from(centiseconds).unwrap_or(u16::MAX);
+        }
+        let mut gif_frame = gif::Frame::from_rgba_speed(size.0, size.1, &mut frame.pixels, 10);
+        gif_frame.delay = last_delay;
+        encoder.write_frame(&gif_frame).map_err(gif_error)?;
+    }
+    Ok(())
+}
+
+/// The captures of a viewport, which are requested from the editor or with a hotkey and done once
+/// the next frame is displayed.
+pub struct Captures {
+    /// The directory screenshots and recordings are saved to.
+    pub dir: PathBuf,
+    /// The format of the recordings started from the editor or with a hotkey.
+    pub format: RecordingFormat,
+    recording: Option<Recording>,
+    screenshot_requested: bool,
+    recording_toggle_requested: bool,
+    /// The path of the last capture saved, or the error it could not be saved with.
+    last: Option<Result<PathBuf, String>>,
+}
+
+impl Default for Captures {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("captures"),
+            format: RecordingFormat::default(),
+            recording: None,
+            screenshot_requested: false,
+            recording_toggle_requested: false,
+            last: None,
+        }
+    }
+}
+
+impl Captures {
+    /// Saves a screenshot to [`dir`](Self::dir) once the next frame is displayed.
+    pub fn request_screenshot(&mut self) {
+        self.screenshot_requested = true;
+    }
+
+    /// Starts recording to [`dir`](Self::dir), or stops the recording in progress, once the next
+    /// frame is displayed.
+    pub fn request_recording_toggle(&mut self) {
+        self.recording_toggle_requested = true;
+    }
+
+    /// Starts recording to the path given, in the format given by
+    /// [`RecordingFormat::from_path`], stopping the recording in progress if any.
+    pub fn start_recording(&mut self, path: impl Into<PathBuf>) -> io::Result<()> {
+        self.stop_recording();
+        self.recording = Some(Recording::start(path)?);
+        Ok(())
+    }
+
+    /// Stops the recording in progress, if any, and returns its path once all of its frames are
+    /// written.
+    pub fn stop_recording(&mut self) -> Option<io::Result<PathBuf>> {
+        let result = self.recording.take()?.finish();
+        self.last = Some(match &result {
+            Ok(path) => Ok(path.clone()),
+            Err(error) => Err(format!("Could not save the recording: {}", error)),
+        });
+        Some(result)
+    }
+
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recording.as_ref()
+    }
+
+    /// Does the captures requested, and adds a frame to the recording in progress, after a frame
+    /// is displayed. The frame is only read back with `capture` if needed.
+    pub fn frame_displayed(&mut self, capture: impl FnOnce() -> Option<Frame>) {
+        if std::mem::take(&mut self.recording_toggle_requested) {
+            if self.recording.is_some() {
+                self.stop_recording();
+            } else {
+                let path = timestamped_path(&self.dir, self.format.extension());
+                let result =
+                    fs::create_dir_all(&self.dir).and_then(|()| self.start_recording(path));
+                if let Err(error) = result {
+                    self.last = Some(Err(format!("Could not start recording: {}", error)));
+                }
+            }
+        }
+
+        let screenshot = std::mem::take(&mut self.screenshot_requested);
+        if !screenshot && self.recording.is_none() {
+            return;
+        }
+        let frame = match capture() {
+            Some(frame) => frame,
+            None => return,
+        };
+        if screenshot {
+            let path = timestamped_path(&self.dir, "png");
+            let result = fs::create_dir_all(&self.dir).and_then(|()| frame.save_png(&path));
+            self.last = Some(match result {
+                Ok(()) => Ok(path),
+                Err(error) => Err(format!("Could not save the screenshot: {}", error)),
+            });
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.push(frame);
+        }
+    }
+
+    /// Shows buttons to take a screenshot and to start or stop recording, along with the last
+    /// capture saved.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("📷 Screenshot").clicked() {
+                self.request_screenshot();
+            }
+            let counts = self
+                .recording
+                .as_ref()
+                .map(|recording| (recording.frames(), recording.dropped_frames()));
+            match counts {
+                Some((frames, dropped)) => {
+                    if ui.button("⏹ Stop recording").clicked() {
+                        self.request_recording_toggle();
+                    }
+                    ui.label(format!("{} frames", frames));
+                    if dropped > 0 {
+                        ui.colored_label(
+                            ui.visuals().warn_fg_color,
+                            format!("{} dropped", dropped),
+                        )
+                        .on_hover_text("Frames are rendered faster than they can be written");
+                    }
+                }
+                None => {
+                    if ui.button("⏺ Record").clicked() {
+                        self.request_recording_toggle();
+                    }
+                    ui.selectable_value(&mut self.format, RecordingFormat::Gif, "GIF");
+                    ui.selectable_value(&mut self.format, RecordingFormat::PngSequence, "PNG");
+                }
+            }
+        });
+        match &self.last {
+            Some(Ok(path)) => {
+                ui.weak(format!("Saved {}", path.display()));
+            }
+            Some(Err(error)) => {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            None => {
+                ui.weak(format!("Captures are saved to {}", self.dir.display()));
+            }
+        }
+    }
+}
@@ -0,0 +1,472 @@
+//! A color editor for the inspector, with HSV and hex entry, an eyedropper picking colors from the
+//! viewport and a palette of saved colors.
+//!
+//! Colors are edited through [`EditColor`], which is implemented for `[f32; 3]` and `[f32; 4]` in
+//! linear RGB(A) and for `[u8; 4]` in sRGBA. Fields of these types are edited as colors when
+//! marked with `#[inspectable(color)]`.
+
+use egui::{
+    color::{
+        gamma_u8_from_linear_f32, linear_f32_from_gamma_u8, linear_f32_from_linear_u8,
+        linear_u8_from_linear_f32, Hsva,
+    },
+    color_picker::Alpha,
+    Color32, DragValue, Id, Sense,
+};
+
+use crate::inspectable::Inspectable;
+
+/// A value that can be edited as a color.
+///
+/// Alpha is always unmultiplied, i.e. the color components do not depend on it.
+pub trait EditColor {
+    /// Whether the color has an alpha channel.
+    const ALPHA: bool;
+    /// Whether the color can be brighter than white, i.e. its components can go over 1.
+    const HDR: bool;
+
+    /// Returns the color in linear RGBA.
+    fn to_rgba(&self) -> [f32; 4];
+    /// Sets the color from linear RGBA.
+    fn set_rgba(&mut self, rgba: [f32; 4]);
+}
+
+impl EditColor for [f32; 3] {
+    const ALPHA: bool = false;
+    const HDR: bool = true;
+
+    fn to_rgba(&self) -> [f32; 4] {
+        let [r, g, b] = *self;
+        [r, g, b, 1.]
+    }
+
+    fn set_rgba(&mut self, [r, g, b, _]: [f32; 4]) {
+        *self = [r, g, b];
+    }
+}
+
+impl EditColor for [f32; 4] {
+    const ALPHA: bool = true;
+    const HDR: bool = true;
+
+    fn to_rgba(&self) -> [f32; 4] {
+        *self
+    }
+
+    fn set_rgba(&mut self, rgba: [f32; 4]) {
+        *self = rgba;
+    }
+}
+
+impl EditColor for [u8; 4] {
+    const ALPHA: bool = true;
+    const HDR: bool = false;
+
+    fn to_rgba(&self) -> [f32; 4] {
+        rgba_from_srgba(*self)
+    }
+
+    fn set_rgba(&mut self, rgba: [f32; 4]) {
+        *self = srgba_from_rgba(rgba);
+    }
+}
+
+impl<T: EditColor + ?Sized> EditColor for &mut T {
+    const ALPHA: bool = T::ALPHA;
+    const HDR: bool = T::HDR;
+
+    fn to_rgba(&self) -> [f32; 4] {
+        (**self).to_rgba()
+    }
+
+    fn set_rgba(&mut self, rgba: [f32; 4]) {
+        (**self).set_rgba(rgba);
+    }
+}
+
+/// Converts a color from sRGBA to linear RGBA, both with unmultiplied alpha.
+pub fn rgba_from_srgba([r, g, b, a]: [u8; 4]) -> [f32; 4] {
+    [
+        linear_f32_from_gamma_u8(r),
+        linear_f32_from_gamma_u8(g),
+        linear_f32_from_gamma_u8(b),
+        linear_f32_from_linear_u8(a),
+    ]
+}
+
+/// Converts a color from linear RGBA to sRGBA, both with unmultiplied alpha, clamping it to the
+/// range of sRGBA.
+pub fn srgba_from_rgba([r, g, b, a]: [f32; 4]) -> [u8; 4] {
+    [
+        gamma_u8_from_linear_f32(r),
+        gamma_u8_from_linear_f32(g),
+        gamma_u8_from_linear_f32(b),
+        linear_u8_from_linear_f32(a.clamp(0., 1.)),
+    ]
+}
+
+/// Returns the hex code of a color in sRGBA, e.g. `#FF8000` or `#FF800080` if `alpha` is set.
+pub fn to_hex([r, g, b, a]: [u8; 4], alpha: bool) -> String {
+    if alpha {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    } else {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    }
+}
+
+/// Parses a color in sRGBA from its hex code, with or without a leading `#` and with or without
+/// alpha, which is opaque if not given.
+pub fn parse_hex(hex: &str) -> Option<[u8; 4]> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+    let component = |idx: usize| u8::from_str_radix(&hex[idx * 2..idx * 2 + 2], 16).ok();
+    match hex.len() {
+        6 => Some([component(0)?, component(1)?, component(2)?, u8::MAX]),
+        8 => Some([component(0)?, component(1)?, component(2)?, component(3)?]),
+        _ => None,
+    }
+}
+
+/// The colors saved from the color editor, in sRGBA.
+///
+/// The palette is kept in the egui memory, so it is saved along with the rest of the editor state
+/// with the `persistence` feature.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    pub colors: Vec<[u8; 4]>,
+}
+
+impl Palette {
+    fn id() -> Id {
+        Id::new("guiedit palette")
+    }
+
+    pub fn load(ctx: &egui::Context) -> Self {
+        ctx.data().get_persisted(Self::id()).unwrap_or_default()
+    }
+
+    pub fn store(self, ctx: &egui::Context) {
+        ctx.data().insert_persisted(Self::id(), self);
+    }
+}
+
+/// The state of the eyedropper, which is shared by all color editors.
+#[derive(Clone, Copy)]
+enum Eyedropper {
+    /// The editor with the ID given is waiting for a color to be picked.
+    Picking(Id),
+    /// A color in sRGBA was picked for the editor with the ID given.
+    Picked(Id, [u8; 4]),
+}
+
+fn eyedropper_id() -> Id {
+    Id::new("guiedit eyedropper")
+}
+
+/// Returns whether a color editor is waiting for a color to be picked from the viewport, in which
+/// case clicks on the viewport should call [`pick`] instead of reaching the application.
+pub fn is_picking(ctx: &egui::Context) -> bool {
+    matches!(
+        ctx.data().get_temp(eyedropper_id()),
+        Some(Eyedropper::Picking(_))
+    )
+}
+
+/// Gives the color, in sRGBA, picked from the viewport to the color editor waiting for it, if any.
+pub fn pick(ctx: &egui::Context, srgba: [u8; 4]) {
+    let mut data = ctx.data();
+    if let Some(Eyedropper::Picking(id)) = data.get_temp(eyedropper_id()) {
+        data.insert_temp(eyedropper_id(), Eyedropper::Picked(id, srgba));
+    }
+}
+
+pub fn cancel_picking(ctx: &egui::Context) {
+    ctx.data().remove::<Eyedropper>(eyedropper_id());
+}
+
+/// Returns the color picked for the editor with the ID given, if any.
+fn take_picked(ctx: &egui::Context, id: Id) -> Option<[u8; 4]> {
+    let mut data = ctx.data();
+    match data.get_temp(eyedropper_id()) {
+        Some(Eyedropper::Picked(picked_id, srgba)) if picked_id == id => {
+            data.remove::<Eyedropper>(eyedropper_id());
+            Some(srgba)
+        }
+        _ => None,
+    }
+}
+
+/// A color as edited, split into a color no brighter than white and the intensity it is
+/// multiplied by.
+///
+/// It is kept while editing, since HSV cannot be recovered from RGB for grays and blacks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Editing {
+    hsva: Hsva,
+    intensity: f32,
+}
+
+impl Editing {
+    fn new([r, g, b, a]: [f32; 4]) -> Self {
+        let intensity = r.max(g).max(b).max(1.);
+        Self {
+            hsva: Hsva::from_rgba_unmultiplied(r / intensity, g / intensity, b / intensity, a),
+            intensity,
+        }
+    }
+
+    fn to_rgba(self) -> [f32; 4] {
+        let [r, g, b, a] = self.hsva.to_rgba_unmultiplied();
+        [
+            r * self.intensity,
+            g * self.intensity,
+            b * self.intensity,
+            a,
+        ]
+    }
+
+    /// Returns the color without its intensity, in sRGBA.
+    fn srgba(self) -> [u8; 4] {
+        srgba_from_rgba(self.hsva.to_rgba_unmultiplied())
+    }
+
+    /// Sets the color without its intensity from sRGBA, keeping the current alpha unless `alpha`
+    /// is set.
+    fn set_srgba(&mut self, srgba: [u8; 4], alpha: bool) {
+        let [r, g, b, a] = rgba_from_srgba(srgba);
+        let a = if alpha { a } else { self.hsva.a };
+        self.hsva = Hsva::from_rgba_unmultiplied(r, g, b, a);
+    }
+}
+
+/// Shows a clickable swatch of the color given, like egui's color buttons.
+fn swatch(ui: &mut egui::Ui, color: Color32) -> egui::Response {
+    let (_, response) = ui.allocate_exact_size(ui.spacing().interact_size, Sense::click());
+    paint_swatch(ui, &response, color, false);
+    response
+}
+
+fn paint_swatch(ui: &egui::Ui, response: &egui::Response, color: Color32, open: bool) {
+    if ui.is_rect_visible(response.rect) {
+        let visuals = if open {
+            &ui.visuals().widgets.open
+        } else {
+            ui.style().interact(response)
+        };
+        let rect = response.rect.expand(visuals.expansion);
+        egui::color_picker::show_color_at(ui.painter(), color, rect);
+        ui.painter()
+            .rect_stroke(rect, visuals.rounding.at_most(2.), (2., visuals.bg_fill));
+    }
+}
+
+/// Shows a button with the color given which opens a color editor when clicked. Returns whether
+/// the color changed.
+///
+/// Besides the usual color picker, the editor has HSV and hex entry, an intensity for HDR colors,
+/// an eyedropper and the [`Palette`].
+pub fn color_edit_ui<C: EditColor + ?Sized>(ui: &mut egui::Ui, color: &mut C) -> bool {
+    let (_, button) = ui.allocate_exact_size(ui.spacing().interact_size, Sense::click());
+    let popup_id = button.id.with("popup");
+    let ctx = ui.ctx().clone();
+    let rgba = color.to_rgba();
+    // Keep the hue of grays while editing them
+    let mut editing = match ctx.data().get_temp::<([f32; 4], Editing)>(popup_id) {
+        Some((edited, editing)) if edited == rgba => editing,
+        _ => Editing::new(rgba),
+    };
+    let mut changed = false;
+
+    if let Some(picked) = take_picked(&ctx, popup_id) {
+        editing.set_srgba(picked, false);
+        changed = true;
+    }
+    let picking = matches!(
+        ctx.data().get_temp(eyedropper_id()),
+        Some(Eyedropper::Picking(id)) if id == popup_id
+    );
+    if picking && ui.input().key_pressed(egui::Key::Escape) {
+        cancel_picking(&ctx);
+    }
+
+    let open = ui.memory().is_popup_open(popup_id);
+    paint_swatch(ui, &button, editing.hsva.into(), open);
+    let mut hover_text = to_hex(editing.srgba(), C::ALPHA);
+    if editing.intensity > 1. {
+        hover_text += &format!(" ×{:.2}", editing.intensity);
+    }
+    let button = button.on_hover_text(hover_text);
+    if button.clicked() {
+        ui.memory().toggle_popup(popup_id);
+    }
+
+    if ui.memory().is_popup_open(popup_id) {
+        let alpha = if C::ALPHA {
+            Alpha::OnlyBlend
+        } else {
+            Alpha::Opaque
+        };
+        let area_response = egui::Area::new(popup_id)
+            .order(egui::Order::Foreground)
+            .current_pos(button.rect.max)
+            .show(&ctx, |ui| {
+                ui.spacing_mut().slider_width = 210.;
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    changed |=
+                        egui::color_picker::color_picker_hsva_2d(ui, &mut editing.hsva, alpha);
+                    changed |= hsva_ui(ui, &mut editing.hsva, C::ALPHA);
+                    if C::HDR {
+                        let intensity = DragValue::new(&mut editing.intensity)
+                            .clamp_range(1.0..=f32::MAX)
+                            .speed(0.01)
+                            .prefix("Intensity: ");
+                        changed |= ui.add(intensity).changed();
+                    }
+                    ui.horizontal(|ui| {
+                        changed |= hex_ui(ui, popup_id, &mut editing, C::ALPHA);
+                        let eyedropper = ui
+                            .selectable_label(picking, "💧")
+                            .on_hover_text("Pick a color from the viewport");
+                        if eyedropper.clicked() {
+                            if picking {
+                                cancel_picking(ui.ctx());
+                            } else {
+                                ui.data()
+                                    .insert_temp(eyedropper_id(), Eyedropper::Picking(popup_id));
+                            }
+                        }
+                    });
+                    ui.separator();
+                    changed |= palette_ui(ui, &mut editing, C::ALPHA);
+                });
+            })
+            .response;
+
+        if !button.clicked()
+            && (ui.input().key_pressed(egui::Key::Escape) || area_response.clicked_elsewhere())
+        {
+            ui.memory().close_popup();
+        }
+    }
+
+    if changed {
+        color.set_rgba(editing.to_rgba());
+    }
+    ctx.data().insert_temp(popup_id, (color.to_rgba(), editing));
+    changed
+}
+
+/// Shows the hue, saturation, value and optionally alpha of a color as drag values.
+fn hsva_ui(ui: &mut egui::Ui, hsva: &mut Hsva, alpha: bool) -> bool {
+    ui.horizontal(|ui| {
+        let mut changed = false;
+        let mut hue = hsva.h * 360.;
+        let drag = DragValue::new(&mut hue)
+            .clamp_range(0.0..=360.0)
+            .prefix("H: ")
+            .suffix("°")
+            .max_decimals(0);
+        if ui.add(drag).changed() {
+            hsva.h = hue / 360.;
+            changed = true;
+        }
+        let mut percent = |ui: &mut egui::Ui, value: &mut f32, prefix: &str| {
+            let mut percent = *value * 100.;
+            let drag = DragValue::new(&mut percent)
+                .clamp_range(0.0..=100.0)
+                .prefix(prefix)
+                .suffix("%")
+                .max_decimals(0);
+            if ui.add(drag).changed() {
+                *value = percent / 100.;
+                changed = true;
+            }
+        };
+        percent(ui, &mut hsva.s, "S: ");
+        percent(ui, &mut hsva.v, "V: ");
+        if alpha {
+            percent(ui, &mut hsva.a, "A: ");
+        }
+        changed
+    })
+    .inner
+}
+
+/// Shows the hex code of a color, which can be edited.
+fn hex_ui(ui: &mut egui::Ui, popup_id: Id, editing: &mut Editing, alpha: bool) -> bool {
+    // The text is kept while typing, so that it is not replaced while it is not valid
+    let hex_id = popup_id.with("hex");
+    let mut hex = ui
+        .data()
+        .get_temp::<String>(hex_id)
+        .unwrap_or_else(|| to_hex(editing.srgba(), alpha));
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut hex)
+            .desired_width(80.)
+            .font(egui::TextStyle::Monospace),
+    );
+    let mut changed = false;
+    if response.changed() {
+        if let Some(srgba) = parse_hex(&hex) {
+            editing.set_srgba(srgba, alpha);
+            changed = true;
+        }
+    }
+    if response.has_focus() {
+        ui.data().insert_temp(hex_id, hex);
+    } else {
+        ui.data().remove::<String>(hex_id);
+    }
+    changed
+}
+
+/// Shows the colors of the palette, which are applied when clicked and removed when right-clicked,
+/// along with a button to add the color edited to it.
+fn palette_ui(ui: &mut egui::Ui, editing: &mut Editing, alpha: bool) -> bool {
+    let mut palette = Palette::load(ui.ctx());
+    let mut changed = false;
+    let mut palette_changed = false;
+    ui.horizontal_wrapped(|ui| {
+        let mut removed = None;
+        for (idx, &saved) in palette.colors.iter().enumerate() {
+            let [r, g, b, a] = saved;
+            let response = swatch(ui, Color32::from_rgba_unmultiplied(r, g, b, a))
+                .on_hover_text(format!("{}\nRight-click to remove", to_hex(saved, alpha)));
+            if response.clicked() {
+                editing.set_srgba(saved, alpha);
+                changed = true;
+            }
+            if response.secondary_clicked() {
+                removed = Some(idx);
+            }
+        }
+        if let Some(removed) = removed {
+            palette.colors.remove(removed);
+            palette_changed = true;
+        }
+        let add = ui
+            .button("+")
+            .on_hover_text("Save this color in the palette");
+        if add.clicked() {
+            palette.colors.push(editing.srgba());
+            palette_changed = true;
+        }
+    });
+    if palette_changed {
+        palette.store(ui.ctx());
+    }
+    changed
+}
+
+/// Edits the value borrowed as a color, which is how `#[inspectable(color)]` fields are shown.
+pub struct AsColor<'a, T: ?Sized>(pub &'a mut T);
+
+impl<T: EditColor + ?Sized> Inspectable for AsColor<'_, T> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        color_edit_ui(ui, self.0);
+    }
+}
@@ -0,0 +1,552 @@
+//! A developer console where the application registers named commands with typed arguments.
+//!
+//! Commands are registered with [`Commands::register`], typed in the console shown by
+//! [`Commands::ui`] (or given to [`Commands::submit`]), and run by [`Commands::run`] with the
+//! state they were registered for:
+//!
+//! ```
+//! # use guiedit::commands::Commands;
+//! struct Game {
+//!     enemies: Vec<(String, f32)>,
+//! }
+//!
+//! let mut commands = Commands::default();
+//! commands
+//!     .register("spawn", |(kind, x): (String, f32), game: &mut Game| {
+//!         game.enemies.push((kind, x));
+//!     })
+//!     .help("Spawns an enemy at the position given");
+//!
+//! let mut game = Game { enemies: vec![] };
+//! commands.submit("spawn slime 12.5");
+//! commands.run(&mut game);
+//! assert_eq!(game.enemies, [("slime".to_owned(), 12.5)]);
+//! ```
+//!
+//! Arguments are separated by whitespace, and can be quoted to include it (`spawn "big slime" 3`).
+
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, VecDeque},
+    fmt::Display,
+};
+
+/// The number of lines kept in the output of the console.
+const OUTPUT_CAPACITY: usize = 1000;
+
+/// A type that can be given as an argument to a command.
+pub trait CommandArg: Sized {
+    /// The name of the type, shown in the signature of commands.
+    const TYPE_NAME: &'static str;
+
+    fn parse(token: &str) -> Result<Self, String>;
+}
+
+macro_rules! implement_command_arg_from_str {
+    ($($Type: ty),*) => {
+        $(
+            impl CommandArg for $Type {
+                const TYPE_NAME: &'static str = stringify!($Type);
+
+                fn parse(token: &str) -> Result<Self, String> {
+                    token
+                        .parse()
+                        .map_err(|_| format!("`{}` is not a valid {}", token, Self::TYPE_NAME))
+                }
+            }
+        )*
+    };
+}
+implement_command_arg_from_str!(
+    i8, u8, i16, u16, i32, u32, i64, u64, isize, usize, f32, f64, bool, char, String
+);
+
+/// The arguments of a command: a tuple of [`CommandArg`]s.
+pub trait CommandArgs: Sized {
+    /// The type names of the arguments, in order.
+    fn types() -> Vec<&'static str>;
+
+    fn parse(tokens: &[String]) -> Result<Self, String>;
+}
+
+macro_rules! implement_command_args {
+    ($($Arg: ident),*) => {
+        impl<$($Arg: CommandArg),*> CommandArgs for ($($Arg,)*) {
+            fn types() -> Vec<&'static str> {
+                vec![$($Arg::TYPE_NAME),*]
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn parse(tokens: &[String]) -> Result<Self, String> {
+                let count = Self::types().len();
+                if tokens.len() != count {
+                    return Err(format!(
+                        "Expected {} argument{}, got {}",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        tokens.len()
+                    ));
+                }
+                let mut tokens = tokens.iter().enumerate();
+                Ok(($({
+                    let (idx, token) = tokens.next().unwrap();
+                    $Arg::parse(token)
+                        .map_err(|error| format!("Argument {}: {}", idx + 1, error))?
+                },)*))
+            }
+        }
+    };
+}
+implement_command_args!();
+implement_command_args!(A);
+implement_command_args!(A, B);
+implement_command_args!(A, B, C);
+implement_command_args!(A, B, C, D);
+implement_command_args!(A, B, C, D, E);
+implement_command_args!(A, B, C, D, E, F);
+
+/// A value that can be returned by a command, which is shown in the console.
+pub trait CommandOutput {
+    /// Returns the text to show, if any, or an error.
+    fn into_output(self) -> Result<Option<String>, String>;
+}
+
+impl CommandOutput for () {
+    fn into_output(self) -> Result<Option<String>, String> {
+        Ok(None)
+    }
+}
+
+impl CommandOutput for String {
+    fn into_output(self) -> Result<Option<String>, String> {
+        Ok(Some(self))
+    }
+}
+
+impl CommandOutput for &str {
+    fn into_output(self) -> Result<Option<String>, String> {
+        Ok(Some(self.to_owned()))
+    }
+}
+
+impl<T: CommandOutput, E: Display> CommandOutput for Result<T, E> {
+    fn into_output(self) -> Result<Option<String>, String> {
+        self.map_err(|error| error.to_string())
+            .and_then(T::into_output)
+    }
+}
+
+type RunFn = Box<dyn FnMut(&[String], &mut dyn Any) -> Result<Option<String>, String>>;
+
+/// A command registered in the console.
+pub struct Command {
+    name: String,
+    help: String,
+    arg_types: Vec<&'static str>,
+    validate: fn(&[String]) -> Result<(), String>,
+    state_type: TypeId,
+    run: RunFn,
+}
+
+impl Command {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the description of the command, shown by `help` and while typing it.
+    pub fn help(&mut self, help: impl Into<String>) -> &mut Self {
+        self.help = help.into();
+        self
+    }
+
+    /// Returns the name of the command followed by the types of its arguments, e.g.
+    /// `spawn <String> <f32>`.
+    pub fn signature(&self) -> String {
+        let args = self.arg_types.iter().map(|ty| format!(" <{}>", ty));
+        args.fold(self.name.clone(), |signature, arg| signature + &arg)
+    }
+}
+
+/// What a line of the console output is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    /// A command typed.
+    Input,
+    /// The output of a command.
+    Output,
+    Error,
+}
+
+/// A line of the console output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub kind: LineKind,
+    pub text: String,
+}
+
+/// The commands registered, along with the console they are typed in.
+#[derive(Default)]
+pub struct Commands {
+    commands: BTreeMap<String, Command>,
+    /// The arguments of the commands submitted that are yet to be run, command name first.
+    pending: Vec<Vec<String>>,
+    history: Vec<String>,
+    output: VecDeque<Line>,
+    input: String,
+    /// The entry of the history being shown in the input, if any.
+    history_cursor: Option<usize>,
+}
+
+impl Commands {
+    /// Registers a command with the name given, replacing any other one with the same name.
+    ///
+    /// The command is run by [`run`](Self::run) with its arguments, already parsed, and the state
+    /// given to it, and whatever it returns is shown in the console.
+    pub fn register<A, S, R>(
+        &mut self,
+        name: impl Into<String>,
+        mut f: impl FnMut(A, &mut S) -> R + 'static,
+    ) -> &mut Command
+    where
+        A: CommandArgs + 'static,
+        S: 'static,
+        R: CommandOutput + 'static,
+    {
+        let name = name.into();
+        let command = Command {
+            name: name.clone(),
+            help: String::new(),
+            arg_types: A::types(),
+            validate: |tokens| A::parse(tokens).map(drop),
+            state_type: TypeId::of::<S>(),
+            run: Box::new(move |tokens, state| {
+                let args = A::parse(tokens)?;
+                let state = state
+                    .downcast_mut::<S>()
+                    .expect("commands are only run with the state they were registered for");
+                f(args, state).into_output()
+            }),
+        };
+        self.commands.insert(name.clone(), command);
+        self.commands.get_mut(&name).unwrap()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.commands.values()
+    }
+
+    /// Returns the names of the commands that start with the text given, in alphabetical order, or
+    /// nothing if the text already includes arguments.
+    pub fn completions(&self, input: &str) -> Vec<&str> {
+        let input = input.trim_start();
+        if input.contains(char::is_whitespace) {
+            return vec![];
+        }
+        let names = self.commands.keys().map(String::as_str);
+        names.filter(|name| name.starts_with(input)).collect()
+    }
+
+    /// Checks that the line given names a command and that its arguments are valid.
+    pub fn validate(&self, line: &str) -> Result<(), String> {
+        let tokens = tokenize(line)?;
+        let Some((name, args)) = tokens.split_first() else {
+            return Ok(());
+        };
+        match self.commands.get(name) {
+            Some(command) => (command.validate)(args),
+            None if is_builtin(name) => Ok(()),
+            None => Err(format!("Unknown command `{}`", name)),
+        }
+    }
+
+    /// Submits a line as if it was typed in the console, queuing the command it names to be run.
+    ///
+    /// The line is added to the history and output, along with any parsing error.
+    pub fn submit(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        self.push_output(LineKind::Input, format!("> {}", line));
+        if self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_owned());
+        }
+
+        if let Err(error) = self.validate(line) {
+            self.push_output(LineKind::Error, error);
+            return;
+        }
+        let tokens = tokenize(line).expect("the line was validated");
+        match tokens[0].as_str() {
+            name if self.commands.contains_key(name) => self.pending.push(tokens),
+            "help" => {
+                let help: Vec<String> = self
+                    .commands
+                    .values()
+                    .map(|command| match command.help.as_str() {
+                        "" => command.signature(),
+                        help => format!("{}: {}", command.signature(), help),
+                    })
+                    .chain(["clear: Clears the console".to_owned()])
+                    .collect();
+                for line in help {
+                    self.push_output(LineKind::Output, line);
+                }
+            }
+            "clear" => self.output.clear(),
+            _ => unreachable!("the line was validated"),
+        }
+    }
+
+    /// Runs the commands submitted that were registered for the type of state given, in the
+    /// order they were submitted. Commands registered for other types of state are kept queued.
+    pub fn run<S: 'static>(&mut self, state: &mut S) {
+        let (to_run, pending) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|tokens| {
+                self.commands
+                    .get(&tokens[0])
+                    .is_some_and(|command| command.state_type == TypeId::of::<S>())
+            });
+        self.pending = pending;
+
+        for tokens in to_run {
+            let Some(command) = self.commands.get_mut(&tokens[0]) else {
+                continue;
+            };
+            match (command.run)(&tokens[1..], state) {
+                Ok(Some(output)) => self.push_output(LineKind::Output, output),
+                Ok(None) => {}
+                Err(error) => self.push_output(LineKind::Error, error),
+            }
+        }
+    }
+
+    /// The lines submitted, from oldest to newest.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// The commands submitted, their output and any errors, from oldest to newest.
+    pub fn output(&self) -> impl Iterator<Item = &Line> {
+        self.output.iter()
+    }
+
+    fn push_output(&mut self, kind: LineKind, text: String) {
+        if self.output.len() >= OUTPUT_CAPACITY {
+            self.output.pop_front();
+        }
+        self.output.push_back(Line { kind, text });
+    }
+
+    /// Shows the output of the console along with a line to type commands in.
+    ///
+    /// Tab completes the name of the command being typed, and the up and down arrows go through
+    /// the history.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        let input_height =
+            ui.spacing().interact_size.y + row_height + ui.spacing().item_spacing.y * 3.;
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .stick_to_bottom(true)
+            .max_height((ui.available_height() - input_height).max(row_height))
+            .show(ui, |ui| {
+                for line in &self.output {
+                    let color = match line.kind {
+                        LineKind::Input => ui.visuals().weak_text_color(),
+                        LineKind::Output => ui.visuals().text_color(),
+                        LineKind::Error => ui.visuals().error_fg_color,
+                    };
+                    ui.label(egui::RichText::new(&line.text).monospace().color(color));
+                }
+            });
+        ui.separator();
+
+        let id = ui.id().with("guiedit command input");
+        let mut replaced_input = false;
+        if ui.memory().has_focus(id) {
+            let (tab, up, down) = {
+                let mut input = ui.input_mut();
+                let mut consume = |key| input.consume_key(egui::Modifiers::NONE, key);
+                (
+                    consume(egui::Key::Tab),
+                    consume(egui::Key::ArrowUp),
+                    consume(egui::Key::ArrowDown),
+                )
+            };
+            if tab {
+                self.complete();
+            } else if up {
+                self.browse_history(-1);
+            } else if down {
+                self.browse_history(1);
+            }
+            replaced_input = tab || up || down;
+        }
+        if replaced_input {
+            // Move the cursor to the end of the new input
+            if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), id) {
+                let cursor = egui::text::CCursor::new(self.input.chars().count());
+                state.set_ccursor_range(Some(egui::text::CCursorRange::one(cursor)));
+                state.store(ui.ctx(), id);
+            }
+        }
+
+        let response = ui
+            .horizontal(|ui| {
+                ui.monospace(">");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.input)
+                        .id(id)
+                        .font(egui::TextStyle::Monospace)
+                        .lock_focus(true)
+                        .desired_width(f32::INFINITY),
+                )
+            })
+            .inner;
+        if response.changed() {
+            self.history_cursor = None;
+        }
+        if response.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
+            let line = std::mem::take(&mut self.input);
+            self.submit(&line);
+            self.history_cursor = None;
+            response.request_focus();
+        }
+
+        self.hint_ui(ui);
+    }
+
+    /// Shows the completions of the command being typed, or its signature and any error in its
+    /// arguments.
+    fn hint_ui(&self, ui: &mut egui::Ui) {
+        let weak = |ui: &mut egui::Ui, text: String| {
+            ui.label(egui::RichText::new(text).monospace().weak());
+        };
+        let tokens = match tokenize(&self.input) {
+            Ok(tokens) => tokens,
+            Err(error) => {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+                return;
+            }
+        };
+        let Some((name, args)) = tokens.split_first() else {
+            weak(ui, "Type `help` to list the commands".to_owned());
+            return;
+        };
+
+        match self.commands.get(name) {
+            Some(command) => {
+                match command.help.as_str() {
+                    "" => weak(ui, command.signature()),
+                    help => weak(ui, format!("{}: {}", command.signature(), help)),
+                }
+                if !args.is_empty() {
+                    if let Err(error) = (command.validate)(args) {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                }
+            }
+            None => match self.completions(&self.input).as_slice() {
+                [] if is_builtin(name) => {}
+                [] => {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        format!("Unknown command `{}`", name),
+                    );
+                }
+                completions => weak(ui, format!("{} (Tab to complete)", completions.join(", "))),
+            },
+        }
+    }
+
+    /// Completes the name of the command being typed as much as possible.
+    fn complete(&mut self) {
+        let completions = self.completions(&self.input);
+        let Some(first) = completions.first() else {
+            return;
+        };
+        let common_len = completions.iter().fold(first.len(), |len, completion| {
+            first
+                .chars()
+                .zip(completion.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum::<usize>()
+                .min(len)
+        });
+        let mut input = first[..common_len].to_owned();
+        if completions.len() == 1 {
+            input.push(' ');
+        }
+        self.input = input;
+    }
+
+    /// Replaces the input with an older (negative offset) or newer (positive offset) line from
+    /// the history, clearing it when going past the newest one.
+    fn browse_history(&mut self, offset: isize) {
+        if self.history.is_empty() {
+            return;
+        }
+        let newest = self.history.len() - 1;
+        let cursor = match self.history_cursor {
+            None if offset < 0 => Some(newest),
+            None => None,
+            Some(cursor) => match cursor.checked_add_signed(offset) {
+                Some(cursor) if cursor > newest => None,
+                Some(cursor) => Some(cursor),
+                None => Some(0),
+            },
+        };
+        self.history_cursor = cursor;
+        self.input = cursor.map_or_else(String::new, |cursor| self.history[cursor].clone());
+    }
+}
+
+/// Commands handled by the console itself.
+fn is_builtin(name: &str) -> bool {
+    matches!(name, "help" | "clear")
+}
+
+/// Splits a line into whitespace-separated tokens, keeping quoted ones (with `\"` and `\\` escapes)
+/// together.
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut token = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(escaped) => token.push(escaped),
+                        None => return Err("Unterminated string".to_owned()),
+                    },
+                    Some(c) => token.push(c),
+                    None => return Err("Unterminated string".to_owned()),
+                }
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+    Ok(tokens)
+}
@@ -0,0 +1,380 @@
+//! A console showing the log records of the application.
+//!
+//! Records are buffered by a [`Console`], which can be fed by the `log` crate through
+//! [`Console::logger`] (requires the `log` feature) or by `tracing` through [`Console::layer`]
+//! (requires the `tracing` feature), and shown with [`Console::ui`].
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The number of records kept by a console, if no other capacity is given.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// The importance of a record, from most to least important.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+
+    fn color(self, ui: &egui::Ui) -> egui::Color32 {
+        match self {
+            Level::Error => ui.visuals().error_fg_color,
+            Level::Warn => ui.visuals().warn_fg_color,
+            Level::Info => ui.visuals().text_color(),
+            Level::Debug | Level::Trace => ui.visuals().weak_text_color(),
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for Level {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => Level::Error,
+            log::Level::Warn => Level::Warn,
+            log::Level::Info => Level::Info,
+            log::Level::Debug => Level::Debug,
+            log::Level::Trace => Level::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<tracing_core::Level> for Level {
+    fn from(level: tracing_core::Level) -> Self {
+        match level {
+            tracing_core::Level::ERROR => Level::Error,
+            tracing_core::Level::WARN => Level::Warn,
+            tracing_core::Level::INFO => Level::Info,
+            tracing_core::Level::DEBUG => Level::Debug,
+            tracing_core::Level::TRACE => Level::Trace,
+        }
+    }
+}
+
+/// A log record buffered by a [`Console`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// The time the record was made at, since the console was created.
+    pub time: Duration,
+    pub level: Level,
+    /// The module path or name given as the target of the record.
+    pub target: String,
+    pub message: String,
+}
+
+impl Record {
+    /// Formats the record as a single line (unless the message spans several of them), as shown
+    /// by the console.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{:>10.3} {:<5} {} {}",
+            self.time.as_secs_f64(),
+            self.level.name(),
+            self.target,
+            self.message
+        )
+    }
+}
+
+struct Records {
+    start: Instant,
+    records: VecDeque<Record>,
+    targets: BTreeSet<String>,
+    capacity: usize,
+}
+
+/// The records shown by the console.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    /// The least important level shown.
+    pub level: Level,
+    /// The target shown, or `None` to show every target.
+    pub target: Option<String>,
+    /// Text that the messages shown contain, ignoring case.
+    pub search: String,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            level: Level::Trace,
+            target: None,
+            search: String::new(),
+        }
+    }
+}
+
+impl Filter {
+    pub fn matches(&self, record: &Record) -> bool {
+        record.level <= self.level
+            && (self.target.is_none() || self.target.as_ref() == Some(&record.target))
+            && (self.search.is_empty()
+                || record
+                    .message
+                    .to_lowercase()
+                    .contains(&self.search.to_lowercase()))
+    }
+}
+
+/// A buffer of log records, shown in the console panel.
+///
+/// Consoles are cheap to clone, and clones share the records buffered, but not the filter.
+#[derive(Clone)]
+pub struct Console {
+    records: Arc<Mutex<Records>>,
+    pub filter: Filter,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl Console {
+    /// Creates a console that keeps the number of records given, dropping the oldest ones.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(Records {
+                start: Instant::now(),
+                records: VecDeque::new(),
+                targets: BTreeSet::new(),
+                capacity,
+            })),
+            filter: Filter::default(),
+        }
+    }
+
+    /// Buffers a new record, made now.
+    pub fn push(&self, level: Level, target: impl Into<String>, message: impl Into<String>) {
+        let mut records = self.records.lock().unwrap();
+        let target = target.into();
+        if !records.targets.contains(&target) {
+            records.targets.insert(target.clone());
+        }
+        if records.records.len() >= records.capacity {
+            records.records.pop_front();
+        }
+        let time = records.start.elapsed();
+        records.records.push_back(Record {
+            time,
+            level,
+            target,
+            message: message.into(),
+        });
+    }
+
+    /// Returns the records buffered, from oldest to newest.
+    pub fn records(&self) -> Vec<Record> {
+        self.records
+            .lock()
+            .unwrap()
+            .records
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().unwrap().records.clear();
+    }
+
+    /// Returns a `log` logger that buffers every record into this console.
+    ///
+    /// Install it with [`Logger::init`], or combine it with other loggers.
+    #[cfg(feature = "log")]
+    pub fn logger(&self) -> Logger {
+        Logger {
+            console: self.clone(),
+        }
+    }
+
+    /// Returns a `tracing` layer that buffers every event into this console.
+    #[cfg(feature = "tracing")]
+    pub fn layer(&self) -> ConsoleLayer {
+        ConsoleLayer {
+            console: self.clone(),
+        }
+    }
+
+    /// Shows the filters and the records that match them, which can be clicked to copy them.
+    ///
+    /// The records are shown in their own scroll area, which sticks to the newest record.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let mut records = self.records.lock().unwrap();
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("guiedit console level")
+                .selected_text(self.filter.level.name())
+                .show_ui(ui, |ui| {
+                    for level in Level::ALL {
+                        ui.selectable_value(&mut self.filter.level, level, level.name());
+                    }
+                })
+                .response
+                .on_hover_text("The least important level shown");
+            egui::ComboBox::from_id_source("guiedit console target")
+                .selected_text(self.filter.target.as_deref().unwrap_or("All targets"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.target, None, "All targets");
+                    for target in &records.targets {
+                        ui.selectable_value(&mut self.filter.target, Some(target.clone()), target);
+                    }
+                });
+            ui.add(egui::TextEdit::singleline(&mut self.filter.search).hint_text("Search"));
+            if ui.button("Clear").clicked() {
+                records.records.clear();
+            }
+        });
+        ui.separator();
+
+        let shown: Vec<&Record> = records
+            .records
+            .iter()
+            .filter(|record| self.filter.matches(record))
+            .collect();
+        if shown.is_empty() {
+            ui.add_enabled_ui(false, |ui| ui.label("No records"));
+            return;
+        }
+
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::both()
+            .auto_shrink([false; 2])
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, shown.len(), |ui, rows| {
+                for record in &shown[rows] {
+                    // Messages spanning several lines are cut, to keep every row the same height
+                    let line = record.to_line();
+                    let first_line = match line.split_once('\n') {
+                        Some((first_line, _)) => format!("{} …", first_line),
+                        None => line.clone(),
+                    };
+                    let text = egui::RichText::new(first_line)
+                        .monospace()
+                        .color(record.level.color(ui));
+                    let response = ui
+                        .add(
+                            egui::Label::new(text)
+                                .wrap(false)
+                                .sense(egui::Sense::click()),
+                        )
+                        .on_hover_text("Click to copy");
+                    if response.clicked() {
+                        ui.output().copied_text = line;
+                    }
+                }
+            });
+    }
+}
+
+/// A `log` logger that buffers every record into a [`Console`], returned by [`Console::logger`].
+#[cfg(feature = "log")]
+pub struct Logger {
+    console: Console,
+}
+
+#[cfg(feature = "log")]
+impl Logger {
+    /// Installs the logger as the global one, with the level filter given.
+    pub fn init(self, level: log::LevelFilter) -> Result<(), log::SetLoggerError> {
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "log")]
+impl log::Log for Logger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.console.push(
+            record.level().into(),
+            record.target(),
+            record.args().to_string(),
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+/// A `tracing` layer that buffers every event into a [`Console`], returned by
+/// [`Console::layer`].
+///
+/// The fields of each event other than its message are appended to it as `name=value`.
+#[cfg(feature = "tracing")]
+pub struct ConsoleLayer {
+    console: Console,
+}
+
+#[cfg(feature = "tracing")]
+impl<S: tracing_core::Subscriber> tracing_subscriber::Layer<S> for ConsoleLayer {
+    fn on_event(
+        &self,
+        event: &tracing_core::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        #[derive(Default)]
+        struct Message {
+            message: String,
+            fields: String,
+        }
+
+        impl tracing_core::field::Visit for Message {
+            fn record_debug(&mut self, field: &tracing_core::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.message = format!("{:?}", value);
+                } else {
+                    self.fields += &format!(" {}={:?}", field.name(), value);
+                }
+            }
+
+            fn record_str(&mut self, field: &tracing_core::Field, value: &str) {
+                if field.name() == "message" {
+                    self.message = value.to_owned();
+                } else {
+                    self.record_debug(field, &value);
+                }
+            }
+        }
+
+        let mut message = Message::default();
+        event.record(&mut message);
+        let metadata = event.metadata();
+        self.console.push(
+            (*metadata.level()).into(),
+            metadata.target(),
+            message.message + &message.fields,
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Debug shapes drawn in world space from anywhere in the application, on top of its viewport.
+//!
+//! Shapes are collected for the frame being rendered, and only while the editor is shown, so that
+//! they never end up in the rendering of the application itself:
+//!
+//! ```
+//! use guiedit::debug_draw;
+//!
+//! # let (position, velocity) = ([10., 20.], [4., 0.]);
+//! debug_draw::arrow(position, [position[0] + velocity[0], position[1]], [255, 255, 0, 255]);
+//! debug_draw::category("physics").rect([0., 0.], [32., 32.], [0, 255, 0, 255]);
+//! ```
+//!
+//! Each category of shapes can be hidden from the debug draw panel of the editor. Backends call
+//! [`DebugDraw::collect`] once per frame and [`DebugDraw::paint`] over their viewport.
+
+use std::{
+    collections::BTreeMap,
+    f32::consts::TAU,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use egui::{Align2, Color32, FontId, Pos2, Stroke};
+
+use crate::color::{self, EditColor};
+
+/// The category of the shapes drawn with the free functions of this module.
+pub const DEFAULT_CATEGORY: &str = "default";
+/// The width of the lines of every shape, in points.
+const LINE_WIDTH: f32 = 1.5;
+/// The length of the heads of arrows, in points.
+const ARROW_HEAD_LENGTH: f32 = 10.;
+/// The number of segments circles are drawn with.
+const CIRCLE_SEGMENTS: usize = 32;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SHAPES: Mutex<Vec<DebugShape>> = Mutex::new(Vec::new());
+
+/// A point in world space.
+pub trait Point {
+    fn xy(&self) -> [f32; 2];
+}
+
+impl Point for [f32; 2] {
+    fn xy(&self) -> [f32; 2] {
+        *self
+    }
+}
+
+impl Point for (f32, f32) {
+    fn xy(&self) -> [f32; 2] {
+        [self.0, self.1]
+    }
+}
+
+impl Point for Pos2 {
+    fn xy(&self) -> [f32; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl Point for egui::Vec2 {
+    fn xy(&self) -> [f32; 2] {
+        [self.x, self.y]
+    }
+}
+
+/// The geometry of a debug shape, in world space.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Line {
+        from: Pos2,
+        to: Pos2,
+    },
+    /// The outline of an axis-aligned box, given by two of its opposite corners.
+    Rect {
+        min: Pos2,
+        max: Pos2,
+    },
+    Circle {
+        center: Pos2,
+        radius: f32,
+    },
+    Arrow {
+        from: Pos2,
+        to: Pos2,
+    },
+    /// Text whose top left corner is at the position given, with a size that does not depend on
+    /// the view.
+    Text {
+        pos: Pos2,
+        text: String,
+    },
+}
+
+/// A shape drawn for the current frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugShape {
+    pub category: &'static str,
+    pub shape: Shape,
+    pub color: Color32,
+}
+
+/// A category of debug shapes, which can be hidden from the editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Category(pub &'static str);
+
+impl Category {
+    pub fn line(self, from: impl Point, to: impl Point, color: impl EditColor) {
+        self.draw(
+            Shape::Line {
+                from: pos(from),
+                to: pos(to),
+            },
+            color,
+        );
+    }
+
+    pub fn rect(self, min: impl Point, max: impl Point, color: impl EditColor) {
+        self.draw(
+            Shape::Rect {
+                min: pos(min),
+                max: pos(max),
+            },
+            color,
+        );
+    }
+
+    pub fn circle(self, center: impl Point, radius: f32, color: impl EditColor) {
+        self.draw(
+            Shape::Circle {
+                center: pos(center),
+                radius,
+            },
+            color,
+        );
+    }
+
+    pub fn arrow(self, from: impl Point, to: impl Point, color: impl EditColor) {
+        self.draw(
+            Shape::Arrow {
+                from: pos(from),
+                to: pos(to),
+            },
+            color,
+        );
+    }
+
+    pub fn text(self, pos: impl Point, text: impl Into<String>, color: impl EditColor) {
+        self.draw(
+            Shape::Text {
+                pos: self::pos(pos),
+                text: text.into(),
+            },
+            color,
+        );
+    }
+
+    /// Draws a shape for the current frame, if the editor is shown.
+    pub fn draw(self, shape: Shape, color: impl EditColor) {
+        if !is_enabled() {
+            return;
+        }
+        let [r, g, b, a] = color::srgba_from_rgba(color.to_rgba());
+        SHAPES.lock().unwrap().push(DebugShape {
+            category: self.0,
+            shape,
+            color: Color32::from_rgba_unmultiplied(r, g, b, a),
+        });
+    }
+}
+
+fn pos(point: impl Point) -> Pos2 {
+    point.xy().into()
+}
+
+/// Returns the category with the name given, to draw shapes in.
+pub fn category(name: &'static str) -> Category {
+    Category(name)
+}
+
+pub fn line(from: impl Point, to: impl Point, color: impl EditColor) {
+    Category(DEFAULT_CATEGORY).line(from, to, color);
+}
+
+pub fn rect(min: impl Point, max: impl Point, color: impl EditColor) {
+    Category(DEFAULT_CATEGORY).rect(min, max, color);
+}
+
+pub fn circle(center: impl Point, radius: f32, color: impl EditColor) {
+    Category(DEFAULT_CATEGORY).circle(center, radius, color);
+}
+
+pub fn arrow(from: impl Point, to: impl Point, color: impl EditColor) {
+    Category(DEFAULT_CATEGORY).arrow(from, to, color);
+}
+
+pub fn text(pos: impl Point, text: impl Into<String>, color: impl EditColor) {
+    Category(DEFAULT_CATEGORY).text(pos, text, color);
+}
+
+/// Returns whether shapes drawn are collected, i.e. whether the editor is shown.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts or stops collecting the shapes drawn. Backends enable it while the editor is shown.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        SHAPES.lock().unwrap().clear();
+    }
+}
+
+/// The debug shapes of the last frame and the categories that are shown.
+#[derive(Default)]
+pub struct DebugDraw {
+    shapes: Vec<DebugShape>,
+    /// Every category drawn in so far, along with whether it is shown.
+    categories: BTreeMap<&'static str, bool>,
+}
+
+impl DebugDraw {
+    /// Takes the shapes drawn since the last call, which are the ones painted from then on.
+    pub fn collect(&mut self) {
+        self.shapes = std::mem::take(&mut *SHAPES.lock().unwrap());
+        for shape in &self.shapes {
+            self.categories.entry(shape.category).or_insert(true);
+        }
+    }
+
+    /// The shapes collected last, including the ones of hidden categories.
+    pub fn shapes(&self) -> &[DebugShape] {
+        &self.shapes
+    }
+
+    /// The categories drawn in so far, along with whether they are shown.
+    pub fn categories(&self) -> impl Iterator<Item = (&'static str, bool)> + '_ {
+        self.categories
+            .iter()
+            .map(|(&category, &visible)| (category, visible))
+    }
+
+    pub fn is_visible(&self, category: &str) -> bool {
+        self.categories.get(category).copied().unwrap_or(true)
+    }
+
+    pub fn set_visible(&mut self, category: &'static str, visible: bool) {
+        self.categories.insert(category, visible);
+    }
+
+    /// Paints the shapes of the categories shown, mapping world positions to screen positions
+    /// with `to_screen`.
+    pub fn paint(&self, painter: &egui::Painter, to_screen: impl Fn(Pos2) -> Pos2) {
+        for DebugShape {
+            category,
+            shape,
+            color,
+        } in &self.shapes
+        {
+            if !self.is_visible(category) {
+                continue;
+            }
+            let stroke = Stroke::new(LINE_WIDTH, *color);
+            match shape {
+                Shape::Line { from, to } => {
+                    painter.line_segment([to_screen(*from), to_screen(*to)], stroke);
+                }
+                Shape::Rect { min, max } => {
+                    // Every corner is mapped in case the view is rotated
+                    let corners = [
+                        (min.x, min.y),
+                        (max.x, min.y),
+                        (max.x, max.y),
+                        (min.x, max.y),
+                    ];
+                    let corners = corners.map(|(x, y)| to_screen(Pos2::new(x, y)));
+                    painter.add(egui::Shape::closed_line(corners.to_vec(), stroke));
+                }
+                Shape::Circle { center, radius } => {
+                    let points = (0..CIRCLE_SEGMENTS)
+                        .map(|idx| {
+                            let angle = idx as f32 / CIRCLE_SEGMENTS as f32 * TAU;
+                            to_screen(*center + egui::Vec2::angled(angle) * *radius)
+                        })
+                        .collect();
+                    painter.add(egui::Shape::closed_line(points, stroke));
+                }
+                Shape::Arrow { from, to } => {
+                    let (from, to) = (to_screen(*from), to_screen(*to));
+                    painter.line_segment([from, to], stroke);
+                    let length = (to - from).length();
+                    if length > 0. {
+                        let back = (from - to) / length * ARROW_HEAD_LENGTH.min(length / 2.);
+                        let side = back.rot90() / 2.;
+                        painter.line_segment([to, to + back + side], stroke);
+                        painter.line_segment([to, to + back - side], stroke);
+                    }
+                }
+                Shape::Text { pos, text } => {
+                    painter.text(
+                        to_screen(*pos),
+                        Align2::LEFT_TOP,
+                        text,
+                        FontId::monospace(12.),
+                        *color,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Shows a checkbox for each category drawn in so far, to show or hide its shapes.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if self.categories.is_empty() {
+            ui.weak("Nothing was drawn with guiedit::debug_draw yet");
+        }
+        for (category, visible) in &mut self.categories {
+            let count = self
+                .shapes
+                .iter()
+                .filter(|shape| shape.category == *category)
+                .count();
+            #[cfg_attr(not(feature = "testing"), allow(unused_variables))]
+            let response = ui.checkbox(visible, format!("{} ({})", category, count));
+            #[cfg(feature = "testing")]
+            crate::testing::record_button(ui.ctx(), category, response.rect);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Support for inspecting [`bevy_ecs`] worlds.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! #[derive(Component, guiedit::Inspectable)]
+//! struct Health(f32);
+//!
+//! guiedit::ecs::bevy::register_component::<Health>();
+//!
+//! let mut world = World::new();
+//! world.spawn(Health(100.));
+//! let tree = guiedit::ecs::WorldTree::new(&mut world);
+//! ```
+
+use std::any::TypeId;
+
+use bevy_ecs::{component::Component, entity::Entity, world::World};
+
+use super::{ComponentInfo, ComponentRegistry, EcsWorld};
+use crate::inspectable::Inspectable;
+
+type VisitFn = fn(&mut World, Entity, &mut dyn FnMut(&mut dyn Inspectable));
+
+static COMPONENTS: ComponentRegistry<(&'static str, VisitFn)> = ComponentRegistry::new();
+
+/// Registers a component type to be inspected with its [`Inspectable`] implementation.
+///
+/// Components that have not been registered are still shown in the tree, but cannot be inspected.
+pub fn register_component<C: Component + Inspectable>() {
+    COMPONENTS.register(
+        TypeId::of::<C>(),
+        (std::any::type_name::<C>(), |world, entity, f| {
+            if let Some(mut component) = world.get_mut::<C>(entity) {
+                f(&mut *component);
+            }
+        }),
+    );
+}
+
+/// Removes a component type from the registry, returning whether it was registered.
+pub fn unregister_component<C: Component>() -> bool {
+    COMPONENTS.unregister(TypeId::of::<C>())
+}
+
+impl EcsWorld for World {
+    type Entity = Entity;
+
+    fn all_entities(&self) -> Vec<Entity> {
+        let mut entities = self.iter_entities().collect::<Vec<_>>();
+        entities.sort_by_key(|entity| entity.index());
+        entities
+    }
+
+    fn entity_bits(&self, entity: Entity) -> u64 {
+        entity.to_bits()
+    }
+
+    fn entity_name(&self, entity: Entity) -> String {
+        format!("Entity {}v{}", entity.index(), entity.generation())
+    }
+
+    fn entity_components(&self, entity: Entity) -> Vec<ComponentInfo> {
+        self.inspect_entity(entity)
+            .into_iter()
+            // Components without a type ID are dynamic, so they cannot have been registered
+            .filter_map(|info| {
+                let type_id = info.type_id()?;
+                let registered = COMPONENTS.get(type_id);
+                Some(ComponentInfo {
+                    type_id,
+                    type_name: match registered {
+                        Some((type_name, _)) => type_name.into(),
+                        None => info.name().to_owned().into(),
+                    },
+                    registered: registered.is_some(),
+                })
+            })
+            .collect()
+    }
+
+    fn visit_component(
+        &mut self,
+        entity: Entity,
+        component: TypeId,
+        f: &mut dyn FnMut(&mut dyn Inspectable),
+    ) {
+        if let Some((_, visit)) = COMPONENTS.get(component) {
+            visit(self, entity, f);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Support for inspecting [`hecs`] worlds.
+//!
+//! Unlike with other ECS crates, the names of component types are not known to `hecs`, so only
+//! registered components are shown in the tree.
+
+use std::any::TypeId;
+
+use hecs::{Component, Entity, World};
+
+use super::{ComponentInfo, ComponentRegistry, EcsWorld};
+use crate::inspectable::Inspectable;
+
+type VisitFn = fn(&World, Entity, &mut dyn FnMut(&mut dyn Inspectable));
+
+static COMPONENTS: ComponentRegistry<(&'static str, VisitFn)> = ComponentRegistry::new();
+
+/// Registers a component type to be shown in the tree and inspected with its [`Inspectable`]
+/// implementation.
+pub fn register_component<C: Component + Inspectable>() {
+    COMPONENTS.register(
+        TypeId::of::<C>(),
+        (std::any::type_name::<C>(), |world, entity, f| {
+            if let Ok(mut component) = world.get::<&mut C>(entity) {
+                f(&mut *component);
+            }
+        }),
+    );
+}
+
+/// Removes a component type from the registry, returning whether it was registered.
+pub fn unregister_component<C: Component>() -> bool {
+    COMPONENTS.unregister(TypeId::of::<C>())
+}
+
+impl EcsWorld for World {
+    type Entity = Entity;
+
+    fn all_entities(&self) -> Vec<Entity> {
+        let mut entities = self
+            .iter()
+            .map(|entity| entity.entity())
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|entity| entity.id());
+        entities
+    }
+
+    fn entity_bits(&self, entity: Entity) -> u64 {
+        entity.to_bits().get()
+    }
+
+    fn entity_name(&self, entity: Entity) -> String {
+        format!("Entity {:?}", entity)
+    }
+
+    fn entity_components(&self, entity: Entity) -> Vec<ComponentInfo> {
+        let entity = match self.entity(entity) {
+            Ok(entity) => entity,
+            Err(_) => return vec![],
+        };
+        entity
+            .component_types()
+            .filter_map(|type_id| {
+                let (type_name, _) = COMPONENTS.get(type_id)?;
+                Some(ComponentInfo {
+                    type_id,
+                    type_name: type_name.into(),
+                    registered: true,
+                })
+            })
+            .collect()
+    }
+
+    fn visit_component(
+        &mut self,
+        entity: Entity,
+        component: TypeId,
+        f: &mut dyn FnMut(&mut dyn Inspectable),
+    ) {
+        if let Some((_, visit)) = COMPONENTS.get(component) {
+            visit(self, entity, f);
+        }
+    }
+}
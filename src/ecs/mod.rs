@@ -0,0 +1,330 @@
+//! Adapters exposing ECS worlds as [`TreeNode`]s, so that they can be passed to the editor in place
+//! of a root object.
+//!
+//! Entities are shown as nodes in the tree, with their components as children. Since components
+//! are stored type-erased, their types need to be registered before they can be inspected, or
+//! their fields accessed e.g. for remote inspection:
+//!
+//! - `bevy_ecs`: `bevy_ecs::world::World`, registering components with
+//!   `ecs::bevy::register_component`.
+//! - `hecs`: `hecs::World`, registering components with `ecs::hecs::register_component`.
+//!
+//! ```ignore
+//! guiedit::ecs::bevy::register_component::<Health>();
+//! window.display_and_inspect(&mut guiedit::ecs::WorldTree::new(&mut world));
+//! ```
+//!
+//! Other ECS crates can be supported by implementing [`EcsWorld`] for their world type.
+
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    hash::{Hash, Hasher},
+};
+
+use crate::{inspectable::Inspectable, tree::TreeNode};
+
+#[cfg(feature = "bevy_ecs")]
+pub mod bevy;
+#[cfg(feature = "hecs")]
+pub mod hecs;
+
+/// A world of entities that can be shown in the tree through [`WorldTree`].
+pub trait EcsWorld {
+    type Entity: Copy;
+
+    /// Returns all entities of the world, in a stable order.
+    fn all_entities(&self) -> Vec<Self::Entity>;
+
+    /// Returns a number that uniquely identifies the entity given, used to keep track of its node.
+    fn entity_bits(&self, entity: Self::Entity) -> u64;
+
+    /// Returns the name the entity given is shown with in the tree.
+    fn entity_name(&self, entity: Self::Entity) -> String;
+
+    /// Returns the components of the entity given that should be shown in the tree.
+    fn entity_components(&self, entity: Self::Entity) -> Vec<ComponentInfo>;
+
+    /// Calls `f` with the component of the entity given, if it has one of that type and it is
+    /// registered.
+    fn visit_component(
+        &mut self,
+        entity: Self::Entity,
+        component: TypeId,
+        f: &mut dyn FnMut(&mut dyn Inspectable),
+    );
+
+    /// Inspects the component of the entity given, if it has one of that type and it is
+    /// registered.
+    fn inspect_component(&mut self, entity: Self::Entity, component: TypeId, ui: &mut egui::Ui) {
+        self.visit_component(entity, component, &mut |component| component.inspect_ui(ui));
+    }
+}
+
+/// A component of an entity, as shown in the tree.
+#[derive(Clone, Debug)]
+pub struct ComponentInfo {
+    pub type_id: TypeId,
+    /// The full name of the component type, borrowed if it is known statically, e.g. for registered
+    /// components.
+    pub type_name: Cow<'static, str>,
+    /// Whether an inspector has been registered for the component type.
+    pub registered: bool,
+}
+
+/// Exposes an ECS world as a [`TreeNode`], with entities as nodes and components as children.
+pub struct WorldTree<'w, W: EcsWorld> {
+    world: &'w mut W,
+}
+
+impl<'w, W: EcsWorld> WorldTree<'w, W> {
+    pub fn new(world: &'w mut W) -> Self {
+        Self { world }
+    }
+}
+
+impl<W: EcsWorld> Inspectable for WorldTree<'_, W> {
+    fn inspect_ui_outside(&mut self, _name: &str, _ui: &mut egui::Ui) {}
+
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("{} entities", self.world.all_entities().len()));
+    }
+}
+
+impl<W: EcsWorld> TreeNode for WorldTree<'_, W> {
+    fn inspect_child(&mut self, this_id: u64, search_id: u64, ui: &mut egui::Ui) {
+        if this_id == search_id {
+            self.inspect_ui(ui);
+            return;
+        }
+
+        for entity in self.world.all_entities() {
+            let entity_id = child_id(this_id, self.world.entity_bits(entity));
+            let mut node = EntityNode::new(&mut *self.world, entity);
+            if node.find_child(entity_id, search_id, ui) {
+                return;
+            }
+        }
+    }
+
+    fn contents_ui(&mut self, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
+        for entity in self.world.all_entities() {
+            let entity_id = child_id(id, self.world.entity_bits(entity));
+            let name = self.world.entity_name(entity);
+            EntityNode::new(&mut *self.world, entity).node_ui(&name, entity_id, selected, ui);
+        }
+    }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        for entity in self.world.all_entities() {
+            let entity_id = child_id(id, self.world.entity_bits(entity));
+            let name = self.world.entity_name(entity);
+            f(
+                &name,
+                entity_id,
+                &mut EntityNode::new(&mut *self.world, entity),
+            );
+        }
+    }
+}
+
+/// An entity of a [`WorldTree`], whose fields are its registered components.
+struct EntityNode<'w, W: EcsWorld> {
+    world: &'w mut W,
+    entity: W::Entity,
+    components: Vec<ComponentInfo>,
+}
+
+impl<'w, W: EcsWorld> EntityNode<'w, W> {
+    fn new(world: &'w mut W, entity: W::Entity) -> Self {
+        let components = world.entity_components(entity);
+        Self {
+            world,
+            entity,
+            components,
+        }
+    }
+
+    fn component(&mut self, info: ComponentInfo) -> ComponentNode<'_, W> {
+        ComponentNode {
+            world: &mut *self.world,
+            entity: self.entity,
+            info,
+        }
+    }
+
+    /// Inspects this entity or the component of it with the ID given, returning whether it was
+    /// found.
+    fn find_child(&mut self, this_id: u64, search_id: u64, ui: &mut egui::Ui) -> bool {
+        if this_id == search_id {
+            self.inspect_ui(ui);
+            return true;
+        }
+        let component = self
+            .components
+            .iter()
+            .find(|component| child_id(this_id, component.type_id) == search_id);
+        match component.cloned() {
+            Some(info) => {
+                self.component(info).inspect_ui(ui);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<W: EcsWorld> Inspectable for EntityNode<'_, W> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        // Selecting an entity shows all of its components at once
+        for component in self
+            .components
+            .iter()
+            .filter(|component| component.registered)
+        {
+            ui.label(short_type_name(&component.type_name));
+            self.world
+                .inspect_component(self.entity, component.type_id, ui);
+        }
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        for component in self
+            .components
+            .iter()
+            .filter(|component| component.registered)
+        {
+            let name = short_type_name(&component.type_name);
+            self.world
+                .visit_component(self.entity, component.type_id, &mut |component| {
+                    f(name, component)
+                });
+        }
+    }
+}
+
+impl<W: EcsWorld> TreeNode for EntityNode<'_, W> {
+    fn inspect_child(&mut self, this_id: u64, search_id: u64, ui: &mut egui::Ui) {
+        self.find_child(this_id, search_id, ui);
+    }
+
+    fn node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
+        crate::tree::default_parent_node_ui(
+            "Entity",
+            name,
+            id,
+            selected,
+            ui,
+            |id, selected, ui| self.contents_ui(id, selected, ui),
+        );
+    }
+
+    fn contents_ui(&mut self, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
+        for info in self.components.clone() {
+            let component_id = child_id(id, info.type_id);
+            let name = short_type_name(&info.type_name).to_owned();
+            self.component(info)
+                .node_ui(&name, component_id, selected, ui);
+        }
+    }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        for info in self.components.clone() {
+            let component_id = child_id(id, info.type_id);
+            let name = short_type_name(&info.type_name).to_owned();
+            f(&name, component_id, &mut self.component(info));
+        }
+    }
+}
+
+/// A component of an entity of a [`WorldTree`], which is a leaf of the tree.
+struct ComponentNode<'w, W: EcsWorld> {
+    world: &'w mut W,
+    entity: W::Entity,
+    info: ComponentInfo,
+}
+
+impl<W: EcsWorld> Inspectable for ComponentNode<'_, W> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        if self.info.registered {
+            self.world
+                .inspect_component(self.entity, self.info.type_id, ui);
+        } else {
+            ui.add_enabled_ui(false, |ui| {
+                ui.label("No inspector registered for this component")
+            });
+        }
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        self.world
+            .visit_component(self.entity, self.info.type_id, &mut |component| {
+                component.visit_fields(f)
+            });
+    }
+}
+
+impl<W: EcsWorld> TreeNode for ComponentNode<'_, W> {
+    fn inspect_child(&mut self, this_id: u64, search_id: u64, ui: &mut egui::Ui) {
+        if this_id == search_id {
+            self.inspect_ui(ui);
+        }
+    }
+
+    fn node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(self.info.registered, |ui| {
+            crate::tree::default_node_ui(&self.info.type_name, name, id, selected, ui)
+        });
+    }
+
+    // Only components of the same type can be edited together
+    fn node_type_name(&self) -> &'static str {
+        match self.info.type_name {
+            Cow::Borrowed(type_name) => type_name,
+            Cow::Owned(_) => std::any::type_name::<Self>(),
+        }
+    }
+}
+
+/// Removes the module paths from a type name, e.g. `game::Player` becomes `Player`.
+fn short_type_name(type_name: &str) -> &str {
+    let path = type_name.split('<').next().unwrap_or(type_name);
+    match path.rfind("::") {
+        Some(idx) => &type_name[idx + 2..],
+        None => type_name,
+    }
+}
+
+fn child_id(parent_id: u64, key: impl Hash) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::default();
+    hasher.write_u64(parent_id);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Component types registered for an ECS crate, along with the function used to inspect them.
+#[cfg(any(feature = "bevy_ecs", feature = "hecs"))]
+pub(crate) struct ComponentRegistry<F> {
+    components: std::sync::RwLock<std::collections::BTreeMap<TypeId, F>>,
+}
+
+#[cfg(any(feature = "bevy_ecs", feature = "hecs"))]
+impl<F: Copy> ComponentRegistry<F> {
+    pub const fn new() -> Self {
+        Self {
+            components: std::sync::RwLock::new(std::collections::BTreeMap::new()),
+        }
+    }
+
+    pub fn register(&self, type_id: TypeId, inspect: F) {
+        self.components.write().unwrap().insert(type_id, inspect);
+    }
+
+    pub fn unregister(&self, type_id: TypeId) -> bool {
+        self.components.write().unwrap().remove(&type_id).is_some()
+    }
+
+    pub fn get(&self, type_id: TypeId) -> Option<F> {
+        self.components.read().unwrap().get(&type_id).copied()
+    }
+}
@@ -0,0 +1,221 @@
+//! A world-space grid, rulers and a readout of the position of the cursor over the viewport,
+//! along with snapping of positions to the grid.
+
+use egui::{Align2, Color32, FontId, Pos2, Rect, Stroke, Vec2};
+
+/// The width of the rulers along the edges of the viewport, in points.
+const RULER_WIDTH: f32 = 16.;
+/// The minimum distance between the lines of the grid, in points. Coarser lines are shown when
+/// zoomed out further.
+const MIN_LINE_DISTANCE: f32 = 8.;
+/// The minimum distance between labeled ticks of the rulers, in points.
+const MIN_TICK_DISTANCE: f32 = 60.;
+
+/// Maps positions between the world and the screen, for a viewport showing an axis-aligned area
+/// of the world.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewMapping {
+    /// The area of the screen the viewport covers.
+    pub screen: Rect,
+    /// The area of the world shown in the viewport. It may have a negative size, e.g. if the
+    /// world is shown upside down.
+    pub world: Rect,
+}
+
+impl ViewMapping {
+    fn scale(&self) -> Vec2 {
+        self.screen.size() / self.world.size()
+    }
+
+    pub fn to_screen(&self, world: Pos2) -> Pos2 {
+        self.screen.min + (world - self.world.min) * self.scale()
+    }
+
+    pub fn to_world(&self, screen: Pos2) -> Pos2 {
+        self.world.min + (screen - self.screen.min) / self.scale()
+    }
+}
+
+/// The settings of the guides shown over the viewport.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    pub show_grid: bool,
+    /// The distance between the lines of the grid, and the step positions are snapped to, in
+    /// world units.
+    pub spacing: f32,
+    /// Whether positions are snapped to the grid by [`snap`](Self::snap).
+    pub snap: bool,
+    pub show_rulers: bool,
+    pub show_cursor_position: bool,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            show_grid: false,
+            spacing: 32.,
+            snap: false,
+            show_rulers: false,
+            show_cursor_position: true,
+        }
+    }
+}
+
+impl Grid {
+    /// Returns the closest position on the grid to the one given when snapping is enabled, or the
+    /// position itself otherwise. Used when dragging things in the viewport.
+    pub fn snap(&self, pos: Pos2) -> Pos2 {
+        if !self.snap || self.spacing <= 0. {
+            return pos;
+        }
+        let snap = |value: f32| (value / self.spacing).round() * self.spacing;
+        Pos2::new(snap(pos.x), snap(pos.y))
+    }
+
+    /// Paints the guides enabled over the viewport.
+    pub fn paint(&self, ui: &egui::Ui, mapping: &ViewMapping) {
+        let painter = ui.painter_at(mapping.screen);
+        if self.show_grid && self.spacing > 0. {
+            paint_grid(&painter, mapping, self.spacing);
+        }
+        if self.show_rulers {
+            paint_rulers(&painter, mapping);
+        }
+        if self.show_cursor_position {
+            let hovered = ui.ctx().pointer_hover_pos();
+            if let Some(pos) = hovered.filter(|pos| mapping.screen.contains(*pos)) {
+                let world = mapping.to_world(pos);
+                let text = format!("{:.1}, {:.1}", world.x, world.y);
+                let corner = mapping.screen.left_bottom() + Vec2::new(4., -4.);
+                let galley = painter.layout_no_wrap(text, FontId::monospace(12.), Color32::WHITE);
+                let rect =
+                    Align2::LEFT_BOTTOM.anchor_rect(Rect::from_min_size(corner, galley.size()));
+                painter.rect_filled(rect.expand(2.), 2., Color32::from_black_alpha(160));
+                painter.galley(rect.min, galley);
+            }
+        }
+    }
+
+    /// Shows the settings of the guides.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.show_grid, "Grid");
+        ui.horizontal(|ui| {
+            ui.label("Spacing");
+            ui.add(
+                egui::DragValue::new(&mut self.spacing)
+                    .clamp_range(0.01..=f32::MAX)
+                    .speed(0.5),
+            );
+        });
+        ui.checkbox(&mut self.snap, "Snap to grid");
+        ui.checkbox(&mut self.show_rulers, "Rulers");
+        ui.checkbox(&mut self.show_cursor_position, "Cursor position");
+    }
+}
+
+/// Returns the smallest multiple of `step` by a power of two whose size on screen is at least
+/// `min_distance`.
+fn coarsen(step: f32, points_per_unit: f32, min_distance: f32) -> f32 {
+    let mut step = step;
+    while step * points_per_unit < min_distance && step.is_finite() {
+        step *= 2.;
+    }
+    step
+}
+
+/// Returns the multiples of `step` in `[from, to]`, in either order, along with the number they
+/// are multiplied by.
+fn multiples(from: f32, to: f32, step: f32) -> impl Iterator<Item = (i64, f32)> {
+    let (from, to) = (from.min(to), from.max(to));
+    let (first, last) = if step > 0. && step.is_finite() {
+        ((from / step).ceil() as i64, (to / step).floor() as i64)
+    } else {
+        (1, 0)
+    };
+    (first..=last).map(move |idx| (idx, idx as f32 * step))
+}
+
+fn paint_grid(painter: &egui::Painter, mapping: &ViewMapping, spacing: f32) {
+    let scale = mapping.scale().abs();
+    let line = Stroke::new(1., Color32::from_white_alpha(24));
+    let axis = Stroke::new(1., Color32::from_white_alpha(96));
+    let (world, screen) = (mapping.world, mapping.screen);
+
+    let step = coarsen(spacing, scale.x, MIN_LINE_DISTANCE);
+    for (idx, x) in multiples(world.min.x, world.max.x, step) {
+        let screen_x = mapping.to_screen(Pos2::new(x, 0.)).x;
+        let stroke = if idx == 0 { axis } else { line };
+        painter.vline(screen_x, screen.y_range(), stroke);
+    }
+    let step = coarsen(spacing, scale.y, MIN_LINE_DISTANCE);
+    for (idx, y) in multiples(world.min.y, world.max.y, step) {
+        let screen_y = mapping.to_screen(Pos2::new(0., y)).y;
+        let stroke = if idx == 0 { axis } else { line };
+        painter.hline(screen.x_range(), screen_y, stroke);
+    }
+}
+
+/// Returns the distance between labeled ticks of a ruler: The smallest of 1, 2 or 5 times a power
+/// of ten whose size on screen is at least 60 points.
+pub fn ruler_step(points_per_unit: f32) -> f32 {
+    let min_step = MIN_TICK_DISTANCE / points_per_unit;
+    let magnitude = 10f32.powf(min_step.log10().floor());
+    [1., 2., 5., 10.]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= min_step)
+        .unwrap_or(10. * magnitude)
+}
+
+fn paint_rulers(painter: &egui::Painter, mapping: &ViewMapping) {
+    let scale = mapping.scale().abs();
+    let (world, screen) = (mapping.world, mapping.screen);
+    let background = Color32::from_black_alpha(180);
+    let tick = Stroke::new(1., Color32::from_gray(200));
+    let font = FontId::monospace(10.);
+
+    let top = Rect::from_min_size(screen.min, Vec2::new(screen.width(), RULER_WIDTH));
+    let left = Rect::from_min_size(screen.min, Vec2::new(RULER_WIDTH, screen.height()));
+    painter.rect_filled(top, 0., background);
+    painter.rect_filled(left, 0., background);
+
+    // Every fifth tick is labeled
+    let step = ruler_step(scale.x);
+    for (idx, x) in multiples(world.min.x, world.max.x, step / 5.) {
+        let screen_x = mapping.to_screen(Pos2::new(x, 0.)).x;
+        let labeled = idx % 5 == 0;
+        let length = if labeled {
+            RULER_WIDTH
+        } else {
+            RULER_WIDTH / 3.
+        };
+        painter.vline(screen_x, top.bottom() - length..=top.bottom(), tick);
+        if labeled {
+            let pos = Pos2::new(screen_x + 2., top.top());
+            let label = ruler_label(x, step);
+            painter.text(pos, Align2::LEFT_TOP, label, font.clone(), tick.color);
+        }
+    }
+    let step = ruler_step(scale.y);
+    for (idx, y) in multiples(world.min.y, world.max.y, step / 5.) {
+        let screen_y = mapping.to_screen(Pos2::new(0., y)).y;
+        let labeled = idx % 5 == 0;
+        let length = if labeled {
+            RULER_WIDTH
+        } else {
+            RULER_WIDTH / 3.
+        };
+        painter.hline(left.right() - length..=left.right(), screen_y, tick);
+        if labeled {
+            let pos = Pos2::new(left.left() + 1., screen_y + 2.);
+            let label = ruler_label(y, step);
+            painter.text(pos, Align2::LEFT_TOP, label, font.clone(), tick.color);
+        }
+    }
+}
+
+/// Formats a value labeled in a ruler with as many decimals as its step needs.
+pub fn ruler_label(value: f32, step: f32) -> String {
+    let decimals = (-step.log10().floor()).max(0.) as usize;
+    format!("{:.*}", decimals, value)
+}
@@ -0,0 +1,261 @@
+//! Hot reloading of game logic compiled as a `cdylib`.
+//!
+//! The game is split into a host executable, which owns the window and the state tree, and a
+//! library exporting the systems that update that state. A [`HotLibrary`] loads the library and
+//! reloads it whenever it is rebuilt, so that changes to the systems take effect without
+//! restarting the game nor losing its state:
+//!
+//! ```ignore
+//! // In the library, built with `crate-type = ["cdylib"]`:
+//! #[no_mangle]
+//! pub fn update(game: &mut Game) {
+//!     game.ball.position += game.ball.velocity;
+//! }
+//!
+//! // In the host:
+//! window.set_hot_library(Some(HotLibrary::load("target/debug/libgame.so")));
+//! loop {
+//!     // ...
+//!     if let Some(library) = window.hot_library() {
+//!         // SAFETY: `update` takes a `&mut Game` in every version of the library
+//!         let _ = unsafe { library.call("update", &mut game) };
+//!     }
+//!     window.display_and_inspect(&mut game);
+//! }
+//! ```
+//!
+//! Both the host and the library must be built by the same compiler, and the types shared by them
+//! (like `Game` above) must not change their layout while the game is running.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use libloading::Library;
+
+/// How often the library is checked for changes.
+pub const CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The state of a [`HotLibrary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The library was loaded successfully, for the nth time (1 for the first load).
+    Loaded { generation: u32 },
+    /// The library changed, and will be reloaded once it stops changing.
+    Pending,
+    /// The last attempt to load the library failed. The previous version of the library, if any,
+    /// is still in use.
+    Failed { error: String },
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Loaded { generation } => write!(f, "Loaded (version {})", generation),
+            Status::Pending => write!(f, "Reloading..."),
+            Status::Failed { error } => write!(f, "Failed: {}", error),
+        }
+    }
+}
+
+/// A dynamic library that is reloaded whenever the file it was loaded from changes.
+///
+/// The library is copied before being loaded, so that the original file can be overwritten by the
+/// compiler, and the copy is removed when it is unloaded.
+pub struct HotLibrary {
+    path: PathBuf,
+    /// The library loaded, along with the path of the copy it was loaded from.
+    library: Option<(Library, PathBuf)>,
+    generation: u32,
+    /// The modification time of the file the last time it was loaded.
+    modified: Option<SystemTime>,
+    /// The modification time of the file when a change was detected, if it is yet to be reloaded.
+    pending: Option<SystemTime>,
+    last_check: Instant,
+    status: Status,
+}
+
+impl HotLibrary {
+    /// Loads the library at the path given, which will be reloaded when it changes.
+    ///
+    /// Errors are reported through [`status`](Self::status) instead of being returned, so that
+    /// the library can be fixed and rebuilt without restarting.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let mut library = Self {
+            path: path.into(),
+            library: None,
+            generation: 0,
+            modified: None,
+            pending: None,
+            last_check: Instant::now(),
+            status: Status::Pending,
+        };
+        let _ = library.reload();
+        library
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// The number of times the library was loaded successfully.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.library.is_some()
+    }
+
+    /// Reloads the library now. If this fails, the previous version is kept.
+    pub fn reload(&mut self) -> io::Result<()> {
+        self.pending = None;
+        // Failed builds are not retried until they change again
+        self.modified = modified(&self.path);
+
+        let copy = std::env::temp_dir().join(format!(
+            "guiedit-{}-{}-{}",
+            std::process::id(),
+            self.generation + 1,
+            self.path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        ));
+        let result = std::fs::copy(&self.path, &copy).and_then(|_| {
+            // SAFETY: Initialization routines of the library are trusted, as is the rest of it
+            unsafe { Library::new(&copy) }.map_err(io::Error::other)
+        });
+
+        match result {
+            Ok(library) => {
+                self.unload();
+                self.library = Some((library, copy));
+                self.generation += 1;
+                self.status = Status::Loaded {
+                    generation: self.generation,
+                };
+                Ok(())
+            }
+            Err(error) => {
+                let _ = std::fs::remove_file(&copy);
+                self.status = Status::Failed {
+                    error: error.to_string(),
+                };
+                Err(error)
+            }
+        }
+    }
+
+    /// Reloads the library if it changed since it was last loaded, and returns whether it was
+    /// reloaded successfully. Meant to be called every frame.
+    ///
+    /// The file is checked every [`CHECK_INTERVAL`] at most, and is only reloaded once it stops
+    /// changing, since it may still be being written by the compiler.
+    pub fn reload_if_changed(&mut self) -> bool {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return false;
+        }
+        self.last_check = Instant::now();
+
+        // The file may not exist for a while during a rebuild
+        let Some(modified) = modified(&self.path) else {
+            return false;
+        };
+        if Some(modified) == self.modified {
+            return false;
+        }
+        if self.pending != Some(modified) {
+            self.pending = Some(modified);
+            self.status = Status::Pending;
+            return false;
+        }
+        self.reload().is_ok()
+    }
+
+    /// Calls the system with the name given, exported by the library, with the state given.
+    ///
+    /// # Safety
+    /// The library must export a function with that name and the signature `fn(&mut S)`, such as
+    /// `#[no_mangle] pub fn update(state: &mut S)`, built with the same compiler and the same
+    /// definition of `S` as the caller.
+    pub unsafe fn call<S>(&self, name: &str, state: &mut S) -> io::Result<()> {
+        let system: fn(&mut S) = self.get(name)?;
+        system(state);
+        Ok(())
+    }
+
+    /// Returns the symbol with the name given, exported by the library, e.g. a function pointer.
+    ///
+    /// # Safety
+    /// The symbol must be of type `T`, and must not be used after the library is reloaded.
+    pub unsafe fn get<T: Copy>(&self, name: &str) -> io::Result<T> {
+        let (library, _) = self
+            .library
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "the library is not loaded"))?;
+        library
+            .get::<T>(name.as_bytes())
+            .map(|symbol| *symbol)
+            .map_err(|error| io::Error::new(io::ErrorKind::NotFound, error))
+    }
+
+    /// Shows the status of the library in the top right corner, along with a window with the
+    /// error if it could not be loaded.
+    pub fn status_ui(&self, ctx: &egui::Context) {
+        let visuals = ctx.style().visuals.clone();
+        let (text, color) = match &self.status {
+            Status::Loaded { generation } => (
+                format!("🔥 Hot reload: version {}", generation),
+                visuals.text_color(),
+            ),
+            Status::Pending => ("⏳ Reloading...".to_owned(), visuals.warn_fg_color),
+            Status::Failed { .. } => ("⚠ Hot reload failed".to_owned(), visuals.error_fg_color),
+        };
+        egui::Area::new("guiedit hot reload status")
+            .anchor(egui::Align2::RIGHT_TOP, [-8., 4.])
+            .show(ctx, |ui| {
+                ui.colored_label(color, text)
+                    .on_hover_text(self.path.display().to_string());
+            });
+
+        if let Status::Failed { error } = &self.status {
+            egui::Window::new("⚠ Hot reload failed")
+                .id(egui::Id::new("guiedit hot reload error"))
+                .anchor(egui::Align2::RIGHT_TOP, [-8., 32.])
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(format!("Could not load {}:", self.path.display()));
+                    ui.colored_label(visuals.error_fg_color, error);
+                    if self.is_loaded() {
+                        ui.label(format!("Version {} is still running.", self.generation));
+                    }
+                });
+        }
+    }
+
+    fn unload(&mut self) {
+        if let Some((library, copy)) = self.library.take() {
+            drop(library);
+            let _ = std::fs::remove_file(copy);
+        }
+    }
+}
+
+impl Drop for HotLibrary {
+    fn drop(&mut self) {
+        self.unload();
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,292 @@
+//! A viewer for images and textures in the inspector, which fits them in the space available and
+//! can be zoomed, panned and limited to some of their channels.
+
+use std::hash::{Hash, Hasher};
+
+use egui::{Color32, Pos2, Rect, Sense, Vec2};
+
+/// The maximum height of the viewer, so that large images leave room for the rest of the
+/// inspector.
+pub const MAX_HEIGHT: f32 = 256.;
+const MIN_ZOOM: f32 = 1. / 32.;
+const MAX_ZOOM: f32 = 64.;
+
+/// The color channels of an image that are shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Channels {
+    pub r: bool,
+    pub g: bool,
+    pub b: bool,
+    pub a: bool,
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl Channels {
+    pub const ALL: Channels = Channels {
+        r: true,
+        g: true,
+        b: true,
+        a: true,
+    };
+
+    pub fn is_all(self) -> bool {
+        self == Self::ALL
+    }
+
+    /// Returns how a pixel, in unmultiplied RGBA, is shown with these channels.
+    ///
+    /// Hidden color channels are zeroed, and hidden alpha makes the pixel opaque. If alpha is the
+    /// only channel shown, it is shown in grayscale.
+    pub fn apply(self, [r, g, b, a]: [u8; 4]) -> [u8; 4] {
+        let mask = |shown, value| if shown { value } else { 0 };
+        if !(self.r || self.g || self.b) {
+            let a = mask(self.a, a);
+            return [a, a, a, u8::MAX];
+        }
+        [
+            mask(self.r, r),
+            mask(self.g, g),
+            mask(self.b, b),
+            if self.a { a } else { u8::MAX },
+        ]
+    }
+
+    /// Shows a toggle for each channel, returning whether any was toggled.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        for (shown, name) in [
+            (&mut self.r, "R"),
+            (&mut self.g, "G"),
+            (&mut self.b, "B"),
+            (&mut self.a, "A"),
+        ] {
+            if ui.selectable_label(*shown, name).clicked() {
+                *shown = !*shown;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Returns the image with the size given, from its pixels in unmultiplied RGBA, as shown with the
+/// channels given.
+pub fn masked_image(size: [usize; 2], pixels: &[u8], channels: Channels) -> egui::ColorImage {
+    let pixels = pixels.chunks_exact(4).map(|pixel| {
+        let [r, g, b, a] = channels.apply([pixel[0], pixel[1], pixel[2], pixel[3]]);
+        Color32::from_rgba_unmultiplied(r, g, b, a)
+    });
+    egui::ColorImage {
+        size,
+        pixels: pixels.collect(),
+    }
+}
+
+/// How often, in seconds, the pixels of the images copied by [`image_copy`] are read again, since
+/// they may change without their source or size changing.
+pub const COPY_REFRESH_INTERVAL: f64 = 0.5;
+
+/// A copy of an image uploaded to egui, along with what it was copied from.
+#[derive(Clone)]
+struct ImageCopy {
+    texture: egui::TextureHandle,
+    source: u64,
+    size: [u32; 2],
+    channels: Channels,
+    /// The hash of the pixels copied.
+    pixels: u64,
+    read_at: f64,
+}
+
+/// Returns the texture showing the image given with the channels given, as a copy uploaded to
+/// egui that is kept with the ID given, e.g. for images living on the GPU.
+///
+/// `source` identifies the image, e.g. by the handle of its texture, and `read_pixels` reads its
+/// pixels in unmultiplied RGBA. The copy is updated at once when the source, size or channels
+/// change, and otherwise the pixels are read every [`COPY_REFRESH_INTERVAL`] and uploaded again if
+/// they changed.
+pub fn image_copy(
+    ctx: &egui::Context,
+    id: egui::Id,
+    source: u64,
+    size: [u32; 2],
+    channels: Channels,
+    read_pixels: impl FnOnce() -> Option<Vec<u8>>,
+) -> egui::TextureId {
+    let time = ctx.input().time;
+    let copy = ctx.data().get_temp::<ImageCopy>(id);
+    let copy = copy.filter(|copy| (copy.source, copy.size) == (source, size));
+    let copy_channels = copy.as_ref().map(|copy| copy.channels);
+    if let Some(copy) = &copy {
+        if copy.channels == channels && time - copy.read_at < COPY_REFRESH_INTERVAL {
+            return copy.texture.id();
+        }
+    }
+
+    let pixel_count = size[0] as usize * size[1] as usize;
+    let pixels = read_pixels().filter(|pixels| pixel_count > 0 && pixels.len() == pixel_count * 4);
+    let hash = {
+        let mut hasher = std::collections::hash_map::DefaultHasher::default();
+        pixels.hash(&mut hasher);
+        hasher.finish()
+    };
+    let texture = match copy {
+        Some(copy) if copy_channels == Some(channels) && copy.pixels == hash => copy.texture,
+        copy => {
+            let image = match &pixels {
+                Some(pixels) => {
+                    masked_image([size[0] as usize, size[1] as usize], pixels, channels)
+                }
+                None => egui::ColorImage::new([1, 1], Color32::TRANSPARENT),
+            };
+            match copy {
+                Some(mut copy) => {
+                    copy.texture.set(image, egui::TextureFilter::Nearest);
+                    copy.texture
+                }
+                None => ctx.load_texture("guiedit image", image, egui::TextureFilter::Nearest),
+            }
+        }
+    };
+    let texture_id = texture.id();
+    ctx.data().insert_temp(
+        id,
+        ImageCopy {
+            texture,
+            source,
+            size,
+            channels,
+            pixels: hash,
+            read_at: time,
+        },
+    );
+    texture_id
+}
+
+/// Returns the pixel at the coordinates given of an image with the width given, from its pixels in
+/// RGBA, if it is inside the image.
+pub fn pixel_at(pixels: &[u8], width: u32, [x, y]: [u32; 2]) -> Option<[u8; 4]> {
+    if x >= width {
+        return None;
+    }
+    let start = (y as usize * width as usize + x as usize) * 4;
+    let pixel = pixels.get(start..start + 4)?;
+    Some([pixel[0], pixel[1], pixel[2], pixel[3]])
+}
+
+/// Shows the coordinates of a pixel, along with its color in unmultiplied RGBA, if known.
+pub fn pixel_ui(ui: &mut egui::Ui, [x, y]: [u32; 2], color: Option<[u8; 4]>) {
+    ui.horizontal(|ui| {
+        ui.label(format!("({}, {})", x, y));
+        if let Some([r, g, b, a]) = color {
+            let color = Color32::from_rgba_unmultiplied(r, g, b, a);
+            egui::color_picker::show_color(ui, color, Vec2::splat(ui.spacing().interact_size.y));
+            ui.label(format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a));
+            ui.weak(format!("({}, {}, {}, {})", r, g, b, a));
+        }
+    });
+}
+
+/// The zoom, pan and channels of an image viewer, which are kept by egui between frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ImageView {
+    /// The number of points each pixel of the image takes, or `None` to fit the image.
+    pub zoom: Option<f32>,
+    /// The position of the image shown at the center of the viewer, in pixels from the center of
+    /// the image.
+    pub pan: Vec2,
+    pub channels: Channels,
+}
+
+impl ImageView {
+    /// Returns the view of the viewer with the ID given, which fits the image the first time.
+    pub fn load(ctx: &egui::Context, id: egui::Id) -> Self {
+        ctx.data().get_temp(id).unwrap_or_default()
+    }
+
+    pub fn store(self, ctx: &egui::Context, id: egui::Id) {
+        ctx.data().insert_temp(id, self);
+    }
+
+    /// Returns the zoom at which an image of the size given fits in a viewer of the size given.
+    pub fn fit_zoom(image_size: Vec2, viewport_size: Vec2) -> f32 {
+        (viewport_size.x / image_size.x).min(viewport_size.y / image_size.y)
+    }
+
+    /// Shows buttons to fit the image or show it at its native size, along with the channel
+    /// toggles. Returns whether the channels shown changed.
+    pub fn controls_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.horizontal(|ui| {
+            if ui.button("Fit").clicked() {
+                *self = Self {
+                    channels: self.channels,
+                    ..Default::default()
+                };
+            }
+            if ui.button("1:1").clicked() {
+                self.zoom = Some(1.);
+                self.pan = Vec2::ZERO;
+            }
+            match self.zoom {
+                Some(zoom) => ui.label(format!("{:.0}%", zoom * 100.)),
+                None => ui.weak("Fit"),
+            };
+            ui.separator();
+            self.channels.ui(ui)
+        })
+        .inner
+    }
+
+    /// Shows the texture given, of the size given in pixels, and returns the pixel hovered, if any.
+    ///
+    /// The image is panned by dragging it, and zoomed around the pointer with Ctrl+scroll.
+    pub fn image_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        texture: egui::TextureId,
+        size: [u32; 2],
+    ) -> Option<[u32; 2]> {
+        let image_size = Vec2::new(size[0] as f32, size[1] as f32).max(Vec2::splat(1.));
+        let width = ui.available_width().max(32.);
+        let viewport_size = Vec2::new(width, (width * image_size.y / image_size.x).min(MAX_HEIGHT));
+        let (rect, response) = ui.allocate_exact_size(viewport_size, Sense::drag());
+        let response = response.on_hover_text("Drag to pan, Ctrl+scroll to zoom");
+
+        let mut zoom = self
+            .zoom
+            .unwrap_or_else(|| Self::fit_zoom(image_size, viewport_size));
+        if let Some(pointer) = response.hover_pos() {
+            let zoom_delta = ui.input().zoom_delta();
+            if zoom_delta != 1. {
+                // The pixel under the pointer stays under it
+                let hovered = self.pan + (pointer - rect.center()) / zoom;
+                zoom = (zoom * zoom_delta).clamp(MIN_ZOOM, MAX_ZOOM);
+                self.zoom = Some(zoom);
+                self.pan = hovered - (pointer - rect.center()) / zoom;
+            }
+        }
+        if response.dragged() {
+            self.zoom = Some(zoom);
+            self.pan -= response.drag_delta() / zoom;
+        }
+
+        let image_rect = Rect::from_center_size(rect.center() - self.pan * zoom, image_size * zoom);
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0., ui.visuals().extreme_bg_color);
+        painter.add(egui::Shape::image(
+            texture,
+            image_rect,
+            Rect::from_min_max(Pos2::ZERO, Pos2::new(1., 1.)),
+            Color32::WHITE,
+        ));
+
+        let pixel = (response.hover_pos()? - image_rect.min) / zoom;
+        let inside = pixel.x >= 0. && pixel.y >= 0. && pixel.x < size[0] as f32;
+        (inside && pixel.y < size[1] as f32).then_some([pixel.x as u32, pixel.y as u32])
+    }
+}
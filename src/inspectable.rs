@@ -1,5 +1,5 @@
 use std::{
-    ops::{DerefMut, RangeInclusive},
+    ops::{Deref, DerefMut, RangeInclusive},
     path::PathBuf,
     time::Duration,
 };
@@ -14,41 +14,261 @@ pub trait Inspectable {
     /// behavior, thus effectively hiding the object from the inspector while still allowing to
     /// inspect its contents via `inspect_ui`.
     fn inspect_ui_outside(&mut self, name: &str, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            ui.label(name);
-            self.inspect_ui(ui);
-        });
+        // Numeric fields can be plotted over time from their label
+        let plottable = self.value().as_ref().and_then(Value::to_float).is_some();
+        field_ui(name, plottable, ui, |ui| self.inspect_ui(ui));
     }
 
     fn inspect_ui(&mut self, _ui: &mut egui::Ui) {}
+
+    /// Returns the value of this object if it is a primitive (e.g. a number or a string).
+    ///
+    /// This and the rest of reflection functions are used to access objects without an UI, e.g.
+    /// for remote inspection.
+    fn value(&self) -> Option<Value> {
+        None
+    }
+
+    /// Sets the value of this object if it is a primitive, returning whether it could be set.
+    fn set_value(&mut self, _value: Value) -> bool {
+        false
+    }
+
+    /// Calls `f` with the name of each of the fields of this object along with the field itself.
+    fn visit_fields(&mut self, _f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {}
+
+    /// Reloads this object from the file it was loaded from if that file changed, returning
+    /// whether it was reloaded. Only assets, which are watched by an `AssetWatcher`, do so, and
+    /// only with the `asset-reload` feature.
+    #[cfg(feature = "asset-reload")]
+    fn reload_if_changed(&mut self) -> bool {
+        false
+    }
+}
+
+/// Shows the contents given next to a label with the name of a field, as done by the default
+/// implementation of [`Inspectable::inspect_ui_outside`].
+pub fn labeled_ui<R>(
+    name: &str,
+    ui: &mut egui::Ui,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    field_ui(name, false, ui, add_contents)
+}
+
+/// Shows a button with the name of a method, as done for each of the methods given to
+/// `#[inspectable(buttons(...))]`, and returns whether it was clicked.
+pub fn method_button(ui: &mut egui::Ui, name: &str) -> bool {
+    let response = ui.button(name);
+    #[cfg(feature = "testing")]
+    crate::testing::record_button(ui.ctx(), name, response.rect);
+    response.clicked()
+}
+
+fn field_ui<R>(
+    name: &str,
+    plottable: bool,
+    ui: &mut egui::Ui,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    crate::inspector::with_field(ui.ctx().clone(), name, |in_node| {
+        // Fields are given their own id so that the widgets of sibling fields of the same type,
+        // e.g. two matrices, keep separate state
+        ui.push_id(name, |ui| {
+            ui.horizontal(|ui| {
+                if plottable && in_node {
+                    ui.add(egui::Label::new(name).sense(egui::Sense::click()))
+                        .on_hover_text("Right-click to plot")
+                        .context_menu(|ui| {
+                            if ui.button("📈 Plot").clicked() {
+                                crate::watch::request_plot(ui.ctx());
+                                ui.close_menu();
+                            }
+                        });
+                } else {
+                    ui.label(name);
+                }
+                if in_node && crate::inspector::is_mixed(ui.ctx()) {
+                    ui.weak("⁓")
+                        .on_hover_text("The nodes selected have different values");
+                }
+                #[cfg(feature = "testing")]
+                let recorded = crate::testing::record_field(ui.ctx(), name);
+                let contents = ui.scope(add_contents);
+                #[cfg(feature = "testing")]
+                crate::testing::record_field_rect(ui.ctx(), recorded, contents.response.rect);
+                contents.inner
+            })
+            .inner
+        })
+        .inner
+    })
+}
+
+/// Returns the value of the field at the path given, as passed to [`Inspectable::visit_fields`],
+/// or `None` if there is no such field or it has no value.
+pub fn field_value<T: Inspectable + ?Sized>(value: &mut T, path: &[String]) -> Option<Value> {
+    let Some((name, path)) = path.split_first() else {
+        return value.value();
+    };
+
+    let mut result = None;
+    value.visit_fields(&mut |field_name, field| {
+        if result.is_none() && field_name == name {
+            result = field_value(field, path);
+        }
+    });
+    result
+}
+
+/// Sets the value of the field at the path given, as passed to [`Inspectable::visit_fields`],
+/// returning whether there is such a field and it could be set.
+pub fn set_field_value<T: Inspectable + ?Sized>(
+    value: &mut T,
+    path: &[String],
+    new_value: Value,
+) -> bool {
+    let Some((name, path)) = path.split_first() else {
+        return value.set_value(new_value);
+    };
+
+    let mut new_value = Some(new_value);
+    let mut set = false;
+    value.visit_fields(&mut |field_name, field| {
+        if field_name == name {
+            if let Some(new_value) = new_value.take() {
+                set = set_field_value(field, path, new_value);
+            }
+        }
+    });
+    set
+}
+
+/// Returns the path and value of every field of the value given that has a value, recursively and
+/// in the order given by [`Inspectable::visit_fields`].
+pub fn field_values<T: Inspectable + ?Sized>(value: &mut T) -> Vec<(Vec<String>, Value)> {
+    fn visit(
+        value: &mut dyn Inspectable,
+        path: &mut Vec<String>,
+        values: &mut Vec<(Vec<String>, Value)>,
+    ) {
+        if let Some(value) = value.value() {
+            values.push((path.clone(), value));
+        }
+        value.visit_fields(&mut |name, field| {
+            path.push(name.to_owned());
+            visit(field, path, values);
+            path.pop();
+        });
+    }
+
+    let mut values = vec![];
+    value.visit_fields(&mut |name, field| {
+        visit(field, &mut vec![name.to_owned()], &mut values);
+    });
+    values
+}
+
+/// A primitive value, as exposed by [`Inspectable::value`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "remote",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    #[cfg_attr(feature = "remote", serde(rename = "uint"))]
+    UInt(u64),
+    Float(f64),
+    String(String),
+}
+
+impl Value {
+    /// Converts the value to an integer type, if it is a number that fits in it without losing
+    /// precision.
+    pub fn to_integer<T: TryFrom<i64> + TryFrom<u64>>(&self) -> Option<T> {
+        match *self {
+            Value::Int(x) => T::try_from(x).ok(),
+            Value::UInt(x) => T::try_from(x).ok(),
+            // The bounds are powers of two, which floats represent exactly: -2^63, 2^63 and 2^64
+            Value::Float(x) if x.fract() == 0. && x >= i64::MIN as f64 && x < i64::MAX as f64 => {
+                T::try_from(x as i64).ok()
+            }
+            Value::Float(x) if x.fract() == 0. && x >= 0. && x < u64::MAX as f64 => {
+                T::try_from(x as u64).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts the value to a float, if it is a number.
+    pub fn to_float(&self) -> Option<f64> {
+        match *self {
+            Value::Int(x) => Some(x as f64),
+            Value::UInt(x) => Some(x as f64),
+            Value::Float(x) => Some(x),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! implement_inspectable_for_numeric {
-    ($NumericType: ty) => {
+    ($NumericType: ty, $Variant: ident, $from_value: expr) => {
         impl Inspectable for $NumericType {
             fn inspect_ui(&mut self, ui: &mut egui::Ui) {
                 ui.add(egui::DragValue::new(self));
             }
+
+            fn value(&self) -> Option<Value> {
+                Some(Value::$Variant(*self as _))
+            }
+
+            fn set_value(&mut self, value: Value) -> bool {
+                let from_value: fn(&Value) -> Option<$NumericType> = $from_value;
+                match from_value(&value) {
+                    Some(value) => {
+                        *self = value;
+                        true
+                    }
+                    None => false,
+                }
+            }
         }
     };
 }
-implement_inspectable_for_numeric!(i8);
-implement_inspectable_for_numeric!(u8);
-implement_inspectable_for_numeric!(i16);
-implement_inspectable_for_numeric!(u16);
-implement_inspectable_for_numeric!(i32);
-implement_inspectable_for_numeric!(u32);
-implement_inspectable_for_numeric!(i64);
-implement_inspectable_for_numeric!(u64);
-implement_inspectable_for_numeric!(isize);
-implement_inspectable_for_numeric!(usize);
-implement_inspectable_for_numeric!(f32);
-implement_inspectable_for_numeric!(f64);
+implement_inspectable_for_numeric!(i8, Int, Value::to_integer);
+implement_inspectable_for_numeric!(u8, UInt, Value::to_integer);
+implement_inspectable_for_numeric!(i16, Int, Value::to_integer);
+implement_inspectable_for_numeric!(u16, UInt, Value::to_integer);
+implement_inspectable_for_numeric!(i32, Int, Value::to_integer);
+implement_inspectable_for_numeric!(u32, UInt, Value::to_integer);
+implement_inspectable_for_numeric!(i64, Int, Value::to_integer);
+implement_inspectable_for_numeric!(u64, UInt, Value::to_integer);
+implement_inspectable_for_numeric!(isize, Int, Value::to_integer);
+implement_inspectable_for_numeric!(usize, UInt, Value::to_integer);
+implement_inspectable_for_numeric!(f32, Float, |value| value.to_float().map(|x| x as f32));
+implement_inspectable_for_numeric!(f64, Float, Value::to_float);
 
 impl Inspectable for bool {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         ui.checkbox(self, "");
     }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Bool(*self))
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        match value {
+            Value::Bool(value) => {
+                *self = value;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Inspectable for PathBuf {
@@ -58,6 +278,10 @@ impl Inspectable for PathBuf {
             ui.text_edit_singleline(&mut self.to_string_lossy().into_owned())
         });
     }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::String(self.to_string_lossy().into_owned()))
+    }
 }
 
 impl Inspectable for Duration {
@@ -70,12 +294,43 @@ impl Inspectable for Duration {
             *self = Duration::from_secs_f64(secs);
         }
     }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Float(self.as_secs_f64()))
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        match value
+            .to_float()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        {
+            Some(duration) => {
+                *self = duration;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl Inspectable for String {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         ui.text_edit_singleline(self);
     }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::String(self.clone()))
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        match value {
+            Value::String(value) => {
+                *self = value;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 pub struct ClampedValue<'v, T: egui::emath::Numeric> {
@@ -92,6 +347,21 @@ impl<T: egui::emath::Numeric> Inspectable for ClampedValue<'_, T> {
                 .speed(self.speed),
         );
     }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Float(self.value.to_f64()))
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        match value.to_float() {
+            Some(value) => {
+                let (min, max) = (self.range.start().to_f64(), self.range.end().to_f64());
+                *self.value = T::from_f64(value.clamp(min, max));
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 // TODO: Figure out how to take in a const ref instead
@@ -102,6 +372,10 @@ impl<'v, T: Inspectable> Inspectable for ReadOnlyValue<'v, T> {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         ui.add_enabled_ui(false, |ui| self.0.inspect_ui(ui));
     }
+
+    fn value(&self) -> Option<Value> {
+        self.0.value()
+    }
 }
 
 // TODO: TreeElement for Option<T>
@@ -126,14 +400,48 @@ impl<T: Inspectable + Default> Inspectable for Option<T> {
             }
         }
     }
+
+    fn value(&self) -> Option<Value> {
+        self.as_ref().and_then(T::value)
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        match self {
+            Some(x) => x.set_value(value),
+            None => false,
+        }
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        if let Some(x) = self {
+            x.visit_fields(f);
+        }
+    }
+
+    #[cfg(feature = "asset-reload")]
+    fn reload_if_changed(&mut self) -> bool {
+        match self {
+            Some(x) => x.reload_if_changed(),
+            None => false,
+        }
+    }
 }
 
 impl Inspectable for () {}
 
 impl<T: Inspectable> Inspectable for [T] {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
-        for element in self.into_iter() {
-            element.inspect_ui(ui);
+        for (i, element) in self.iter_mut().enumerate() {
+            // Matches the names given by `visit_fields`, so that elements can be plotted
+            crate::inspector::with_field(ui.ctx().clone(), &i.to_string(), |_| {
+                element.inspect_ui(ui)
+            });
+        }
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        for (i, element) in self.iter_mut().enumerate() {
+            f(&i.to_string(), element);
         }
     }
 }
@@ -142,22 +450,64 @@ impl<T: Inspectable, const X: usize> Inspectable for [T; X] {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         self[..].as_mut().inspect_ui(ui)
     }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        self[..].visit_fields(f)
+    }
 }
 
 impl<T: Inspectable> Inspectable for Vec<T> {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         self[..].inspect_ui(ui)
     }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        self[..].visit_fields(f)
+    }
 }
 
 impl<T: Inspectable + ?Sized> Inspectable for &mut T {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         (*self).inspect_ui(ui)
     }
+
+    fn value(&self) -> Option<Value> {
+        (**self).value()
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        (*self).set_value(value)
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        (*self).visit_fields(f)
+    }
+
+    #[cfg(feature = "asset-reload")]
+    fn reload_if_changed(&mut self) -> bool {
+        (*self).reload_if_changed()
+    }
 }
 
 impl<T: Inspectable + ?Sized> Inspectable for Box<T> {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
         self.deref_mut().inspect_ui(ui)
     }
+
+    fn value(&self) -> Option<Value> {
+        self.deref().value()
+    }
+
+    fn set_value(&mut self, value: Value) -> bool {
+        self.deref_mut().set_value(value)
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        self.deref_mut().visit_fields(f)
+    }
+
+    #[cfg(feature = "asset-reload")]
+    fn reload_if_changed(&mut self) -> bool {
+        self.deref_mut().reload_if_changed()
+    }
 }
@@ -0,0 +1,136 @@
+//! The inspector showing the fields of the nodes selected in the tree.
+//!
+//! While a node is shown by [`inspect_node`], the path of the field being shown is tracked, so
+//! that fields know which node they belong to, as needed to [plot](crate::watch) them.
+
+use crate::{
+    inspectable,
+    tree::{self, TreeNode, ROOT_ID},
+};
+
+/// The node and field currently being inspected.
+#[derive(Clone, Default)]
+struct Inspection {
+    node: Option<u64>,
+    field_path: Vec<String>,
+    /// The paths of the fields whose values differ between the nodes shown by [`inspect_nodes`].
+    mixed: Vec<Vec<String>>,
+}
+
+fn with_inspection<R>(ctx: &egui::Context, f: impl FnOnce(&mut Inspection) -> R) -> R {
+    f(ctx
+        .data()
+        .get_temp_mut_or_default::<Inspection>(egui::Id::new("guiedit::inspector")))
+}
+
+/// Shows the inspector of the node with the ID given, given the root of the tree.
+pub fn inspect_node(root: &mut dyn TreeNode, id: u64, ui: &mut egui::Ui) {
+    let previous = with_inspection(ui.ctx(), |inspection| {
+        let previous = inspection.node.replace(id);
+        (previous, std::mem::take(&mut inspection.field_path))
+    });
+    root.inspect_child(ROOT_ID, id, ui);
+    with_inspection(ui.ctx(), |inspection| {
+        (inspection.node, inspection.field_path) = previous;
+    });
+}
+
+/// Shows the inspector of several nodes with the IDs given at once, given the root of the tree, as
+/// selected in the tree.
+///
+/// If they are all of the same type, the fields of the first node are shown, marking the ones whose
+/// value differs between the nodes, and the fields changed are set on every node. Only fields with
+/// an [`Inspectable::value`] are set, so types that are edited as a whole must expose their parts
+/// through [`Inspectable::visit_fields`] to be edited together.
+///
+/// [`Inspectable::value`]: crate::Inspectable::value
+/// [`Inspectable::visit_fields`]: crate::Inspectable::visit_fields
+pub fn inspect_nodes(root: &mut dyn TreeNode, ids: &[u64], ui: &mut egui::Ui) {
+    let (first, rest) = match ids {
+        [] => return,
+        [id] => return inspect_node(root, *id, ui),
+        [first, rest @ ..] => (*first, rest),
+    };
+
+    let mut type_names = vec![];
+    for &id in ids {
+        tree::find_node(root, ROOT_ID, id, &mut |node| {
+            type_names.push(node.node_type_name())
+        });
+    }
+    if type_names.windows(2).any(|names| names[0] != names[1]) {
+        ui.weak(format!(
+            "{} nodes of different types are selected; Only the first one is edited",
+            ids.len()
+        ));
+        return inspect_node(root, first, ui);
+    }
+    ui.weak(format!("Editing {} nodes", ids.len()));
+
+    let values = node_field_values(root, first);
+    let others = rest
+        .iter()
+        .map(|&id| node_field_values(root, id))
+        .collect::<Vec<_>>();
+    let mixed = values
+        .iter()
+        .filter(|field| others.iter().any(|values| !values.contains(field)))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    with_inspection(ui.ctx(), |inspection| inspection.mixed = mixed);
+    inspect_node(root, first, ui);
+    with_inspection(ui.ctx(), |inspection| inspection.mixed.clear());
+
+    for (path, value) in node_field_values(root, first) {
+        if values.contains(&(path.clone(), value.clone())) {
+            continue;
+        }
+        for &id in rest {
+            tree::find_node(root, ROOT_ID, id, &mut |node| {
+                inspectable::set_field_value(node, &path, value.clone());
+            });
+        }
+    }
+}
+
+fn node_field_values(root: &mut dyn TreeNode, id: u64) -> Vec<(Vec<String>, inspectable::Value)> {
+    let mut values = vec![];
+    tree::find_node(root, ROOT_ID, id, &mut |node| {
+        values = inspectable::field_values(node)
+    });
+    values
+}
+
+/// Returns whether the field being shown by [`inspect_nodes`] has different values in the nodes
+/// shown.
+pub(crate) fn is_mixed(ctx: &egui::Context) -> bool {
+    with_inspection(ctx, |inspection| {
+        inspection.mixed.contains(&inspection.field_path)
+    })
+}
+
+/// Returns the ID of the node being shown by [`inspect_node`] and the path to the field being
+/// shown from it, if any.
+pub(crate) fn current_field(ctx: &egui::Context) -> Option<(u64, Vec<String>)> {
+    with_inspection(ctx, |inspection| {
+        Some((inspection.node?, inspection.field_path.clone()))
+    })
+}
+
+/// Calls `f` while the field with the name given is being shown, with whether it belongs to a node
+/// shown by [`inspect_node`].
+pub(crate) fn with_field<R>(ctx: egui::Context, name: &str, f: impl FnOnce(bool) -> R) -> R {
+    let in_node = with_inspection(&ctx, |inspection| {
+        let in_node = inspection.node.is_some();
+        if in_node {
+            inspection.field_path.push(name.to_owned());
+        }
+        in_node
+    });
+    let result = f(in_node);
+    if in_node {
+        with_inspection(&ctx, |inspection| inspection.field_path.pop());
+    }
+    result
+}
@@ -0,0 +1,323 @@
+//! Configurable layout of the editor panels.
+//!
+//! Each [`Panel`] can be docked to any side of the viewport, resized, collapsed, hidden or popped
+//! out as a floating window, either from its header or from the "View" menu shown by
+//! [`Layout::show`]. The viewport takes whatever space is left.
+
+/// The width of side panels and the height of top and bottom panels while collapsed.
+const COLLAPSED_SIZE: f32 = 24.;
+
+/// A panel of the editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum Panel {
+    /// The inspector of the node selected.
+    Inspector,
+    /// The node tree.
+    Tree,
+    /// Frame times and information about the viewport.
+    Performance,
+    /// Plots of the fields watched.
+    Watch,
+    /// The log records of the application.
+    Console,
+    /// The developer console, where commands are typed.
+    Commands,
+    /// The files assets were loaded from, which are reloaded when they change.
+    Assets,
+    /// Controls to record the input events received and replay them.
+    Input,
+    /// The categories of shapes drawn with [`debug_draw`](crate::debug_draw), which can be hidden.
+    DebugDraw,
+    /// The settings of the grid, rulers and snapping of the viewport.
+    Viewport,
+}
+
+impl Panel {
+    /// All panels, in the order they are listed in the "View" menu.
+    pub const ALL: [Panel; 10] = [
+        Panel::Inspector,
+        Panel::Tree,
+        Panel::Performance,
+        Panel::Watch,
+        Panel::Console,
+        Panel::Commands,
+        Panel::Assets,
+        Panel::Input,
+        Panel::DebugDraw,
+        Panel::Viewport,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Panel::Inspector => "Inspector",
+            Panel::Tree => "Tree",
+            Panel::Performance => "Performance",
+            Panel::Watch => "Watch",
+            Panel::Console => "Console",
+            Panel::Commands => "Commands",
+            Panel::Assets => "Assets",
+            Panel::Input => "Input",
+            Panel::DebugDraw => "Debug draw",
+            Panel::Viewport => "Viewport",
+        }
+    }
+
+    /// Whether the panel shows its contents in its own scroll area, instead of being wrapped in
+    /// one.
+    fn scrolls(self) -> bool {
+        matches!(self, Panel::Console | Panel::Commands)
+    }
+}
+
+/// Where a panel is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dock {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// In its own window, over the rest of the editor.
+    Floating,
+}
+
+impl Dock {
+    pub const ALL: [Dock; 5] = [
+        Dock::Left,
+        Dock::Right,
+        Dock::Top,
+        Dock::Bottom,
+        Dock::Floating,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Dock::Left => "Left",
+            Dock::Right => "Right",
+            Dock::Top => "Top",
+            Dock::Bottom => "Bottom",
+            Dock::Floating => "Floating",
+        }
+    }
+}
+
+/// The placement of a panel in the layout.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub struct PanelLayout {
+    pub panel: Panel,
+    pub dock: Dock,
+    pub visible: bool,
+    /// Whether only the header of the panel is shown. Floating panels are collapsed through their
+    /// window instead.
+    pub collapsed: bool,
+}
+
+/// The placement of every panel of the editor.
+///
+/// Docked panels are laid out in order, the first one being the outermost on its side.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "persistence",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Layout {
+    pub panels: Vec<PanelLayout>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        let panel = |panel, dock, visible| PanelLayout {
+            panel,
+            dock,
+            visible,
+            collapsed: false,
+        };
+        Self {
+            panels: vec![
+                panel(Panel::Inspector, Dock::Right, true),
+                panel(Panel::Tree, Dock::Bottom, true),
+                panel(Panel::Performance, Dock::Floating, false),
+                panel(Panel::Watch, Dock::Bottom, false),
+                panel(Panel::Console, Dock::Bottom, false),
+                panel(Panel::Commands, Dock::Bottom, false),
+                panel(Panel::Assets, Dock::Floating, false),
+                panel(Panel::Input, Dock::Floating, false),
+                panel(Panel::DebugDraw, Dock::Floating, false),
+                panel(Panel::Viewport, Dock::Floating, false),
+            ],
+        }
+    }
+}
+
+impl Layout {
+    /// Returns the placement of the panel given, adding it to the layout if it is missing (e.g.
+    /// because the layout was stored by an older version).
+    pub fn panel_mut(&mut self, panel: Panel) -> &mut PanelLayout {
+        let idx = match self.panels.iter().position(|layout| layout.panel == panel) {
+            Some(idx) => idx,
+            None => {
+                let default = Layout::default()
+                    .panels
+                    .into_iter()
+                    .find(|layout| layout.panel == panel)
+                    .expect("all panels are in the default layout");
+                self.panels.push(default);
+                self.panels.len() - 1
+            }
+        };
+        &mut self.panels[idx]
+    }
+
+    /// Returns whether the contents of the panel given are currently shown.
+    pub fn is_shown(&self, panel: Panel) -> bool {
+        self.panels
+            .iter()
+            .any(|layout| layout.panel == panel && layout.visible && !layout.collapsed)
+    }
+
+    /// Shows the menu bar and every visible panel, calling `contents` to fill each of the ones
+    /// that are not collapsed.
+    ///
+    /// The viewport should be shown afterwards with an [`egui::CentralPanel`], which will take
+    /// the space left.
+    pub fn show(&mut self, ctx: &egui::Context, mut contents: impl FnMut(Panel, &mut egui::Ui)) {
+        for panel in Panel::ALL {
+            self.panel_mut(panel);
+        }
+
+        egui::TopBottomPanel::top("guiedit menu").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| ui.menu_button("View", |ui| self.menu_ui(ui)));
+        });
+
+        for layout in &mut self.panels {
+            if layout.visible {
+                show_panel(layout, ctx, &mut contents);
+            }
+        }
+    }
+
+    /// Shows a toggle for the visibility of each panel, and a button to restore the default
+    /// layout.
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) {
+        for panel in Panel::ALL {
+            ui.checkbox(&mut self.panel_mut(panel).visible, panel.title());
+        }
+        ui.separator();
+        if ui.button("Reset layout").clicked() {
+            *self = Layout::default();
+            ui.close_menu();
+        }
+    }
+}
+
+fn show_panel(
+    layout: &mut PanelLayout,
+    ctx: &egui::Context,
+    contents: &mut impl FnMut(Panel, &mut egui::Ui),
+) {
+    let id = egui::Id::new(("guiedit panel", layout.panel));
+    let (dock, collapsed) = (layout.dock, layout.collapsed);
+    let add_contents = |ui: &mut egui::Ui| {
+        if collapsed && matches!(dock, Dock::Left | Dock::Right) {
+            // There is no room for the whole header
+            if ui.button("☰").on_hover_text(layout.panel.title()).clicked() {
+                layout.collapsed = false;
+            }
+            return;
+        }
+
+        header_ui(layout, ui);
+        if !collapsed {
+            ui.separator();
+            contents_ui(layout.panel, id, ui, contents);
+        }
+    };
+
+    match dock {
+        Dock::Left | Dock::Right => {
+            let side = match dock {
+                Dock::Left => egui::panel::Side::Left,
+                _ => egui::panel::Side::Right,
+            };
+            let mut panel = egui::SidePanel::new(side, id).resizable(!collapsed);
+            if collapsed {
+                panel = panel.width_range(COLLAPSED_SIZE..=COLLAPSED_SIZE);
+            }
+            panel.show(ctx, add_contents);
+        }
+        Dock::Top | Dock::Bottom => {
+            let side = match dock {
+                Dock::Top => egui::panel::TopBottomSide::Top,
+                _ => egui::panel::TopBottomSide::Bottom,
+            };
+            let mut panel = egui::TopBottomPanel::new(side, id).resizable(!collapsed);
+            if collapsed {
+                panel = panel.height_range(COLLAPSED_SIZE..=COLLAPSED_SIZE);
+            }
+            panel.show(ctx, add_contents);
+        }
+        Dock::Floating => {
+            let mut open = true;
+            egui::Window::new(layout.panel.title())
+                .id(id)
+                .open(&mut open)
+                .resizable(true)
+                .default_size([300., 300.])
+                .show(ctx, |ui| {
+                    dock_menu_ui(layout, ui);
+                    ui.separator();
+                    contents_ui(layout.panel, id, ui, contents);
+                });
+            layout.visible = open;
+        }
+    }
+}
+
+fn contents_ui(
+    panel: Panel,
+    id: egui::Id,
+    ui: &mut egui::Ui,
+    contents: &mut impl FnMut(Panel, &mut egui::Ui),
+) {
+    if panel.scrolls() {
+        contents(panel, ui);
+    } else {
+        egui::ScrollArea::vertical()
+            .id_source(id)
+            .show(ui, |ui| contents(panel, ui));
+    }
+}
+
+/// Shows the title of a docked panel along with buttons to collapse, move and hide it.
+fn header_ui(layout: &mut PanelLayout, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        let icon = if layout.collapsed { "⏵" } else { "⏷" };
+        if ui.small_button(icon).clicked() {
+            layout.collapsed = !layout.collapsed;
+        }
+        ui.strong(layout.panel.title());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("×").on_hover_text("Hide").clicked() {
+                layout.visible = false;
+            }
+            dock_menu_ui(layout, ui);
+        });
+    });
+}
+
+fn dock_menu_ui(layout: &mut PanelLayout, ui: &mut egui::Ui) {
+    ui.menu_button("Dock", |ui| {
+        for dock in Dock::ALL {
+            if ui
+                .radio_value(&mut layout.dock, dock, dock.name())
+                .clicked()
+            {
+                layout.collapsed = false;
+                ui.close_menu();
+            }
+        }
+    });
+}
@@ -1,5 +1,32 @@
+#[cfg(feature = "asset-reload")]
+pub mod assets;
+#[cfg(feature = "capture")]
+pub mod capture;
+pub mod color;
+pub mod commands;
+pub mod console;
+pub mod debug_draw;
+pub mod ecs;
+pub mod grid;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
+pub mod image_view;
 pub mod inspectable;
+pub mod inspector;
+pub mod layout;
+pub mod math;
+#[cfg(feature = "persistence")]
+pub mod persistence;
+pub mod pins;
+pub mod registry;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod replay;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod tree;
+pub mod watch;
+pub mod waveform;
 
 // Backends
 #[cfg(feature = "sfml")]
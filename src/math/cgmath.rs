@@ -0,0 +1,66 @@
+use cgmath::{
+    BaseFloat, Deg, Matrix2, Matrix3, Matrix4, Point1, Point2, Point3, Quaternion, Rad, Vector1,
+    Vector2, Vector3, Vector4,
+};
+use egui::emath::Numeric;
+
+use super::{matrix_ui, quaternion_ui, transform_ui, vector_ui};
+use crate::inspectable::Inspectable;
+
+macro_rules! impl_vectors {
+    ($($vector:ident: $len:literal),*) => {$(
+        impl<S: Numeric> Inspectable for $vector<S> {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                vector_ui(ui, AsMut::<[S; $len]>::as_mut(self));
+            }
+        }
+    )*};
+}
+
+impl_vectors!(
+    Vector1: 1, Vector2: 2, Vector3: 3, Vector4: 4, Point1: 1, Point2: 2, Point3: 3
+);
+
+impl<S: BaseFloat + Numeric> Inspectable for Quaternion<S> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        // Stored as `[x, y, z, s]`
+        quaternion_ui(ui, AsMut::<[S; 4]>::as_mut(self));
+    }
+}
+
+impl<S: Numeric> Inspectable for Matrix2<S> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        matrix_ui(ui, "guiedit matrix", AsMut::<[S; 4]>::as_mut(self), 2);
+    }
+}
+
+impl<S: Numeric> Inspectable for Matrix3<S> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        matrix_ui(ui, "guiedit matrix", AsMut::<[S; 9]>::as_mut(self), 3);
+    }
+}
+
+impl<S: Numeric> Inspectable for Matrix4<S> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        transform_ui(ui, "guiedit transform", AsMut::<[S; 16]>::as_mut(self));
+    }
+}
+
+impl<S: Numeric> Inspectable for Deg<S> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::DragValue::new(&mut self.0).suffix("°"));
+    }
+}
+
+impl<S: Numeric> Inspectable for Rad<S> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        // Radians are shown in degrees, which are easier to reason about
+        let mut degrees = self.0.to_f64().to_degrees();
+        if ui
+            .add(egui::DragValue::new(&mut degrees).suffix("°"))
+            .changed()
+        {
+            self.0 = S::from_f64(degrees.to_radians());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use glam::{
+    DMat2, DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat,
+    UVec2, UVec3, UVec4, Vec2, Vec3, Vec3A, Vec4,
+};
+
+use super::{matrix_ui, quaternion_ui, transform_ui, vector_ui};
+use crate::inspectable::Inspectable;
+
+macro_rules! impl_vectors {
+    ($($vector:ty),*) => {$(
+        impl Inspectable for $vector {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                vector_ui(ui, self.as_mut());
+            }
+        }
+    )*};
+}
+
+impl_vectors!(
+    Vec2, Vec3, Vec3A, Vec4, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, UVec2, UVec3, UVec4
+);
+
+macro_rules! impl_quaternions {
+    ($($quaternion:ident),*) => {$(
+        impl Inspectable for $quaternion {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                let mut xyzw = self.to_array();
+                if quaternion_ui(ui, &mut xyzw) {
+                    *self = $quaternion::from_array(xyzw);
+                }
+            }
+        }
+    )*};
+}
+
+impl_quaternions!(Quat, DQuat);
+
+macro_rules! impl_matrices {
+    ($($matrix:ty: $rows:literal),*) => {$(
+        impl Inspectable for $matrix {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                matrix_ui(ui, "guiedit matrix", self.as_mut(), $rows);
+            }
+        }
+    )*};
+}
+
+impl_matrices!(Mat2: 2, Mat3: 3, DMat2: 2, DMat3: 3);
+
+macro_rules! impl_transforms {
+    ($($matrix:ty),*) => {$(
+        impl Inspectable for $matrix {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                transform_ui(ui, "guiedit transform", self.as_mut());
+            }
+        }
+    )*};
+}
+
+impl_transforms!(Mat4, DMat4);
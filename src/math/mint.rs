@@ -0,0 +1,82 @@
+use egui::emath::Numeric;
+use mint::{
+    ColumnMatrix2, ColumnMatrix2x3, ColumnMatrix2x4, ColumnMatrix3, ColumnMatrix3x2,
+    ColumnMatrix3x4, ColumnMatrix4, ColumnMatrix4x2, ColumnMatrix4x3, Point2, Point3, Quaternion,
+    RowMatrix2, RowMatrix2x3, RowMatrix2x4, RowMatrix3, RowMatrix3x2, RowMatrix3x4, RowMatrix4,
+    RowMatrix4x2, RowMatrix4x3, Vector2, Vector3, Vector4,
+};
+
+use super::{matrix_ui, quaternion_ui, transform_ui, vector_ui};
+use crate::inspectable::Inspectable;
+
+macro_rules! impl_vectors {
+    ($($vector:ident: $len:literal),*) => {$(
+        impl<T: Numeric> Inspectable for $vector<T> {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                vector_ui(ui, AsMut::<[T; $len]>::as_mut(self));
+            }
+        }
+    )*};
+}
+
+impl_vectors!(Vector2: 2, Vector3: 3, Vector4: 4, Point2: 2, Point3: 3);
+
+impl<T: Numeric> Inspectable for Quaternion<T> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        // Stored as `[x, y, z, s]`
+        quaternion_ui(ui, self.as_mut());
+    }
+}
+
+// Column matrices are stored in column-major order, just like the helpers expect them
+macro_rules! impl_column_matrices {
+    ($($matrix:ident: $rows:literal x $columns:literal),*) => {$(
+        impl<T: Numeric> Inspectable for $matrix<T> {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                matrix_ui(ui, "guiedit matrix", AsMut::<[T; $rows * $columns]>::as_mut(self), $rows);
+            }
+        }
+    )*};
+}
+
+impl_column_matrices!(
+    ColumnMatrix2: 2 x 2,
+    ColumnMatrix2x3: 2 x 3,
+    ColumnMatrix2x4: 2 x 4,
+    ColumnMatrix3x2: 3 x 2,
+    ColumnMatrix3: 3 x 3,
+    ColumnMatrix3x4: 3 x 4,
+    ColumnMatrix4x2: 4 x 2,
+    ColumnMatrix4x3: 4 x 3
+);
+
+impl<T: Numeric> Inspectable for ColumnMatrix4<T> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        transform_ui(ui, "guiedit transform", AsMut::<[T; 16]>::as_mut(self));
+    }
+}
+
+// Row matrices are transposed into their column counterparts to be shown
+macro_rules! impl_row_matrices {
+    ($($matrix:ident: $column_matrix:ident),*) => {$(
+        impl<T: Numeric> Inspectable for $matrix<T> {
+            fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+                let mut transposed = $column_matrix::from(*self);
+                transposed.inspect_ui(ui);
+                *self = transposed.into();
+            }
+        }
+    )*};
+}
+
+impl_row_matrices!(
+    RowMatrix2: ColumnMatrix2,
+    RowMatrix2x3: ColumnMatrix2x3,
+    RowMatrix2x4: ColumnMatrix2x4,
+    RowMatrix3x2: ColumnMatrix3x2,
+    RowMatrix3: ColumnMatrix3,
+    RowMatrix3x4: ColumnMatrix3x4,
+    RowMatrix4x2: ColumnMatrix4x2,
+    RowMatrix4x3: ColumnMatrix4x3,
+    RowMatrix4: ColumnMatrix4
+);
@@ -0,0 +1,320 @@
+//! Layout helpers for inspecting vectors, quaternions and matrices, along with feature-gated
+//! [`Inspectable`] implementations for the types of common math crates:
+//!
+//! | Feature    | Types                                                           |
+//! |------------|-----------------------------------------------------------------|
+//! | `glam`     | Vectors, `Quat`/`DQuat` and `Mat2`/`Mat3`/`Mat4` (+ `f64`)       |
+//! | `nalgebra` | Statically-sized matrices and vectors, `Point` and `UnitQuaternion` |
+//! | `mint`     | Vectors, points, `Quaternion` and row/column matrices           |
+//! | `cgmath`   | Vectors, points, `Quaternion`, `Matrix2/3/4`, `Deg` and `Rad`   |
+//!
+//! Quaternions are edited as euler angles in degrees, and 4x4 matrices are additionally shown
+//! decomposed into translation, rotation and scale as long as they are affine.
+//!
+//! [`Inspectable`]: crate::inspectable::Inspectable
+
+use egui::emath::Numeric;
+
+#[cfg(feature = "cgmath")]
+mod cgmath;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "mint")]
+mod mint;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+
+const AXES: [&str; 4] = ["x", "y", "z", "w"];
+
+/// Shows a drag value for each of the components of a vector, prefixed by their axis.
+///
+/// Returns whether any of the components was changed.
+pub fn vector_ui<'a, T: Numeric>(
+    ui: &mut egui::Ui,
+    components: impl IntoIterator<Item = &'a mut T>,
+) -> bool {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            components
+                .into_iter()
+                .enumerate()
+                .fold(false, |changed, (idx, component)| {
+                    let mut drag = egui::DragValue::new(component);
+                    if let Some(axis) = AXES.get(idx) {
+                        drag = drag.prefix(format!("{}: ", axis));
+                    }
+                    ui.add(drag).changed() || changed
+                })
+        })
+        .inner
+    })
+    .inner
+}
+
+/// Shows a quaternion, given as `[x, y, z, w]`, as editable euler angles in degrees.
+///
+/// See [`quat_to_euler`] for the convention used. Returns whether the quaternion was changed.
+pub fn quaternion_ui<T: Numeric>(ui: &mut egui::Ui, xyzw: &mut [T; 4]) -> bool {
+    let quat = xyzw.map(T::to_f64);
+
+    ui.group(|ui| {
+        // Converting back and forth between euler angles and quaternions is not stable (e.g. on
+        // gimbal lock), so keep the angles shown last around while the quaternion stays the same
+        let id = ui.id().with("euler");
+        let cached = ui.data().get_temp::<([f64; 4], [f64; 3])>(id);
+        let mut euler = match cached {
+            Some((cached_quat, euler)) if cached_quat == quat => euler,
+            _ => quat_to_euler(quat),
+        }
+        .map(f64::to_degrees);
+
+        let changed = ui
+            .horizontal(|ui| {
+                euler
+                    .iter_mut()
+                    .zip(AXES)
+                    .fold(false, |changed, (angle, axis)| {
+                        let drag = egui::DragValue::new(angle)
+                            .prefix(format!("{}: ", axis))
+                            .suffix("°");
+                        ui.add(drag).changed() || changed
+                    })
+            })
+            .inner;
+
+        let euler = euler.map(f64::to_radians);
+        let quat = if changed {
+            let quat = euler_to_quat(euler);
+            *xyzw = quat.map(T::from_f64);
+            quat
+        } else {
+            quat
+        };
+        ui.data().insert_temp(id, (quat, euler));
+
+        changed
+    })
+    .inner
+}
+
+/// Shows a matrix, given as its elements in column-major order, as a grid of drag values.
+///
+/// `id_source` must be unique among the matrices shown in the same `Ui`. Returns whether any of
+/// the elements was changed.
+pub fn matrix_ui<T: Numeric>(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    elements: &mut [T],
+    rows: usize,
+) -> bool {
+    assert!(
+        elements.len().checked_rem(rows) == Some(0),
+        "a matrix with {} rows cannot have {} elements",
+        rows,
+        elements.len()
+    );
+    let columns = elements.len() / rows;
+
+    ui.group(|ui| {
+        egui::Grid::new(id_source)
+            .show(ui, |ui| {
+                let mut changed = false;
+                for row in 0..rows {
+                    for column in 0..columns {
+                        changed |= ui
+                            .add(egui::DragValue::new(&mut elements[column * rows + row]))
+                            .changed();
+                    }
+                    ui.end_row();
+                }
+                changed
+            })
+            .inner
+    })
+    .inner
+}
+
+/// Shows a 4x4 transformation matrix, given in column-major order, as a grid of drag values.
+///
+/// If the matrix is affine, it is also shown decomposed into editable translation, rotation and
+/// scale. `id_source` must be unique among the transforms shown in the same `Ui`. Returns whether
+/// the matrix was changed.
+pub fn transform_ui<T: Numeric>(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    elements: &mut [T; 16],
+) -> bool {
+    let id = egui::Id::new(id_source);
+    ui.vertical(|ui| {
+        let mut changed = matrix_ui(ui, id.with("matrix"), elements, 4);
+
+        if let Some(mut trs) = Trs::from_matrix(elements.map(T::to_f64)) {
+            ui.collapsing("Decomposition", |ui| {
+                let trs_changed = egui::Grid::new(id.with("trs"))
+                    .show(ui, |ui| {
+                        ui.label("Translation");
+                        let mut changed = vector_ui(ui, &mut trs.translation);
+                        ui.end_row();
+                        ui.label("Rotation");
+                        changed |= quaternion_ui(ui, &mut trs.rotation);
+                        ui.end_row();
+                        ui.label("Scale");
+                        changed |= vector_ui(ui, &mut trs.scale);
+                        ui.end_row();
+                        changed
+                    })
+                    .inner;
+
+                if trs_changed {
+                    *elements = trs.to_matrix().map(T::from_f64);
+                    changed = true;
+                }
+            });
+        }
+
+        changed
+    })
+    .inner
+}
+
+/// An affine transform decomposed into translation, rotation and scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trs {
+    pub translation: [f64; 3],
+    /// The rotation as an unit quaternion, given as `[x, y, z, w]`.
+    pub rotation: [f64; 4],
+    pub scale: [f64; 3],
+}
+
+impl Trs {
+    /// Decomposes a 4x4 matrix, given in column-major order.
+    ///
+    /// Returns `None` if the matrix is not affine or if any of its axes has a scale of zero.
+    pub fn from_matrix(m: [f64; 16]) -> Option<Self> {
+        if m[3] != 0. || m[7] != 0. || m[11] != 0. || m[15] != 1. {
+            return None;
+        }
+
+        let mut axes = [[m[0], m[1], m[2]], [m[4], m[5], m[6]], [m[8], m[9], m[10]]];
+        let mut scale = axes.map(|axis| axis.iter().map(|x| x * x).sum::<f64>().sqrt());
+        if scale.iter().any(|&scale| scale <= f64::EPSILON) {
+            return None;
+        }
+        // Mirroring transforms are represented as a negative scale on the X axis
+        let [x, y, z] = axes;
+        let determinant = x[0] * (y[1] * z[2] - z[1] * y[2]) - y[0] * (x[1] * z[2] - z[1] * x[2])
+            + z[0] * (x[1] * y[2] - y[1] * x[2]);
+        if determinant < 0. {
+            scale[0] = -scale[0];
+        }
+        for (axis, scale) in axes.iter_mut().zip(scale) {
+            *axis = axis.map(|x| x / scale);
+        }
+
+        Some(Self {
+            translation: [m[12], m[13], m[14]],
+            rotation: rotation_matrix_to_quat(axes),
+            scale,
+        })
+    }
+
+    /// Composes the transform back into a 4x4 matrix, in column-major order.
+    pub fn to_matrix(&self) -> [f64; 16] {
+        let [x, y, z, w] = self.rotation;
+        let axes = [
+            [
+                1. - 2. * (y * y + z * z),
+                2. * (x * y + z * w),
+                2. * (x * z - y * w),
+            ],
+            [
+                2. * (x * y - z * w),
+                1. - 2. * (x * x + z * z),
+                2. * (y * z + x * w),
+            ],
+            [
+                2. * (x * z + y * w),
+                2. * (y * z - x * w),
+                1. - 2. * (x * x + y * y),
+            ],
+        ];
+        let [sx, sy, sz] = self.scale;
+        let [tx, ty, tz] = self.translation;
+        #[rustfmt::skip]
+        let matrix = [
+            axes[0][0] * sx, axes[0][1] * sx, axes[0][2] * sx, 0.,
+            axes[1][0] * sy, axes[1][1] * sy, axes[1][2] * sy, 0.,
+            axes[2][0] * sz, axes[2][1] * sz, axes[2][2] * sz, 0.,
+            tx, ty, tz, 1.,
+        ];
+        matrix
+    }
+}
+
+/// Converts an unit quaternion, given as `[x, y, z, w]`, into euler angles in radians.
+///
+/// The angles are returned as `[x, y, z]`, and are applied in that order: First around the X axis,
+/// then around the Y axis and finally around the Z axis.
+pub fn quat_to_euler([x, y, z, w]: [f64; 4]) -> [f64; 3] {
+    let roll = f64::atan2(2. * (w * x + y * z), 1. - 2. * (x * x + y * y));
+    let pitch = (2. * (w * y - z * x)).clamp(-1., 1.).asin();
+    let yaw = f64::atan2(2. * (w * z + x * y), 1. - 2. * (y * y + z * z));
+    [roll, pitch, yaw]
+}
+
+/// Converts euler angles in radians, given as `[x, y, z]`, into an unit quaternion given as
+/// `[x, y, z, w]`.
+///
+/// This is the inverse of [`quat_to_euler`].
+pub fn euler_to_quat([roll, pitch, yaw]: [f64; 3]) -> [f64; 4] {
+    let (sr, cr) = (roll / 2.).sin_cos();
+    let (sp, cp) = (pitch / 2.).sin_cos();
+    let (sy, cy) = (yaw / 2.).sin_cos();
+    [
+        sr * cp * cy - cr * sp * sy,
+        cr * sp * cy + sr * cp * sy,
+        cr * cp * sy - sr * sp * cy,
+        cr * cp * cy + sr * sp * sy,
+    ]
+}
+
+/// Converts a rotation matrix, given as its columns, into an unit quaternion given as
+/// `[x, y, z, w]`.
+fn rotation_matrix_to_quat([x, y, z]: [[f64; 3]; 3]) -> [f64; 4] {
+    // Element at row `r` and column `c` is `m(r, c)`
+    let m = |r: usize, c: usize| [x, y, z][c][r];
+    let trace = m(0, 0) + m(1, 1) + m(2, 2);
+    if trace > 0. {
+        let s = (trace + 1.).sqrt() * 2.;
+        [
+            (m(2, 1) - m(1, 2)) / s,
+            (m(0, 2) - m(2, 0)) / s,
+            (m(1, 0) - m(0, 1)) / s,
+            s / 4.,
+        ]
+    } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+        let s = (1. + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * 2.;
+        [
+            s / 4.,
+            (m(0, 1) + m(1, 0)) / s,
+            (m(0, 2) + m(2, 0)) / s,
+            (m(2, 1) - m(1, 2)) / s,
+        ]
+    } else if m(1, 1) > m(2, 2) {
+        let s = (1. + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * 2.;
+        [
+            (m(0, 1) + m(1, 0)) / s,
+            s / 4.,
+            (m(1, 2) + m(2, 1)) / s,
+            (m(0, 2) - m(2, 0)) / s,
+        ]
+    } else {
+        let s = (1. + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * 2.;
+        [
+            (m(0, 2) + m(2, 0)) / s,
+            (m(1, 2) + m(2, 1)) / s,
+            s / 4.,
+            (m(1, 0) - m(0, 1)) / s,
+        ]
+    }
+}
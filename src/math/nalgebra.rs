@@ -0,0 +1,37 @@
+use egui::emath::Numeric;
+use nalgebra::{Point, Quaternion, RealField, SMatrix, Scalar, UnitQuaternion};
+
+use super::{matrix_ui, quaternion_ui, transform_ui, vector_ui};
+use crate::inspectable::Inspectable;
+
+/// Covers vectors as well, since they are just matrices with one column.
+impl<T: Scalar + Numeric, const R: usize, const C: usize> Inspectable for SMatrix<T, R, C> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        // Elements are stored in column-major order
+        let elements = self.as_mut_slice();
+        if C == 1 {
+            vector_ui(ui, elements);
+        } else if R == 4 && C == 4 {
+            transform_ui(ui, "guiedit transform", elements.try_into().unwrap());
+        } else {
+            matrix_ui(ui, "guiedit matrix", elements, R);
+        }
+    }
+}
+
+impl<T: Scalar + Numeric, const D: usize> Inspectable for Point<T, D> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        self.coords.inspect_ui(ui);
+    }
+}
+
+impl<T: RealField + Numeric> Inspectable for UnitQuaternion<T> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        let coords = &self.quaternion().coords;
+        let mut xyzw = [coords.x, coords.y, coords.z, coords.w];
+        if quaternion_ui(ui, &mut xyzw) {
+            let [x, y, z, w] = xyzw;
+            *self = UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Editor state kept across runs of an application.
+//!
+//! The state is stored as RON in the file given by [`EditorState::default_path`] unless
+//! configured otherwise, and includes the egui memory of the editor (which tree nodes are
+//! expanded, panel sizes, window positions...) along with the layout of the panels, the nodes
+//! pinned, the fields watched and the node selected.
+//!
+//! Storing the state is opt-in through the `persistence` feature, since it writes a file on
+//! every run of the application.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::tree::{self, TreeNode};
+
+/// The name of the file the state is stored in, if no other path is configured.
+pub const FILE_NAME: &str = "guiedit.ron";
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct EditorState {
+    /// The egui memory of the editor, including expanded tree nodes and panel sizes.
+    pub memory: egui::Memory,
+    /// The placement of the editor panels.
+    pub layout: crate::layout::Layout,
+    /// The nodes pinned into their own inspector windows.
+    pub pins: crate::pins::Pins,
+    /// The fields watched, without the values recorded.
+    pub watches: crate::watch::Watches,
+    /// The names of the nodes leading from the root to the node selected, as returned by
+    /// [`tree::path_to`].
+    pub selected_path: Option<Vec<String>>,
+}
+
+impl EditorState {
+    /// Returns the path the state of the application is stored in by default.
+    ///
+    /// This is `$XDG_CONFIG_HOME/guiedit/<executable name>.ron` if `XDG_CONFIG_HOME` is set, and
+    /// [`FILE_NAME`] in the working directory otherwise.
+    pub fn default_path() -> PathBuf {
+        let executable = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.file_stem().map(|stem| stem.to_owned()));
+        match (std::env::var_os("XDG_CONFIG_HOME"), executable) {
+            (Some(config), Some(executable)) => {
+                let mut path = PathBuf::from(config).join("guiedit").join(executable);
+                path.set_extension("ron");
+                path
+            }
+            _ => PathBuf::from(FILE_NAME),
+        }
+    }
+
+    /// Loads the state stored in the path given.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        ron::from_str(&contents).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Stores the state in the path given, creating its parent directories if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        std::fs::write(path, contents)
+    }
+
+    /// Stores the node with the ID given as the one selected, given the root of the tree.
+    pub fn set_selected(&mut self, root: &mut dyn TreeNode, selected: Option<u64>) {
+        self.selected_path =
+            selected.and_then(|selected| tree::path_to(root, tree::ROOT_ID, selected));
+    }
+
+    /// Returns the ID of the node selected, given the root of the tree, or `None` if there is no
+    /// selection or the node selected does not exist anymore.
+    pub fn selected(&self, root: &mut dyn TreeNode) -> Option<u64> {
+        let path = self.selected_path.as_ref()?;
+        tree::id_at_path(root, tree::ROOT_ID, path)
+    }
+}
@@ -0,0 +1,89 @@
+//! Nodes pinned into their own inspector windows, so that several of them can be watched and
+//! edited at once.
+//!
+//! Pinned nodes are tracked through [`NodeRef`]s, so that they keep pointing to the same node
+//! across runs.
+
+use crate::tree::{NodeRef, TreeNode};
+
+/// The nodes pinned into their own inspector windows.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "persistence",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Pins {
+    pins: Vec<NodeRef>,
+}
+
+impl Pins {
+    /// Pins the node with the ID given, given the root of the tree. Does nothing if it is already
+    /// pinned.
+    pub fn pin(&mut self, root: &mut dyn TreeNode, id: u64) {
+        if !self.is_pinned(root, id) {
+            self.pins.push(NodeRef::new(root, id));
+        }
+    }
+
+    /// Unpins the node with the ID given, given the root of the tree.
+    pub fn unpin(&mut self, root: &mut dyn TreeNode, id: u64) {
+        self.pins.retain(|pin| pin.resolve(root) != Some(id));
+    }
+
+    pub fn is_pinned(&self, root: &mut dyn TreeNode, id: u64) -> bool {
+        self.pins.iter().any(|pin| pin.resolve(root) == Some(id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &NodeRef> {
+        self.pins.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    /// Shows a button that pins the node given, or unpins it if it is already pinned.
+    pub fn pin_button(&mut self, root: &mut dyn TreeNode, id: u64, ui: &mut egui::Ui) {
+        if self.is_pinned(root, id) {
+            if ui
+                .small_button("📌 Unpin")
+                .on_hover_text("Close the window of this node")
+                .clicked()
+            {
+                self.unpin(root, id);
+            }
+        } else if ui
+            .small_button("📌 Pin")
+            .on_hover_text("Inspect this node in its own window")
+            .clicked()
+        {
+            self.pin(root, id);
+        }
+    }
+
+    /// Shows a window with the inspector of each of the pinned nodes, unpinning the ones whose
+    /// window is closed.
+    pub fn show(&mut self, ctx: &egui::Context, root: &mut dyn TreeNode) {
+        self.pins.retain(|pin| {
+            let mut open = true;
+            egui::Window::new(pin.title())
+                .id(egui::Id::new(("guiedit pin", pin.title())))
+                .open(&mut open)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| match pin.resolve(root) {
+                        Some(id) => crate::inspector::inspect_node(root, id, ui),
+                        None => {
+                            ui.add_enabled_ui(false, |ui| ui.label("This node does not exist"));
+                        }
+                    })
+                });
+            open
+        });
+    }
+}
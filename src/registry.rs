@@ -0,0 +1,82 @@
+//! Runtime registry of inspectors for types that do not implement [`Inspectable`].
+//!
+//! Because of the orphan rule, it is not possible to implement [`Inspectable`] for types defined
+//! in other crates. Instead, applications can register an inspector function for them here. Fields
+//! of these types will then be inspected with it by `#[derive(Inspectable)]`, as long as their type
+//! is `'static`.
+//!
+//! ```
+//! # struct Vec3 { x: f32, y: f32, z: f32 }
+//! guiedit::registry::register::<Vec3>(|vec, ui| {
+//!     ui.add(guiedit::egui::DragValue::new(&mut vec.x));
+//!     ui.add(guiedit::egui::DragValue::new(&mut vec.y));
+//!     ui.add(guiedit::egui::DragValue::new(&mut vec.z));
+//! });
+//! ```
+//!
+//! [`Inspectable`]: crate::inspectable::Inspectable
+
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// An inspector for a type-erased value, as stored in the registry.
+pub type InspectorFn = fn(&mut dyn Any, &mut egui::Ui);
+
+type Inspector = Arc<dyn Fn(&mut dyn Any, &mut egui::Ui) + Send + Sync>;
+
+static INSPECTORS: RwLock<BTreeMap<TypeId, Inspector>> = RwLock::new(BTreeMap::new());
+
+/// Registers the inspector to use for values of type `T`, replacing any previous one.
+pub fn register<T: 'static>(inspector: fn(&mut T, &mut egui::Ui)) {
+    insert(
+        TypeId::of::<T>(),
+        Arc::new(move |value, ui| {
+            if let Some(value) = value.downcast_mut::<T>() {
+                inspector(value, ui)
+            }
+        }),
+    );
+}
+
+/// Registers the inspector to use for values with the type ID given, replacing any previous one.
+///
+/// The inspector will only ever be called with values of that type.
+pub fn register_any(type_id: TypeId, inspector: InspectorFn) {
+    insert(type_id, Arc::new(inspector));
+}
+
+/// Removes the inspector registered for values of type `T`, returning whether there was one.
+pub fn unregister<T: 'static>() -> bool {
+    INSPECTORS
+        .write()
+        .unwrap()
+        .remove(&TypeId::of::<T>())
+        .is_some()
+}
+
+/// Returns whether there is an inspector registered for the type ID given.
+pub fn is_registered(type_id: TypeId) -> bool {
+    INSPECTORS.read().unwrap().contains_key(&type_id)
+}
+
+/// Inspects the value given with the inspector registered for its type.
+///
+/// Returns `false` if there was no inspector registered, in which case nothing is shown.
+pub fn inspect(value: &mut dyn Any, ui: &mut egui::Ui) -> bool {
+    // Clone the inspector out of the lock so that it can register inspectors itself
+    let inspector = INSPECTORS.read().unwrap().get(&(*value).type_id()).cloned();
+    match inspector {
+        Some(inspector) => {
+            inspector(value, ui);
+            true
+        }
+        None => false,
+    }
+}
+
+fn insert(type_id: TypeId, inspector: Inspector) {
+    INSPECTORS.write().unwrap().insert(type_id, inspector);
+}
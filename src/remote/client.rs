@@ -0,0 +1,125 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use super::protocol::{Field, Node, Request, Response};
+use crate::inspectable::Value;
+
+/// How long requests wait for the server by default, see [`Client::set_timeout`].
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A client for inspecting the node tree of a remote [`Server`].
+///
+/// Requests block until the server answers them, which happens the next time it is polled, or
+/// until they time out. Once a request fails, the connection is no longer usable, since a late
+/// response would be taken as the answer to the next request, and every request fails.
+///
+/// [`Server`]: super::Server
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    failed: bool,
+}
+
+impl Client {
+    /// Connects to the server at the address given, with requests timing out after
+    /// [`DEFAULT_TIMEOUT`].
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let writer = TcpStream::connect(address)?;
+        writer.set_nodelay(true)?;
+        let client = Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+            failed: false,
+        };
+        client.set_timeout(Some(DEFAULT_TIMEOUT))?;
+        Ok(client)
+    }
+
+    /// Sets how long requests wait for the server to receive them and to answer them, or removes
+    /// the limit if `None`.
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.writer.set_read_timeout(timeout)?;
+        self.writer.set_write_timeout(timeout)
+    }
+
+    /// Returns whether requests can still be sent, i.e. whether no request failed with an I/O
+    /// error so far.
+    pub fn is_connected(&self) -> bool {
+        !self.failed
+    }
+
+    /// Sends a request and waits for its response.
+    ///
+    /// Error responses are returned as they are; the rest of functions convert them into errors.
+    /// Requests that are not answered in time fail with [`io::ErrorKind::TimedOut`].
+    pub fn request(&mut self, request: &Request) -> io::Result<Response> {
+        if self.failed {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "the connection was dropped after a failed request",
+            ));
+        }
+        let response = self.exchange(request);
+        self.failed = response.is_err();
+        response
+    }
+
+    fn exchange(&mut self, request: &Request) -> io::Result<Response> {
+        let mut message = serde_json::to_vec(request)?;
+        message.push(b'\n');
+        self.writer.write_all(&message).map_err(timed_out)?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).map_err(timed_out)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    /// Returns the whole node tree.
+    pub fn tree(&mut self) -> io::Result<Node> {
+        match self.request(&Request::Tree)? {
+            Response::Tree { root } => Ok(root),
+            response => Err(unexpected(response)),
+        }
+    }
+
+    /// Returns the fields of the node with the ID given.
+    pub fn fields(&mut self, node: u64) -> io::Result<Vec<Field>> {
+        match self.request(&Request::Fields { node })? {
+            Response::Fields { fields, .. } => Ok(fields),
+            response => Err(unexpected(response)),
+        }
+    }
+
+    /// Sets the value of the field at the path given in the node with the ID given.
+    pub fn set(&mut self, node: u64, path: Vec<String>, value: Value) -> io::Result<()> {
+        match self.request(&Request::Set { node, path, value })? {
+            Response::Set => Ok(()),
+            response => Err(unexpected(response)),
+        }
+    }
+}
+
+/// Reports timeouts, which some platforms report as [`io::ErrorKind::WouldBlock`], as such.
+fn timed_out(error: io::Error) -> io::Error {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            io::Error::new(io::ErrorKind::TimedOut, "the server did not answer in time")
+        }
+        _ => error,
+    }
+}
+
+fn unexpected(response: Response) -> io::Error {
+    match response {
+        Response::Error { message } => io::Error::other(message),
+        response => io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected response: {:?}", response),
+        ),
+    }
+}
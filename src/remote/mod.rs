@@ -0,0 +1,68 @@
+//! Remote inspection over TCP, for applications without a window (e.g. dedicated servers or test
+//! harnesses).
+//!
+//! The application hosts a [`Server`] and calls [`Server::poll`] with its root node once per
+//! frame. Clients, like [`Client`] or the `guiedit-viewer` binary, can then browse the node tree
+//! and edit values through reflection (see [`Inspectable::value`] and
+//! [`Inspectable::visit_fields`]).
+//!
+//! # Protocol
+//! Messages are JSON objects, one per line, tagged by their `type`. Clients send requests, and the
+//! server answers each of them with exactly one response, in order:
+//!
+//! | Request                                                  | Response                                     |
+//! |----------------------------------------------------------|----------------------------------------------|
+//! | `{"type":"tree"}`                                        | `{"type":"tree","root":<node>}`              |
+//! | `{"type":"fields","node":<id>}`                          | `{"type":"fields","node":<id>,"fields":[<field>...]}` |
+//! | `{"type":"set","node":<id>,"path":[<name>...],"value":<value>}` | `{"type":"set"}`                      |
+//!
+//! Any request can also be answered with `{"type":"error","message":"..."}`.
+//!
+//! - `<node>` is `{"id":<id>,"name":"...","children":[<node>...]}`. IDs are unsigned 64-bit
+//!   integers, and the root node always has the ID [`ROOT_ID`].
+//! - `<field>` is `{"name":"...","value":<value>|null,"fields":[<field>...]}`.
+//! - `<value>` is one of `{"bool":true}`, `{"int":-1}`, `{"uint":1}`, `{"float":1.5}` or
+//!   `{"string":"..."}`.
+//! - `path` is the list of field names leading to the value to set, starting from the node. An
+//!   empty path sets the value of the node itself.
+//!
+//! Requests may be at most 1 MiB long. The server does not wait for clients to read their
+//! responses, and drops clients that let more than 16 MiB of them pile up. [`Client`] gives up on
+//! requests that are not answered within [`DEFAULT_TIMEOUT`], e.g. while the application is paused
+//! in a debugger.
+//!
+//! ```
+//! # use guiedit::{inspectable::Value, remote::{Client, Server}, tree::ROOT_ID};
+//! #[derive(guiedit::Inspectable, guiedit::TreeNode)]
+//! struct Level {
+//!     enemies: u32,
+//! }
+//!
+//! let mut level = Level { enemies: 3 };
+//! let mut server = Server::bind("127.0.0.1:0").unwrap();
+//! let address = server.local_addr().unwrap();
+//!
+//! let client = std::thread::spawn(move || {
+//!     let mut client = Client::connect(address).unwrap();
+//!     client.set(ROOT_ID, vec!["enemies".to_owned()], Value::UInt(5)).unwrap();
+//! });
+//! while !client.is_finished() {
+//!     server.poll(&mut level).unwrap();
+//! }
+//! assert_eq!(level.enemies, 5);
+//! ```
+//!
+//! [`Inspectable::value`]: crate::inspectable::Inspectable::value
+//! [`Inspectable::visit_fields`]: crate::inspectable::Inspectable::visit_fields
+//! [`ROOT_ID`]: crate::tree::ROOT_ID
+
+mod client;
+mod protocol;
+mod server;
+
+pub use client::{Client, DEFAULT_TIMEOUT};
+pub use protocol::{Field, Node, Request, Response};
+pub use server::{respond, Server};
+
+/// The address the `guiedit-viewer` binary connects to by default.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7417";
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::inspectable::Value;
+
+/// A message sent from a client to the server.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Requests the whole node tree.
+    Tree,
+    /// Requests the fields of a node.
+    Fields { node: u64 },
+    /// Sets the value of a field of a node.
+    Set {
+        node: u64,
+        path: Vec<String>,
+        value: Value,
+    },
+}
+
+/// A message sent from the server to a client, in answer to a [`Request`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Tree { root: Node },
+    Fields { node: u64, fields: Vec<Field> },
+    Set,
+    Error { message: String },
+}
+
+/// A node of the tree, along with all of its children.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: u64,
+    pub name: String,
+    pub children: Vec<Node>,
+}
+
+/// A field of a node, along with all of its own fields.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    /// The value of the field, if it is a primitive.
+    pub value: Option<Value>,
+    pub fields: Vec<Field>,
+}
@@ -0,0 +1,230 @@
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use super::protocol::{Field, Node, Request, Response};
+use crate::{
+    inspectable::{Inspectable, Value},
+    tree::{self, TreeNode, ROOT_ID},
+};
+
+/// The maximum length of a request, in bytes. Clients sending longer lines are dropped.
+const MAX_REQUEST_LENGTH: usize = 1 << 20;
+/// The maximum amount of responses waiting to be sent to a client, in bytes. Clients that stop
+/// reading their responses are dropped once this is exceeded.
+const MAX_PENDING_OUTPUT: usize = 16 << 20;
+
+/// A server answering the requests of remote clients about a node tree.
+pub struct Server {
+    listener: TcpListener,
+    connections: Vec<Connection>,
+}
+
+struct Connection {
+    stream: TcpStream,
+    /// Received data that does not form a whole line yet.
+    buffer: Vec<u8>,
+    /// Responses that could not be sent yet without blocking.
+    output: Vec<u8>,
+}
+
+impl Server {
+    /// Creates a server listening on the address given, e.g. [`DEFAULT_ADDRESS`].
+    ///
+    /// [`DEFAULT_ADDRESS`]: super::DEFAULT_ADDRESS
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            connections: vec![],
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts new clients and answers all of their pending requests, using the node given as the
+    /// root of the tree.
+    ///
+    /// This never waits for requests to arrive nor for responses to be sent, so it is meant to be
+    /// called once per frame. Clients that disconnect or misbehave (e.g. by not reading their
+    /// responses) are dropped.
+    pub fn poll(&mut self, root: &mut impl TreeNode) -> io::Result<()> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(true)?;
+                    self.connections.push(Connection {
+                        stream,
+                        buffer: vec![],
+                        output: vec![],
+                    });
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                Err(error) => return Err(error),
+            }
+        }
+
+        self.connections
+            .retain_mut(|connection| connection.poll(root).is_ok());
+        Ok(())
+    }
+}
+
+impl Connection {
+    /// Answers all pending requests. Returns an error if the connection should be dropped.
+    fn poll(&mut self, root: &mut impl TreeNode) -> io::Result<()> {
+        self.flush()?;
+        let mut chunk = [0; 4096];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(read) => {
+                    self.buffer.extend_from_slice(&chunk[..read]);
+                    self.answer_requests(root)?;
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Answers the requests received in whole, queueing their responses.
+    fn answer_requests(&mut self, root: &mut impl TreeNode) -> io::Result<()> {
+        while let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+            let line = self.buffer.drain(..=end).collect::<Vec<_>>();
+            let response = match serde_json::from_slice::<Request>(&line) {
+                Ok(request) => respond(root, request),
+                Err(error) => Response::Error {
+                    message: format!("invalid request: {}", error),
+                },
+            };
+            serde_json::to_writer(&mut self.output, &response)?;
+            self.output.push(b'\n');
+
+            self.flush()?;
+            if self.output.len() > MAX_PENDING_OUTPUT {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "the client is not reading its responses",
+                ));
+            }
+        }
+
+        if self.buffer.len() > MAX_REQUEST_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the request is too long",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sends as much of the queued responses as possible without blocking.
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.output.is_empty() {
+            match self.stream.write(&self.output) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(written) => {
+                    self.output.drain(..written);
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Answers a request about the tree with the root given.
+///
+/// Useful for serving requests through transports other than [`Server`].
+pub fn respond(root: &mut impl TreeNode, request: Request) -> Response {
+    match request {
+        Request::Tree => Response::Tree {
+            root: node("root", ROOT_ID, root),
+        },
+        Request::Fields { node } => {
+            let mut result = None;
+            tree::find_node(root, ROOT_ID, node, &mut |node| result = Some(fields(node)));
+            match result {
+                Some(fields) => Response::Fields { node, fields },
+                None => no_such_node(node),
+            }
+        }
+        Request::Set { node, path, value } => {
+            let mut result = None;
+            let mut value = Some(value);
+            tree::find_node(root, ROOT_ID, node, &mut |node| {
+                if let Some(value) = value.take() {
+                    result = Some(set_field(node, &path, value));
+                }
+            });
+            match result {
+                Some(Ok(())) => Response::Set,
+                Some(Err(message)) => Response::Error { message },
+                None => no_such_node(node),
+            }
+        }
+    }
+}
+
+fn no_such_node(id: u64) -> Response {
+    Response::Error {
+        message: format!("there is no node with ID {}", id),
+    }
+}
+
+fn node<T: TreeNode + ?Sized>(name: &str, id: u64, node: &mut T) -> Node {
+    let mut children = vec![];
+    node.visit_children(id, &mut |name, id, child| {
+        children.push(self::node(name, id, child))
+    });
+    Node {
+        id,
+        name: name.to_owned(),
+        children,
+    }
+}
+
+fn fields<T: Inspectable + ?Sized>(value: &mut T) -> Vec<Field> {
+    let mut fields = vec![];
+    value.visit_fields(&mut |name, field| {
+        fields.push(Field {
+            name: name.to_owned(),
+            value: field.value(),
+            fields: self::fields(field),
+        })
+    });
+    fields
+}
+
+fn set_field<T: Inspectable + ?Sized>(
+    value: &mut T,
+    path: &[String],
+    new_value: Value,
+) -> Result<(), String> {
+    let Some((name, path)) = path.split_first() else {
+        return if value.set_value(new_value.clone()) {
+            Ok(())
+        } else {
+            Err(format!("the value cannot be set to {:?}", new_value))
+        };
+    };
+
+    let mut result = Err(format!("there is no field named `{}`", name));
+    let mut new_value = Some(new_value);
+    value.visit_fields(&mut |field_name, field| {
+        if field_name == name {
+            if let Some(new_value) = new_value.take() {
+                result = set_field(field, path, new_value);
+            }
+        }
+    });
+    result
+}
@@ -0,0 +1,409 @@
+//! Recording of the input events received by the application, and replay of them.
+//!
+//! Events are recorded along with the number of the frame they were received in, and replayed in
+//! the same frames instead of the events of the OS. A replay is only deterministic if the
+//! application is, e.g. if it uses a fixed time step and seeds its random number generators.
+//!
+//! Recordings are stored as text, with a line per event, through [`RecordedEvent`].
+
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+/// The first line of every recording file.
+const HEADER: &str = "guiedit input recording 1";
+
+/// An event that can be stored in a recording file.
+pub trait RecordedEvent: Clone + Sized {
+    /// Returns the event as a line of text, without any newlines, or `None` if it is not recorded.
+    fn to_line(&self) -> Option<String>;
+    /// Parses an event from a line returned by [`to_line`](Self::to_line).
+    fn from_line(line: &str) -> Option<Self>;
+}
+
+/// The events received by an application over a number of frames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputRecording<E> {
+    /// The events received, in order, along with the number of the frame they were received in.
+    pub events: Vec<(u64, E)>,
+    /// The number of frames recorded.
+    pub frames: u64,
+}
+
+impl<E> Default for InputRecording<E> {
+    fn default() -> Self {
+        Self {
+            events: vec![],
+            frames: 0,
+        }
+    }
+}
+
+impl<E: RecordedEvent> InputRecording<E> {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(io::BufReader::new(fs::File::open(path)?))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(fs::File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Reads a recording, as written by [`write`](Self::write).
+    pub fn read(reader: impl BufRead) -> io::Result<Self> {
+        let invalid = |line: usize, message: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", line + 1, message),
+            )
+        };
+
+        let mut lines = reader.lines().enumerate();
+        match lines.next() {
+            Some((_, Ok(header))) if header == HEADER => {}
+            Some((_, Err(error))) => return Err(error),
+            _ => return Err(invalid(0, "not an input recording")),
+        }
+        let frames = match lines.next() {
+            Some((idx, line)) => line?
+                .strip_prefix("frames ")
+                .and_then(|frames| frames.parse().ok())
+                .ok_or_else(|| invalid(idx, "expected the number of frames"))?,
+            None => return Err(invalid(1, "expected the number of frames")),
+        };
+
+        let mut events = vec![];
+        for (idx, line) in lines {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let (frame, event) = line
+                .split_once(' ')
+                .ok_or_else(|| invalid(idx, "expected a frame and an event"))?;
+            let frame = frame
+                .parse()
+                .map_err(|_| invalid(idx, "invalid frame number"))?;
+            if events.last().is_some_and(|&(last, _)| frame < last) || frame >= frames {
+                return Err(invalid(idx, "frame out of order"));
+            }
+            let event = E::from_line(event).ok_or_else(|| invalid(idx, "invalid event"))?;
+            events.push((frame, event));
+        }
+        Ok(Self { events, frames })
+    }
+
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "frames {}", self.frames)?;
+        for (frame, event) in &self.events {
+            if let Some(line) = event.to_line() {
+                writeln!(writer, "{} {}", frame, line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+enum Mode<E> {
+    Live,
+    Recording {
+        path: PathBuf,
+        recording: InputRecording<E>,
+    },
+    Replaying {
+        recording: InputRecording<E>,
+        /// The index of the next event to replay.
+        next: usize,
+        paused: bool,
+        /// The frame the replay is paused at once reached, if seeking.
+        seek: Option<u64>,
+    },
+}
+
+/// Records the events received by an application, or replays a recording instead of them.
+///
+/// Backends pass every event they return to the application through [`record`](Self::record),
+/// return the events of [`next_replayed`](Self::next_replayed) instead of the events of the OS
+/// while [`is_replaying`](Self::is_replaying), and call [`end_frame`](Self::end_frame) once a
+/// frame is displayed.
+pub struct InputReplay<E> {
+    mode: Mode<E>,
+    /// The number of frames since recording or replaying started.
+    frame: u64,
+    /// Whether the replay went back to its start since the last call to
+    /// [`take_restart`](Self::take_restart).
+    restarted: bool,
+    /// The path typed in the editor to record to and replay from.
+    path: String,
+    /// The result of the last recording saved or loaded from the editor.
+    status: Option<Result<String, String>>,
+}
+
+impl<E> Default for InputReplay<E> {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Live,
+            frame: 0,
+            restarted: false,
+            path: "input.txt".to_owned(),
+            status: None,
+        }
+    }
+}
+
+impl<E: RecordedEvent> InputReplay<E> {
+    /// Starts recording the events received to the file given, which is written once the
+    /// recording stops. Stops the recording or replay in progress, if any.
+    pub fn start_recording(&mut self, path: impl Into<PathBuf>) {
+        self.stop();
+        self.mode = Mode::Recording {
+            path: path.into(),
+            recording: InputRecording::default(),
+        };
+        self.frame = 0;
+    }
+
+    /// Starts replaying the recording stored in the file given. Stops the recording or replay in
+    /// progress, if any.
+    pub fn start_replay(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let recording = InputRecording::load(path)?;
+        self.replay(recording);
+        Ok(())
+    }
+
+    /// Starts replaying the recording given. Stops the recording or replay in progress, if any.
+    pub fn replay(&mut self, recording: InputRecording<E>) {
+        self.stop();
+        self.mode = Mode::Replaying {
+            recording,
+            next: 0,
+            paused: false,
+            seek: None,
+        };
+        self.frame = 0;
+    }
+
+    /// Stops the recording or replay in progress, if any, going back to the events of the OS.
+    ///
+    /// Returns the path a recording was saved to, or the error it could not be saved with. The
+    /// current frame is part of the recording if events were recorded in it.
+    pub fn stop(&mut self) -> Option<io::Result<PathBuf>> {
+        match std::mem::replace(&mut self.mode, Mode::Live) {
+            Mode::Recording {
+                path,
+                mut recording,
+            } => {
+                if recording
+                    .events
+                    .last()
+                    .is_some_and(|&(frame, _)| frame == self.frame)
+                {
+                    recording.frames = self.frame + 1;
+                }
+                Some(recording.save(&path).map(|()| path))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        matches!(self.mode, Mode::Recording { .. })
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.mode, Mode::Replaying { .. })
+    }
+
+    /// The number of frames since recording or replaying started.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// The recording being made or replayed, if any.
+    pub fn recording(&self) -> Option<&InputRecording<E>> {
+        match &self.mode {
+            Mode::Live => None,
+            Mode::Recording { recording, .. } | Mode::Replaying { recording, .. } => {
+                Some(recording)
+            }
+        }
+    }
+
+    /// Records an event returned to the application, if recording and it can be stored.
+    pub fn record(&mut self, event: &E) {
+        if let Mode::Recording { recording, .. } = &mut self.mode {
+            if event.to_line().is_some() {
+                recording.events.push((self.frame, event.clone()));
+            }
+        }
+    }
+
+    /// Returns the next event replayed in the current frame, if any.
+    pub fn next_replayed(&mut self) -> Option<E> {
+        match &mut self.mode {
+            Mode::Replaying {
+                recording,
+                next,
+                paused: false,
+                ..
+            } => {
+                let (frame, event) = recording.events.get(*next)?;
+                if *frame > self.frame {
+                    return None;
+                }
+                *next += 1;
+                Some(event.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves on to the next frame.
+    ///
+    /// Replays are paused once their last frame or the frame sought is reached.
+    pub fn end_frame(&mut self) {
+        match &mut self.mode {
+            Mode::Live => {}
+            Mode::Recording { recording, .. } => {
+                self.frame += 1;
+                recording.frames = self.frame;
+            }
+            Mode::Replaying {
+                recording,
+                paused,
+                seek,
+                ..
+            } => {
+                if !*paused {
+                    self.frame += 1;
+                }
+                if *seek == Some(self.frame) || self.frame >= recording.frames {
+                    *paused = true;
+                    *seek = None;
+                }
+            }
+        }
+    }
+
+    /// Pauses or resumes the replay in progress, if any.
+    pub fn set_paused(&mut self, pause: bool) {
+        if let Mode::Replaying { paused, seek, .. } = &mut self.mode {
+            *paused = pause;
+            *seek = None;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self.mode, Mode::Replaying { paused: true, .. })
+    }
+
+    /// Replays the frames until the one given and pauses there.
+    ///
+    /// Frames before the current one are reached by replaying from the start, which the
+    /// application is told about through [`take_restart`](Self::take_restart).
+    pub fn seek(&mut self, frame: u64) {
+        if let Mode::Replaying {
+            recording,
+            next,
+            paused,
+            seek,
+        } = &mut self.mode
+        {
+            let frame = frame.min(recording.frames);
+            if frame < self.frame {
+                self.frame = 0;
+                *next = 0;
+                self.restarted = true;
+            }
+            *paused = frame == self.frame;
+            *seek = (frame != self.frame).then_some(frame);
+        }
+    }
+
+    /// Returns whether the replay went back to its start since the last call, in which case the
+    /// application should go back to the state the recording started from.
+    pub fn take_restart(&mut self) -> bool {
+        std::mem::take(&mut self.restarted)
+    }
+
+    /// Shows controls to record and replay the events received, along with a slider to seek
+    /// through the replay.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        match &self.mode {
+            Mode::Live => {
+                ui.horizontal(|ui| {
+                    ui.label("File");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("⏺ Record").clicked() {
+                        self.start_recording(self.path.clone());
+                        self.status = None;
+                    }
+                    if ui.button("▶ Replay").clicked() {
+                        let path = self.path.clone();
+                        self.status = match self.start_replay(&path) {
+                            Ok(()) => None,
+                            Err(error) => Some(Err(format!("Could not load {}: {}", path, error))),
+                        };
+                    }
+                });
+            }
+            Mode::Recording { recording, .. } => {
+                let (frames, events) = (recording.frames, recording.events.len());
+                ui.horizontal(|ui| {
+                    if ui.button("⏹ Stop recording").clicked() {
+                        self.status = self.stop().map(|result| match result {
+                            Ok(path) => Ok(format!("Saved {}", path.display())),
+                            Err(error) => Err(format!("Could not save the recording: {}", error)),
+                        });
+                    }
+                    ui.label(format!("{} frames, {} events", frames, events));
+                });
+            }
+            Mode::Replaying { recording, .. } => {
+                let frames = recording.frames;
+                ui.horizontal(|ui| {
+                    let paused = self.is_paused();
+                    if ui.button(if paused { "▶" } else { "⏸" }).clicked() {
+                        if paused && self.frame >= frames {
+                            self.seek(0);
+                            self.set_paused(false);
+                        } else {
+                            self.set_paused(!paused);
+                        }
+                    }
+                    let step = egui::Button::new("⏭").small();
+                    if ui
+                        .add_enabled(self.frame < frames, step)
+                        .on_hover_text("Next frame")
+                        .clicked()
+                    {
+                        self.seek(self.frame + 1);
+                    }
+                    if ui.button("⏹ Stop replay").clicked() {
+                        self.stop();
+                    }
+                });
+                let mut frame = self.frame;
+                let slider = egui::Slider::new(&mut frame, 0..=frames).text("Frame");
+                if ui.add(slider).changed() {
+                    self.seek(frame);
+                }
+            }
+        }
+        match &self.status {
+            Some(Ok(status)) => {
+                ui.weak(status);
+            }
+            Some(Err(error)) => {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! SFML resources that are reloaded when the file they were loaded from changes.
+
+use std::{
+    io,
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+use sfml::{
+    audio::SoundBuffer,
+    graphics::{Font, Texture},
+    SfBox, SfResource,
+};
+
+use crate::{
+    assets::{AssetHandle, AssetWatcher},
+    inspectable::Inspectable,
+};
+
+/// A resource that can be loaded from a file, and thus reloaded.
+pub trait LoadFromFile: SfResource + Sized {
+    /// The name of the kind of resource, shown in the assets panel.
+    const KIND: &'static str;
+
+    fn from_file(path: &str) -> Option<SfBox<Self>>;
+
+    /// Carries the settings of a previous version of the resource over to a new one after it is
+    /// reloaded.
+    fn keep_settings(&mut self, _previous: &Self) {}
+}
+
+impl LoadFromFile for Texture {
+    const KIND: &'static str = "Texture";
+
+    fn from_file(path: &str) -> Option<SfBox<Self>> {
+        Texture::from_file(path)
+    }
+
+    fn keep_settings(&mut self, previous: &Self) {
+        self.set_smooth(previous.is_smooth());
+        self.set_repeated(previous.is_repeated());
+    }
+}
+
+impl LoadFromFile for Font {
+    const KIND: &'static str = "Font";
+
+    fn from_file(path: &str) -> Option<SfBox<Self>> {
+        Font::from_file(path)
+    }
+}
+
+impl LoadFromFile for SoundBuffer {
+    const KIND: &'static str = "SoundBuffer";
+
+    fn from_file(path: &str) -> Option<SfBox<Self>> {
+        SoundBuffer::from_file(path)
+    }
+}
+
+/// A resource loaded from a file, which is recreated in place when the file changes.
+///
+/// Assets are reloaded by [`RenderWindow::display_and_inspect`] when they are part of the state
+/// tree inspected, or from their reload button in the inspector.
+///
+/// [`RenderWindow::display_and_inspect`]: crate::sfml::graphics::RenderWindow::display_and_inspect
+pub struct Asset<T: LoadFromFile> {
+    resource: SfBox<T>,
+    handle: AssetHandle,
+}
+
+impl<T: LoadFromFile> Asset<T> {
+    /// Loads the resource from the file at the path given, which is watched for changes by the
+    /// watcher given, e.g. `window.assets()`.
+    pub fn load(watcher: &AssetWatcher, path: impl AsRef<Path>) -> io::Result<Self> {
+        let resource = load(path.as_ref())?;
+        let handle = watcher.watch(path, T::KIND)?;
+        Ok(Self { resource, handle })
+    }
+
+    /// The absolute path of the file the resource was loaded from.
+    pub fn path(&self) -> &Path {
+        self.handle.path()
+    }
+
+    /// Reloads the resource from its file now. If this fails, the previous version is kept.
+    pub fn reload(&mut self) -> io::Result<()> {
+        match load::<T>(self.handle.path()) {
+            Ok(mut resource) => {
+                resource.keep_settings(&self.resource);
+                self.resource = resource;
+                self.handle.reloaded(Ok(()));
+                Ok(())
+            }
+            Err(error) => {
+                self.handle.reloaded(Err(error.to_string()));
+                Err(error)
+            }
+        }
+    }
+}
+
+fn load<T: LoadFromFile>(path: &Path) -> io::Result<SfBox<T>> {
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Could not load {} from {}", T::KIND, path.display()),
+        )
+    };
+    let path_str = path.to_str().ok_or_else(invalid)?;
+    T::from_file(path_str).ok_or_else(invalid)
+}
+
+impl<T: LoadFromFile> Deref for Asset<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.resource
+    }
+}
+
+impl<T: LoadFromFile> DerefMut for Asset<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.resource
+    }
+}
+
+impl<T: LoadFromFile + Inspectable> Inspectable for Asset<T> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                if ui.button("⟳ Reload").clicked() {
+                    // Errors are shown from the assets panel
+                    let _ = self.reload();
+                }
+                ui.weak(self.path().display().to_string());
+            });
+            self.resource.inspect_ui(ui);
+        });
+    }
+
+    fn reload_if_changed(&mut self) -> bool {
+        self.handle.is_changed() && self.reload().is_ok()
+    }
+}
@@ -1,5 +1,18 @@
+use std::{collections::VecDeque, time::Instant};
+
+use crate::color;
+use crate::commands::{Command, CommandArgs, CommandOutput, Commands};
+use crate::console::Console;
+use crate::debug_draw::{self, DebugDraw};
+use crate::grid::{Grid, ViewMapping};
+use crate::image_view;
 use crate::inspectable::Inspectable;
-use crate::tree::TreeNode;
+use crate::inspector;
+use crate::layout::{Layout, Panel};
+use crate::pins::Pins;
+use crate::replay::InputReplay;
+use crate::tree::{self, TreeNode, ROOT_ID};
+use crate::watch::{self, Watches};
 use ::sfml::{
     graphics::{
         CircleShape, Color, ConvexShape, CustomShape, Drawable, FloatRect, IntRect, PrimitiveType,
@@ -25,8 +38,45 @@ pub struct RenderWindow {
     is_editor_active: bool,
     active_node: Option<u64>,
     egui_ctx: SfEgui,
+    layout: Layout,
+    pins: Pins,
+    /// The duration of the last frames displayed, in seconds, for the performance panel.
+    frame_times: VecDeque<f32>,
+    last_display: Option<Instant>,
+    watches: Watches,
+    console: Console,
+    commands: Commands,
+    input_replay: InputReplay<Event>,
+    debug_draw: DebugDraw,
+    grid: Grid,
+    /// When the window was created, which watched values are timed from.
+    start: Instant,
+
+    #[cfg(feature = "hot-reload")]
+    hot_library: Option<crate::hot_reload::HotLibrary>,
+    #[cfg(feature = "asset-reload")]
+    assets: crate::assets::AssetWatcher,
+    #[cfg(feature = "capture")]
+    captures: crate::capture::Captures,
+    /// The key taking a screenshot when pressed, or starting and stopping a recording when pressed
+    /// along with Shift.
+    #[cfg(feature = "capture")]
+    capture_hotkey: Option<Key>,
+
+    /// The file the editor state is stored in, if any.
+    #[cfg(feature = "persistence")]
+    editor_state_path: Option<std::path::PathBuf>,
+    #[cfg(feature = "persistence")]
+    editor_state: crate::persistence::EditorState,
+    /// Whether the selection stored in `editor_state` is yet to be restored, which requires the
+    /// root node.
+    #[cfg(feature = "persistence")]
+    restore_selection: bool,
 }
 
+/// The number of frames shown in the performance panel.
+const FRAME_TIME_HISTORY: usize = 120;
+
 /// An event variant used for representing no event, yet that the event polling should continue.
 ///
 /// This is required because of how the editor event handling works currently. The editor polls
@@ -102,14 +152,270 @@ impl RenderWindow {
     }
 
     fn from_window_and_target(window: SfRenderWindow, target: RenderTexture) -> RenderWindow {
-        Self {
+        #[allow(unused_mut)]
+        let mut render_window = Self {
             egui_ctx: SfEgui::new(&window),
             target,
             is_editor_active: false,
             target_rect: FloatRect::new(0., 0., window.size().x as f32, window.size().y as f32),
             window,
             active_node: None,
+            layout: Default::default(),
+            pins: Default::default(),
+            frame_times: VecDeque::with_capacity(FRAME_TIME_HISTORY),
+            last_display: None,
+            watches: Default::default(),
+            console: Default::default(),
+            commands: Default::default(),
+            input_replay: Default::default(),
+            debug_draw: Default::default(),
+            grid: Default::default(),
+            start: Instant::now(),
+            #[cfg(feature = "hot-reload")]
+            hot_library: None,
+            #[cfg(feature = "asset-reload")]
+            assets: Default::default(),
+            #[cfg(feature = "capture")]
+            captures: Default::default(),
+            #[cfg(feature = "capture")]
+            capture_hotkey: None,
+            #[cfg(feature = "persistence")]
+            editor_state_path: None,
+            #[cfg(feature = "persistence")]
+            editor_state: Default::default(),
+            #[cfg(feature = "persistence")]
+            restore_selection: false,
+        };
+        #[cfg(feature = "persistence")]
+        render_window.set_editor_state_path(Some(crate::persistence::EditorState::default_path()));
+        render_window
+    }
+
+    /// Changes the file the editor state (layout, expanded nodes, selection...) is stored in, and
+    /// restores the state stored in it, if any. `None` disables storing the state.
+    ///
+    /// The state is stored when the window is dropped, and by default in
+    /// [`EditorState::default_path`](crate::persistence::EditorState::default_path).
+    #[cfg(feature = "persistence")]
+    pub fn set_editor_state_path(&mut self, path: Option<std::path::PathBuf>) {
+        // Files that do not exist or cannot be read just mean that there is no state to restore
+        if let Some(mut state) = path
+            .as_ref()
+            .and_then(|path| crate::persistence::EditorState::load(path).ok())
+        {
+            *self.egui_ctx.context().memory() = std::mem::take(&mut state.memory);
+            self.layout = std::mem::take(&mut state.layout);
+            self.pins = std::mem::take(&mut state.pins);
+            self.watches = std::mem::take(&mut state.watches);
+            self.editor_state = state;
+            self.restore_selection = true;
         }
+        self.editor_state_path = path;
+    }
+
+    /// Stores the editor state in the file configured with
+    /// [`set_editor_state_path`](Self::set_editor_state_path), if any.
+    #[cfg(feature = "persistence")]
+    pub fn save_editor_state(&mut self) -> std::io::Result<()> {
+        let Some(path) = &self.editor_state_path else {
+            return Ok(());
+        };
+        self.editor_state.memory = self.egui_ctx.context().memory().clone();
+        self.editor_state.layout = self.layout.clone();
+        self.editor_state.pins = self.pins.clone();
+        self.editor_state.watches = self.watches.clone();
+        self.editor_state.save(path)
+    }
+
+    /// The placement of the editor panels, which can also be changed from the editor itself.
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn layout_mut(&mut self) -> &mut Layout {
+        &mut self.layout
+    }
+
+    /// The nodes pinned into their own inspector windows, which can also be changed from the
+    /// inspector.
+    pub fn pins(&self) -> &Pins {
+        &self.pins
+    }
+
+    pub fn pins_mut(&mut self) -> &mut Pins {
+        &mut self.pins
+    }
+
+    /// The fields whose values are recorded and plotted in the watch panel, which can also be
+    /// added from the inspector.
+    pub fn watches(&self) -> &Watches {
+        &self.watches
+    }
+
+    pub fn watches_mut(&mut self) -> &mut Watches {
+        &mut self.watches
+    }
+
+    /// The console shown in the console panel. Records can be sent to it through its `log` logger
+    /// or its `tracing` layer, e.g. with `window.console().logger().init(LevelFilter::Debug)`.
+    pub fn console(&self) -> &Console {
+        &self.console
+    }
+
+    pub fn console_mut(&mut self) -> &mut Console {
+        &mut self.console
+    }
+
+    /// The commands that can be typed in the commands panel.
+    pub fn commands(&self) -> &Commands {
+        &self.commands
+    }
+
+    pub fn commands_mut(&mut self) -> &mut Commands {
+        &mut self.commands
+    }
+
+    /// Registers a command that can be typed in the commands panel. See [`Commands::register`].
+    ///
+    /// Commands registered for `()` are run by [`display`](Self::display) and
+    /// [`display_and_inspect`](Self::display_and_inspect), while any other ones must be run with
+    /// their state through [`run_commands`](Self::run_commands).
+    pub fn register_command<A, S, R>(
+        &mut self,
+        name: impl Into<String>,
+        f: impl FnMut(A, &mut S) -> R + 'static,
+    ) -> &mut Command
+    where
+        A: CommandArgs + 'static,
+        S: 'static,
+        R: CommandOutput + 'static,
+    {
+        self.commands.register(name, f)
+    }
+
+    /// Sets the library the systems of the game are loaded from, which is reloaded by
+    /// [`display_and_inspect`](Self::display_and_inspect) whenever it is rebuilt, and whose status
+    /// is shown in the editor.
+    #[cfg(feature = "hot-reload")]
+    pub fn set_hot_library(&mut self, library: Option<crate::hot_reload::HotLibrary>) {
+        self.hot_library = library;
+    }
+
+    #[cfg(feature = "hot-reload")]
+    pub fn hot_library(&self) -> Option<&crate::hot_reload::HotLibrary> {
+        self.hot_library.as_ref()
+    }
+
+    #[cfg(feature = "hot-reload")]
+    pub fn hot_library_mut(&mut self) -> Option<&mut crate::hot_reload::HotLibrary> {
+        self.hot_library.as_mut()
+    }
+
+    /// The watcher of the files assets are loaded from, listed in the assets panel. Assets loaded
+    /// with it, e.g. `Asset::<Texture>::load(window.assets(), "player.png")`, are reloaded by
+    /// [`display_and_inspect`](Self::display_and_inspect) when their file changes.
+    #[cfg(feature = "asset-reload")]
+    pub fn assets(&self) -> &crate::assets::AssetWatcher {
+        &self.assets
+    }
+
+    /// Reads back the last frame displayed, as rendered by the application, without the editor.
+    #[cfg(feature = "capture")]
+    pub fn capture_frame(&self) -> Option<crate::capture::Frame> {
+        frame_from_texture(self.target.texture())
+    }
+
+    /// Starts recording every frame displayed to the path given, stopping the recording in
+    /// progress if any. Paths with the `gif` extension are recorded to a GIF, and any other path
+    /// to a directory of PNG files.
+    #[cfg(feature = "capture")]
+    pub fn start_recording(&mut self, path: impl Into<std::path::PathBuf>) -> std::io::Result<()> {
+        self.captures.start_recording(path)
+    }
+
+    /// Stops the recording in progress, if any, and returns its path once all of its frames are
+    /// written.
+    #[cfg(feature = "capture")]
+    pub fn stop_recording(&mut self) -> Option<std::io::Result<std::path::PathBuf>> {
+        self.captures.stop_recording()
+    }
+
+    /// The captures of the viewport, which can also be taken from the performance panel.
+    #[cfg(feature = "capture")]
+    pub fn captures(&self) -> &crate::capture::Captures {
+        &self.captures
+    }
+
+    #[cfg(feature = "capture")]
+    pub fn captures_mut(&mut self) -> &mut crate::capture::Captures {
+        &mut self.captures
+    }
+
+    /// Changes the key taking a screenshot when pressed, or starting and stopping a recording
+    /// when pressed along with Shift. `None`, the default, disables it.
+    ///
+    /// The key works even when the editor is hidden, and is not passed on to the application.
+    #[cfg(feature = "capture")]
+    pub fn set_capture_hotkey(&mut self, key: Option<Key>) {
+        self.capture_hotkey = key;
+    }
+
+    /// Starts recording the events returned by [`poll_event`](Self::poll_event) and
+    /// [`wait_event`](Self::wait_event) to the file given, which is written once the recording
+    /// stops or the window is dropped.
+    pub fn start_input_recording(&mut self, path: impl Into<std::path::PathBuf>) {
+        self.input_replay.start_recording(path);
+    }
+
+    /// Replays the events recorded in the file given instead of the events of the OS, frame by
+    /// frame. Only events are replayed: state read directly from SFML, e.g. with
+    /// `Key::is_pressed`, is still the live one.
+    pub fn replay_input(&mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        self.input_replay.start_replay(path)
+    }
+
+    /// Stops the input recording or replay in progress, if any, and returns the path a recording
+    /// was saved to.
+    pub fn stop_input_replay(&mut self) -> Option<std::io::Result<std::path::PathBuf>> {
+        self.input_replay.stop()
+    }
+
+    /// The input recording or replay in progress, which can also be controlled from the input
+    /// panel.
+    ///
+    /// When seeking backwards, replays start over from their first frame: Applications should
+    /// check [`InputReplay::take_restart`] every frame and go back to their initial state when it
+    /// returns `true`.
+    pub fn input_replay(&self) -> &InputReplay<Event> {
+        &self.input_replay
+    }
+
+    pub fn input_replay_mut(&mut self) -> &mut InputReplay<Event> {
+        &mut self.input_replay
+    }
+
+    /// The grid, rulers and cursor position shown over the viewport, as set up in the viewport
+    /// panel.
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid {
+        &mut self.grid
+    }
+
+    /// Returns the closest point on the grid to the one given if snapping is enabled in the
+    /// viewport panel, or the point itself otherwise. Meant for dragging things in the viewport,
+    /// e.g. to the mouse position mapped by [`RenderTarget::map_pixel_to_coords`].
+    pub fn snap_to_grid(&self, point: Vector2f) -> Vector2f {
+        let snapped = self.grid.snap(egui::pos2(point.x, point.y));
+        Vector2f::new(snapped.x, snapped.y)
+    }
+
+    /// Runs the commands typed in the commands panel that were registered for the type of state
+    /// given.
+    pub fn run_commands<S: 'static>(&mut self, state: &mut S) {
+        self.commands.run(state);
     }
 
     /// Get the OS-specific handle of the window.
@@ -189,9 +495,10 @@ impl RenderWindow {
     /// }
     /// ```
     pub fn poll_event(&mut self) -> Option<Event> {
-        self.window
-            .poll_event()
-            .and_then(|ev| self.process_event(ev))
+        match self.window.poll_event() {
+            Some(ev) => self.process_event(ev).map(|ev| self.pass_event(ev)),
+            None => self.input_replay.next_replayed(),
+        }
     }
 
     /// Wait for an event and return it
@@ -225,9 +532,23 @@ impl RenderWindow {
     /// }
     /// ```
     pub fn wait_event(&mut self) -> Option<Event> {
+        if let Some(ev) = self.input_replay.next_replayed() {
+            return Some(ev);
+        }
         self.window
             .wait_event()
             .and_then(|ev| self.process_event(ev))
+            .map(|ev| self.pass_event(ev))
+    }
+
+    /// Records an event of the OS passed on to the application, or replaces it while replaying
+    /// recorded events, except for `Closed` so that the window can still be closed.
+    fn pass_event(&mut self, event: Event) -> Event {
+        if self.input_replay.is_replaying() && !matches!(event, Event::Closed) {
+            return NOOP_EVENT;
+        }
+        self.input_replay.record(&event);
+        event
     }
 
     fn process_event(&mut self, event: Event) -> Option<Event> {
@@ -253,7 +574,7 @@ impl RenderWindow {
             }
             Event::MouseButtonPressed { button, x, y } => {
                 let pos = Vector2f::new(x as f32, y as f32);
-                if self.target_rect.contains(pos) {
+                if self.target_rect.contains(pos) && !self.is_over_editor_window(pos) {
                     let vec = self.map_window_pos(pos).as_other();
                     Some(Event::MouseButtonPressed {
                         button,
@@ -266,7 +587,7 @@ impl RenderWindow {
             }
             Event::MouseButtonReleased { button, x, y } => {
                 let pos = Vector2f::new(x as f32, y as f32);
-                if self.target_rect.contains(pos) {
+                if self.target_rect.contains(pos) && !self.is_over_editor_window(pos) {
                     let vec = self.map_window_pos(pos).as_other();
                     Some(Event::MouseButtonReleased {
                         button,
@@ -279,7 +600,7 @@ impl RenderWindow {
             }
             Event::MouseMoved { x, y } => {
                 let pos = Vector2f::new(x as f32, y as f32);
-                if self.target_rect.contains(pos) {
+                if self.target_rect.contains(pos) && !self.is_over_editor_window(pos) {
                     let vec = self.map_window_pos(pos).as_other();
                     Some(Event::MouseMoved { x: vec.x, y: vec.y })
                 } else {
@@ -295,6 +616,15 @@ impl RenderWindow {
                 self.is_editor_active = !self.is_editor_active;
                 Some(NOOP_EVENT)
             }
+            #[cfg(feature = "capture")]
+            Event::KeyPressed { code, shift, .. } if Some(code) == self.capture_hotkey => {
+                if shift {
+                    self.captures.request_recording_toggle();
+                } else {
+                    self.captures.request_screenshot();
+                }
+                Some(NOOP_EVENT)
+            }
             other => Some(other),
         }
     }
@@ -408,54 +738,136 @@ impl RenderWindow {
     pub fn display_and_inspect(&mut self, node: &mut impl TreeNode) {
         self.window.clear(Color::BLACK); // HACK
         self.target.display();
+        #[cfg(feature = "capture")]
+        {
+            let target = &self.target;
+            self.captures
+                .frame_displayed(|| frame_from_texture(target.texture()));
+        }
+        #[cfg(feature = "persistence")]
+        if std::mem::take(&mut self.restore_selection) {
+            self.active_node = self.editor_state.selected(node);
+        }
+        #[cfg(feature = "persistence")]
+        let previous_active_node = self.active_node;
+        let now = Instant::now();
+        if let Some(last_display) = self.last_display.replace(now) {
+            if self.frame_times.len() == FRAME_TIME_HISTORY {
+                self.frame_times.pop_front();
+            }
+            self.frame_times
+                .push_back((now - last_display).as_secs_f32());
+        }
+        self.watches.record(node, (now - self.start).as_secs_f64());
+        // Shapes are only collected while the editor is shown, starting from the next frame
+        self.debug_draw.collect();
+        debug_draw::set_enabled(self.is_editor_active);
+        self.commands.run(&mut ());
+        #[cfg(feature = "hot-reload")]
+        if let Some(library) = &mut self.hot_library {
+            library.reload_if_changed();
+        }
+        #[cfg(feature = "asset-reload")]
+        if self.assets.take_changes() {
+            crate::assets::reload_changed(node);
+        }
         if self.is_editor_active {
             let target_rect = &mut self.target_rect;
+            let target_size = self.target.size();
+            let (layout, pins, watches, console, commands, active_node, frame_times) = (
+                &mut self.layout,
+                &mut self.pins,
+                &mut self.watches,
+                &mut self.console,
+                &mut self.commands,
+                &mut self.active_node,
+                &self.frame_times,
+            );
+            #[cfg(feature = "hot-reload")]
+            let hot_library = &self.hot_library;
+            #[cfg(feature = "asset-reload")]
+            let assets = &self.assets;
+            #[cfg(feature = "capture")]
+            let captures = &mut self.captures;
+            let input_replay = &mut self.input_replay;
+            let debug_draw = &mut self.debug_draw;
+            let grid = &mut self.grid;
+            let target = &self.target;
             self.egui_ctx
                 .do_frame(|ctx| {
-                    egui::SidePanel::right("inspector").show(ctx, |ui| {
-                        ui.vertical_centered(|ui| ui.heading("Target"));
-                        ui.horizontal(|ui| {
-                            let sfml::system::Vector2u {
-                                x: width,
-                                y: height,
-                            } = self.target.size();
-                            ui.label(format!("Resolution: {}x{}", width, height));
-                        });
-
-                        ui.vertical_centered(|ui| ui.heading("Inspector"));
-                        let Some(active_node) = self.active_node else {return};
-                        node.inspect_child(0, active_node, ui);
+                    layout.show(ctx, |panel, ui| match panel {
+                        Panel::Inspector => {
+                            if let Some(active_node) = *active_node {
+                                pins.pin_button(node, active_node, ui);
+                                let selected = tree::selected_nodes(ctx, Some(active_node));
+                                inspector::inspect_nodes(node, &selected, ui);
+                            }
+                        }
+                        Panel::Tree => node.node_ui("root", ROOT_ID, active_node, ui),
+                        Panel::Performance => {
+                            performance_ui(frame_times, target_size, ui);
+                            #[cfg(feature = "capture")]
+                            {
+                                ui.separator();
+                                captures.ui(ui);
+                            }
+                        }
+                        Panel::Watch => watches.ui(ui),
+                        Panel::Console => console.ui(ui),
+                        Panel::Commands => commands.ui(ui),
+                        Panel::Input => input_replay.ui(ui),
+                        Panel::DebugDraw => debug_draw.ui(ui),
+                        Panel::Viewport => grid.ui(ui),
+                        #[cfg(feature = "asset-reload")]
+                        Panel::Assets => assets.ui(ui),
+                        #[cfg(not(feature = "asset-reload"))]
+                        Panel::Assets => {
+                            ui.weak("Assets are only reloaded with the `asset-reload` feature");
+                        }
                     });
-                    let viewport_aspect_ratio =
-                        self.target.size().x as f32 / self.target.size().y as f32;
-                    let viewport_target_size = super::util::fit_aspect_ratio_in_size(
-                        viewport_aspect_ratio,
-                        ctx.available_rect().size(),
-                    );
-
-                    egui::TopBottomPanel::bottom("tree")
-                        .height_range(
-                            ctx.available_rect().height() - viewport_target_size.y
-                                ..=ctx.available_rect().height() - viewport_target_size.y,
-                        )
-                        .show(ctx, |ui| {
-                            ui.vertical_centered(|ui| ui.heading("Tree"));
-                            // TODO: Use constant instead of 0 for root node
-                            node.node_ui("root", 0, &mut self.active_node, ui);
-                            ui.add_space(ui.available_height());
-                        });
+                    pins.show(ctx, node);
+                    #[cfg(feature = "hot-reload")]
+                    if let Some(library) = hot_library {
+                        library.status_ui(ctx);
+                    }
+
+                    let requests = watch::take_plot_requests(ctx);
+                    if !requests.is_empty() {
+                        layout.panel_mut(Panel::Watch).visible = true;
+                    }
+                    for request in requests {
+                        watches.add(node, request.node, request.field_path);
+                    }
+
+                    // The viewport takes the space left by the panels, keeping the aspect ratio
+                    // of the target
                     let rect = egui::CentralPanel::default()
                         .frame(egui::Frame::none())
                         .show(ctx, |ui| {
-                            let target_size = self.target.size();
                             let aspect_ratio = target_size.x as f32 / target_size.y as f32;
-                            ui.image(
-                                egui::TextureId::User(1),
-                                super::util::fit_aspect_ratio_in_size(
-                                    aspect_ratio,
-                                    ui.available_size(),
-                                ),
-                            )
+                            let size = super::util::fit_aspect_ratio_in_size(
+                                aspect_ratio,
+                                ui.available_size(),
+                            );
+                            ui.centered_and_justified(|ui| {
+                                let image = ui.image(egui::TextureId::User(1), size);
+                                if color::is_picking(ctx) {
+                                    eyedropper_ui(ctx, &image, target.texture());
+                                }
+                                let scale = image.rect.size()
+                                    / Vec2::new(target_size.x as f32, target_size.y as f32);
+                                debug_draw.paint(&ui.painter_at(image.rect), |pos| {
+                                    let pixel = target.map_coords_to_pixel(
+                                        Vector2f::new(pos.x, pos.y),
+                                        target.view(),
+                                    );
+                                    image.rect.min
+                                        + Vec2::new(pixel.x as f32, pixel.y as f32) * scale
+                                });
+                                grid.paint(ui, &view_mapping(target.view(), image.rect));
+                                image
+                            })
+                            .inner
                         })
                         .inner
                         .rect;
@@ -477,6 +889,11 @@ impl RenderWindow {
             );
             self.window.draw(&sprite);
         }
+        #[cfg(feature = "persistence")]
+        if self.active_node != previous_active_node {
+            self.editor_state.set_selected(node, self.active_node);
+        }
+        self.input_replay.end_frame();
         self.window.display()
     }
 
@@ -663,6 +1080,19 @@ impl RenderWindow {
         self.map_window_pos(window_pos.as_other()).as_other()
     }
 
+    /// Returns whether the position given, in window coordinates, is over a floating editor
+    /// window, in which case mouse events should not reach the application. This is also the
+    /// case while a color is being picked from the viewport.
+    fn is_over_editor_window(&self, pos: Vector2f) -> bool {
+        self.is_editor_active
+            && self
+                .egui_ctx
+                .context()
+                .layer_id_at(egui::pos2(pos.x, pos.y))
+                .map_or(false, |layer| layer.order != egui::Order::Background)
+            || color::is_picking(self.egui_ctx.context())
+    }
+
     /// Maps a position from its real window position to its viewport position.
     fn map_window_pos(&self, pos: Vector2f) -> Vector2f {
         if !self.is_editor_active {
@@ -765,6 +1195,108 @@ impl RenderWindow {
     }
 }
 
+/// Reads the texture given back into a frame.
+#[cfg(feature = "capture")]
+fn frame_from_texture(texture: &Texture) -> Option<crate::capture::Frame> {
+    let image = texture.copy_to_image()?;
+    let size = image.size();
+    Some(crate::capture::Frame {
+        width: size.x,
+        height: size.y,
+        pixels: image.pixel_data().to_vec(),
+    })
+}
+
+/// Picks the color of the pixel of the viewport texture clicked, if any, for the color editor
+/// waiting for it.
+fn eyedropper_ui(ctx: &egui::Context, viewport: &egui::Response, texture: &Texture) {
+    let viewport = viewport
+        .interact(egui::Sense::click())
+        .on_hover_cursor(egui::CursorIcon::Crosshair)
+        .on_hover_text("Click to pick a color, Escape to cancel");
+    let pos = match viewport.interact_pointer_pos() {
+        Some(pos) if viewport.clicked() => pos,
+        _ => return,
+    };
+    let size = texture.size();
+    let uv = (pos - viewport.rect.min) / viewport.rect.size();
+    let pixel = [
+        (uv.x * size.x as f32).clamp(0., size.x as f32 - 1.) as u32,
+        (uv.y * size.y as f32).clamp(0., size.y as f32 - 1.) as u32,
+    ];
+    let image = texture.copy_to_image();
+    let srgba = image
+        .as_ref()
+        .and_then(|image| image_view::pixel_at(image.pixel_data(), size.x, pixel));
+    match srgba {
+        Some(srgba) => color::pick(ctx, srgba),
+        None => color::cancel_picking(ctx),
+    }
+}
+
+/// Returns the mapping of the world shown by a view to the area of the screen given, which the
+/// view is rendered to. The rotation of the view is not taken into account.
+fn view_mapping(view: &View, rect: egui::Rect) -> ViewMapping {
+    let (center, size, viewport) = (view.center(), view.size(), view.viewport());
+    ViewMapping {
+        screen: egui::Rect::from_min_size(
+            rect.min + Vec2::new(viewport.left, viewport.top) * rect.size(),
+            Vec2::new(viewport.width, viewport.height) * rect.size(),
+        ),
+        world: egui::Rect::from_center_size(
+            egui::pos2(center.x, center.y),
+            Vec2::new(size.x, size.y),
+        ),
+    }
+}
+
+impl debug_draw::Point for Vector2f {
+    fn xy(&self) -> [f32; 2] {
+        [self.x, self.y]
+    }
+}
+
+fn performance_ui(frame_times: &VecDeque<f32>, target_size: Vector2u, ui: &mut egui::Ui) {
+    ui.label(format!("Resolution: {}x{}", target_size.x, target_size.y));
+    if frame_times.is_empty() {
+        return;
+    }
+
+    let average = frame_times.iter().sum::<f32>() / frame_times.len() as f32;
+    let worst = frame_times.iter().copied().fold(0., f32::max);
+    ui.label(format!(
+        "Frame time: {:.2} ms average, {:.2} ms worst ({:.0} FPS)",
+        average * 1000.,
+        worst * 1000.,
+        1. / average
+    ));
+    let points: egui::plot::PlotPoints = frame_times
+        .iter()
+        .enumerate()
+        .map(|(idx, time)| [idx as f64, *time as f64 * 1000.])
+        .collect();
+    egui::plot::Plot::new("frame times")
+        .height(80.)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .include_y(0.)
+        .show_axes([false, true])
+        .show(ui, |plot| plot.line(egui::plot::Line::new(points)));
+}
+
+impl Drop for RenderWindow {
+    fn drop(&mut self) {
+        // There is nowhere to report errors to at this point, and failing to store the editor
+        // state or a recording should not stop the application from closing
+        #[cfg(feature = "persistence")]
+        let _ = self.save_editor_state();
+        let _ = self.input_replay.stop();
+        // Recordings are only complete once all of their frames are written
+        #[cfg(feature = "capture")]
+        self.captures.stop_recording();
+    }
+}
+
 impl RenderTarget for RenderWindow {
     fn push_gl_states(&mut self) {
         self.target.push_gl_states()
@@ -838,6 +1370,9 @@ impl RenderTarget for RenderWindow {
     }
 }
 
+/// Provides the viewport texture, drawn as `egui::TextureId::User(1)`, to egui. It is the only user
+/// texture; Other textures are shown through copies managed by egui, see
+/// [`image_view::image_copy`].
 struct SingleTextureProvider<'tex>(&'tex Texture);
 
 impl egui_sfml::UserTexSource for SingleTextureProvider<'_> {
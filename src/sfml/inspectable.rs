@@ -1,44 +1,48 @@
-use std::ops::DerefMut;
+use std::{cell::RefCell, ops::DerefMut};
 
 use sfml::{
-    audio::SoundBuffer,
-    graphics::{Color, Texture},
-    system::{Vector2, Vector3},
+    audio::{Music, Sound, SoundBuffer, SoundSource, SoundStatus},
+    graphics::{Color, Font, Texture},
+    system::{Time, Vector2, Vector3},
     SfBox,
 };
 
-use crate::inspectable::Inspectable;
+use crate::{
+    color::{self, EditColor},
+    image_view::{self, ImageView},
+    inspectable::Inspectable,
+    math::vector_ui,
+    waveform,
+};
 
-impl Inspectable for Color {
-    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
-        let mut color = egui::Color32::from_rgba_premultiplied(self.r, self.g, self.b, self.a);
+impl EditColor for Color {
+    const ALPHA: bool = true;
+    const HDR: bool = false;
 
-        ui.color_edit_button_srgba(&mut color);
+    fn to_rgba(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a].to_rgba()
+    }
 
-        [self.r, self.g, self.b, self.a] = color.to_array();
+    fn set_rgba(&mut self, rgba: [f32; 4]) {
+        [self.r, self.g, self.b, self.a] = color::srgba_from_rgba(rgba);
+    }
+}
+
+impl Inspectable for Color {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        color::color_edit_ui(ui, self);
     }
 }
 
 impl<T: egui::emath::Numeric> Inspectable for Vector2<T> {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
-        ui.group(|ui| {
-            ui.horizontal(|ui| {
-                ui.add(egui::DragValue::new(&mut self.x));
-                ui.add(egui::DragValue::new(&mut self.y));
-            })
-        });
+        vector_ui(ui, [&mut self.x, &mut self.y]);
     }
 }
 
 impl<T: egui::emath::Numeric> Inspectable for Vector3<T> {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
-        ui.group(|ui| {
-            ui.horizontal(|ui| {
-                ui.add(egui::DragValue::new(&mut self.x));
-                ui.add(egui::DragValue::new(&mut self.y));
-                ui.add(egui::DragValue::new(&mut self.z));
-            })
-        });
+        vector_ui(ui, [&mut self.x, &mut self.y, &mut self.z]);
     }
 }
 
@@ -48,21 +52,281 @@ impl<T: Inspectable + sfml::SfResource> Inspectable for SfBox<T> {
     }
 }
 
+thread_local! {
+    /// The sound buffer being previewed from the inspector, if any, along with the ID of the
+    /// inspector it is previewed from.
+    static PREVIEW: RefCell<Option<(egui::Id, Preview)>> = RefCell::new(None);
+}
+
+/// A copy of a sound buffer being previewed, along with the sound playing it.
+struct Preview {
+    // Declared before the buffer it borrows, so that it is dropped first
+    sound: Sound<'static>,
+    _buffer: SfBox<SoundBuffer>,
+}
+
+impl Preview {
+    fn play(buffer: &SoundBuffer) -> Self {
+        let buffer = buffer.to_owned();
+        // SAFETY: The buffer is boxed, so it does not move along with the preview, and the sound
+        // is dropped before it
+        let mut sound = Sound::with_buffer(unsafe { &*(&*buffer as *const SoundBuffer) });
+        sound.play();
+        Self {
+            sound,
+            _buffer: buffer,
+        }
+    }
+
+    fn is_playing(&self) -> bool {
+        self.sound.status() == SoundStatus::PLAYING
+    }
+}
+
 impl Inspectable for SoundBuffer {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
-        // TODO: Better impl
-        ui.label(format!(
-            "SoundBuffer; {:.1}s long",
-            self.duration().as_seconds()
-        ));
+        let id = ui.id().with("guiedit sound buffer");
+        ui.vertical(|ui| {
+            ui.label(format!(
+                "{:.2}s, {} channels, {} Hz",
+                self.duration().as_seconds(),
+                self.channel_count(),
+                self.sample_rate()
+            ));
+
+            // Only one buffer is previewed at a time
+            let position = PREVIEW.with(|preview| {
+                let mut preview = preview.borrow_mut();
+                let previewed = |preview: &Option<(egui::Id, Preview)>| match preview {
+                    Some((preview_id, preview)) if *preview_id == id && preview.is_playing() => {
+                        Some(preview.sound.playing_offset().as_seconds())
+                    }
+                    _ => None,
+                };
+                if previewed(&preview).is_some() {
+                    if ui.button("⏹ Stop").clicked() {
+                        *preview = None;
+                    }
+                } else if ui.button("▶ Play").clicked() {
+                    *preview = Some((id, Preview::play(self)));
+                }
+                previewed(&preview)
+            });
+            if position.is_some() {
+                ui.ctx().request_repaint();
+            }
+
+            let channel_count = self.channel_count() as usize;
+            waveform::waveform_ui(
+                ui,
+                id,
+                self.samples(),
+                channel_count,
+                self.sample_rate(),
+                position,
+            );
+        });
+    }
+}
+
+/// The playback controls shared by sounds and music, which SFML only has as inherent methods.
+trait Playback: SoundSource {
+    fn status(&self) -> SoundStatus;
+    fn play(&mut self);
+    fn pause(&mut self);
+    fn stop(&mut self);
+    fn is_looping(&self) -> bool;
+    fn set_looping(&mut self, looping: bool);
+    fn playing_offset(&self) -> Time;
+    fn set_playing_offset(&mut self, offset: Time);
+    fn duration(&self) -> Option<Time>;
+}
+
+macro_rules! impl_playback {
+    ($type:ty, |$this:ident| $duration:expr) => {
+        impl Playback for $type {
+            fn status(&self) -> SoundStatus {
+                <$type>::status(self)
+            }
+
+            fn play(&mut self) {
+                <$type>::play(self)
+            }
+
+            fn pause(&mut self) {
+                <$type>::pause(self)
+            }
+
+            fn stop(&mut self) {
+                <$type>::stop(self)
+            }
+
+            fn is_looping(&self) -> bool {
+                <$type>::is_looping(self)
+            }
+
+            fn set_looping(&mut self, looping: bool) {
+                <$type>::set_looping(self, looping)
+            }
+
+            fn playing_offset(&self) -> Time {
+                <$type>::playing_offset(self)
+            }
+
+            fn set_playing_offset(&mut self, offset: Time) {
+                <$type>::set_playing_offset(self, offset)
+            }
+
+            fn duration(&self) -> Option<Time> {
+                let $this = self;
+                $duration
+            }
+        }
+    };
+}
+
+impl_playback!(Sound<'_>, |sound| sound.buffer().map(SoundBuffer::duration));
+impl_playback!(Music<'_>, |music| Some(music.duration()));
+
+/// Shows the status of a sound or music, along with controls for its playback.
+fn playback_ui(source: &mut impl Playback, ui: &mut egui::Ui) {
+    let status = source.status();
+    ui.horizontal(|ui| {
+        if status == SoundStatus::PLAYING {
+            if ui.button("⏸ Pause").clicked() {
+                source.pause();
+            }
+        } else if ui.button("▶ Play").clicked() {
+            source.play();
+        }
+        let stop = egui::Button::new("⏹ Stop");
+        if ui
+            .add_enabled(status != SoundStatus::STOPPED, stop)
+            .clicked()
+        {
+            source.stop();
+        }
+        ui.weak(if status == SoundStatus::PLAYING {
+            "Playing"
+        } else if status == SoundStatus::PAUSED {
+            "Paused"
+        } else {
+            "Stopped"
+        });
+    });
+
+    let mut volume = source.volume();
+    let slider = egui::Slider::new(&mut volume, 0.0..=100.0).text("Volume");
+    if ui.add(slider).changed() {
+        source.set_volume(volume);
+    }
+    let mut pitch = source.pitch();
+    let slider = egui::Slider::new(&mut pitch, 0.1..=4.0)
+        .logarithmic(true)
+        .text("Pitch");
+    if ui.add(slider).changed() {
+        source.set_pitch(pitch);
+    }
+    let mut looping = source.is_looping();
+    if ui.checkbox(&mut looping, "Loop").changed() {
+        source.set_looping(looping);
+    }
+    let mut offset = source.playing_offset().as_seconds();
+    let duration = source.duration().map_or(0., Time::as_seconds);
+    let slider = egui::Slider::new(&mut offset, 0.0..=duration)
+        .suffix(" s")
+        .text("Offset");
+    if ui.add(slider).changed() {
+        source.set_playing_offset(Time::seconds(offset));
+    }
+
+    if status == SoundStatus::PLAYING {
+        ui.ctx().request_repaint();
+    }
+}
+
+impl Inspectable for Sound<'_> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            playback_ui(self, ui);
+            if let Some(buffer) = self.buffer() {
+                let position = self.playing_offset().as_seconds();
+                waveform::waveform_ui(
+                    ui,
+                    "guiedit sound",
+                    buffer.samples(),
+                    buffer.channel_count() as usize,
+                    buffer.sample_rate(),
+                    Some(position),
+                );
+            }
+        });
+    }
+}
+
+impl Inspectable for Music<'_> {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.label(format!(
+                "{:.2}s, {} channels, {} Hz",
+                self.duration().as_seconds(),
+                self.channel_count(),
+                self.sample_rate()
+            ));
+            playback_ui(self, ui);
+        });
+    }
+}
+
+impl Inspectable for Font {
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("Font; {}", self.info().family));
     }
 }
 
 impl Inspectable for Texture {
     fn inspect_ui(&mut self, ui: &mut egui::Ui) {
-        ui.image(
-            egui::TextureId::User(self.native_handle() as u64),
-            egui::Vec2::new(self.size().x as f32, self.size().y as f32),
-        );
+        let id = ui.id().with("guiedit texture");
+        let mut view = ImageView::load(ui.ctx(), id);
+        let size = self.size();
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}×{}", size.x, size.y));
+                let mut smooth = self.is_smooth();
+                if ui.checkbox(&mut smooth, "Smooth").changed() {
+                    self.set_smooth(smooth);
+                }
+                let mut repeated = self.is_repeated();
+                if ui.checkbox(&mut repeated, "Repeated").changed() {
+                    self.set_repeated(repeated);
+                }
+                let mut srgb = self.is_srgb();
+                if ui.checkbox(&mut srgb, "sRGB").changed() {
+                    self.set_srgb(srgb);
+                }
+            });
+            view.controls_ui(ui);
+
+            // The texture is shown through a copy managed by egui, since the only texture of the
+            // application egui can draw is the viewport
+            let texture = image_view::image_copy(
+                ui.ctx(),
+                id.with("copy"),
+                self.native_handle() as u64,
+                [size.x, size.y],
+                view.channels,
+                || Some(self.copy_to_image()?.pixel_data().to_vec()),
+            );
+
+            if let Some(pixel) = view.image_ui(ui, texture, [size.x, size.y]) {
+                // Only read back while hovering, since it is slow
+                let image = self.copy_to_image();
+                let color = image
+                    .as_ref()
+                    .and_then(|image| image_view::pixel_at(image.pixel_data(), size.x, pixel));
+                image_view::pixel_ui(ui, pixel, color);
+            }
+        });
+        view.store(ui.ctx(), id);
     }
 }
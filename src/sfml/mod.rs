@@ -1,5 +1,8 @@
+#[cfg(feature = "asset-reload")]
+pub mod assets;
 pub mod graphics;
 pub mod inspectable;
+mod replay;
 pub mod tree;
 
 mod util;
@@ -0,0 +1,245 @@
+//! Storage of SFML events in input recordings.
+//!
+//! Events are written as their variant name followed by their fields separated by spaces, with
+//! enums written as the names of their variants, booleans as `0` or `1` and characters as their
+//! code point, e.g. `KeyPressed A 0 1 0 0` or `MouseButtonPressed Left 120 48`.
+
+use std::str::{FromStr, SplitWhitespace};
+
+use ::sfml::window::{
+    joystick::Axis,
+    mouse::{Button, Wheel},
+    sensor::Type as SensorType,
+    Event, Key,
+};
+
+use crate::replay::RecordedEvent;
+
+/// Defines a function returning the variant of a fieldless enum whose name is given, i.e. the
+/// inverse of its `Debug` implementation.
+macro_rules! from_name {
+    ($fn:ident -> $type:ident { $($variant:ident),* $(,)? }) => {
+        fn $fn(name: &str) -> Option<$type> {
+            match name {
+                $(stringify!($variant) => Some($type::$variant),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+from_name!(key_from_name -> Key {
+    Unknown, A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Num0, Num1, Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9,
+    Escape, LControl, LShift, LAlt, LSystem, RControl, RShift, RAlt, RSystem, Menu,
+    LBracket, RBracket, Semicolon, Comma, Period, Quote, Slash, Backslash, Tilde, Equal, Hyphen,
+    Space, Enter, Backspace, Tab, PageUp, PageDown, End, Home, Insert, Delete,
+    Add, Subtract, Multiply, Divide, Left, Right, Up, Down,
+    Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15, Pause,
+});
+
+from_name!(button_from_name -> Button { Left, Right, Middle, XButton1, XButton2 });
+from_name!(wheel_from_name -> Wheel { VerticalWheel, HorizontalWheel });
+from_name!(axis_from_name -> Axis { X, Y, Z, R, U, V, PovX, PovY });
+
+from_name!(sensor_from_name -> SensorType {
+    Accelerometer, Gyroscope, Magnetometer, Gravity, UserAcceleration, Orientation,
+});
+
+fn flag(value: bool) -> u8 {
+    value as u8
+}
+
+fn next<T: FromStr>(fields: &mut SplitWhitespace) -> Option<T> {
+    fields.next()?.parse().ok()
+}
+
+fn next_flag(fields: &mut SplitWhitespace) -> Option<bool> {
+    match fields.next()? {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    }
+}
+
+impl RecordedEvent for Event {
+    fn to_line(&self) -> Option<String> {
+        let line = match *self {
+            Event::Closed => "Closed".to_owned(),
+            Event::Resized { width, height } => format!("Resized {} {}", width, height),
+            Event::LostFocus => "LostFocus".to_owned(),
+            Event::GainedFocus => "GainedFocus".to_owned(),
+            Event::TextEntered { unicode } => format!("TextEntered {}", unicode as u32),
+            Event::KeyPressed {
+                code,
+                alt,
+                ctrl,
+                shift,
+                system,
+            } => format!(
+                "KeyPressed {:?} {} {} {} {}",
+                code,
+                flag(alt),
+                flag(ctrl),
+                flag(shift),
+                flag(system)
+            ),
+            Event::KeyReleased {
+                code,
+                alt,
+                ctrl,
+                shift,
+                system,
+            } => format!(
+                "KeyReleased {:?} {} {} {} {}",
+                code,
+                flag(alt),
+                flag(ctrl),
+                flag(shift),
+                flag(system)
+            ),
+            Event::MouseWheelScrolled { wheel, delta, x, y } => {
+                format!("MouseWheelScrolled {:?} {} {} {}", wheel, delta, x, y)
+            }
+            Event::MouseButtonPressed { button, x, y } => {
+                format!("MouseButtonPressed {:?} {} {}", button, x, y)
+            }
+            Event::MouseButtonReleased { button, x, y } => {
+                format!("MouseButtonReleased {:?} {} {}", button, x, y)
+            }
+            Event::MouseMoved { x, y } => format!("MouseMoved {} {}", x, y),
+            Event::MouseEntered => "MouseEntered".to_owned(),
+            Event::MouseLeft => "MouseLeft".to_owned(),
+            Event::JoystickButtonPressed { joystickid, button } => {
+                format!("JoystickButtonPressed {} {}", joystickid, button)
+            }
+            Event::JoystickButtonReleased { joystickid, button } => {
+                format!("JoystickButtonReleased {} {}", joystickid, button)
+            }
+            Event::JoystickMoved {
+                joystickid,
+                axis,
+                position,
+            } => format!("JoystickMoved {} {:?} {}", joystickid, axis, position),
+            Event::JoystickConnected { joystickid } => {
+                format!("JoystickConnected {}", joystickid)
+            }
+            Event::JoystickDisconnected { joystickid } => {
+                format!("JoystickDisconnected {}", joystickid)
+            }
+            Event::TouchBegan { finger, x, y } => format!("TouchBegan {} {} {}", finger, x, y),
+            Event::TouchMoved { finger, x, y } => format!("TouchMoved {} {} {}", finger, x, y),
+            Event::TouchEnded { finger, x, y } => format!("TouchEnded {} {} {}", finger, x, y),
+            Event::SensorChanged { type_, x, y, z } => {
+                format!("SensorChanged {:?} {} {} {}", type_, x, y, z)
+            }
+            // Events consumed by the editor are replaced by `NOOP_EVENT`, which is not recorded
+            _ => return None,
+        };
+        Some(line)
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let fields = &mut fields;
+        let event = match fields.next()? {
+            "Closed" => Event::Closed,
+            "Resized" => Event::Resized {
+                width: next(fields)?,
+                height: next(fields)?,
+            },
+            "LostFocus" => Event::LostFocus,
+            "GainedFocus" => Event::GainedFocus,
+            "TextEntered" => Event::TextEntered {
+                unicode: char::from_u32(next(fields)?)?,
+            },
+            name @ ("KeyPressed" | "KeyReleased") => {
+                let code = key_from_name(fields.next()?)?;
+                let (alt, ctrl) = (next_flag(fields)?, next_flag(fields)?);
+                let (shift, system) = (next_flag(fields)?, next_flag(fields)?);
+                if name == "KeyPressed" {
+                    Event::KeyPressed {
+                        code,
+                        alt,
+                        ctrl,
+                        shift,
+                        system,
+                    }
+                } else {
+                    Event::KeyReleased {
+                        code,
+                        alt,
+                        ctrl,
+                        shift,
+                        system,
+                    }
+                }
+            }
+            "MouseWheelScrolled" => Event::MouseWheelScrolled {
+                wheel: wheel_from_name(fields.next()?)?,
+                delta: next(fields)?,
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "MouseButtonPressed" => Event::MouseButtonPressed {
+                button: button_from_name(fields.next()?)?,
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "MouseButtonReleased" => Event::MouseButtonReleased {
+                button: button_from_name(fields.next()?)?,
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "MouseMoved" => Event::MouseMoved {
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "MouseEntered" => Event::MouseEntered,
+            "MouseLeft" => Event::MouseLeft,
+            "JoystickButtonPressed" => Event::JoystickButtonPressed {
+                joystickid: next(fields)?,
+                button: next(fields)?,
+            },
+            "JoystickButtonReleased" => Event::JoystickButtonReleased {
+                joystickid: next(fields)?,
+                button: next(fields)?,
+            },
+            "JoystickMoved" => Event::JoystickMoved {
+                joystickid: next(fields)?,
+                axis: axis_from_name(fields.next()?)?,
+                position: next(fields)?,
+            },
+            "JoystickConnected" => Event::JoystickConnected {
+                joystickid: next(fields)?,
+            },
+            "JoystickDisconnected" => Event::JoystickDisconnected {
+                joystickid: next(fields)?,
+            },
+            "TouchBegan" => Event::TouchBegan {
+                finger: next(fields)?,
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "TouchMoved" => Event::TouchMoved {
+                finger: next(fields)?,
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "TouchEnded" => Event::TouchEnded {
+                finger: next(fields)?,
+                x: next(fields)?,
+                y: next(fields)?,
+            },
+            "SensorChanged" => Event::SensorChanged {
+                type_: sensor_from_name(fields.next()?)?,
+                x: next(fields)?,
+                y: next(fields)?,
+                z: next(fields)?,
+            },
+            _ => return None,
+        };
+        fields.next().is_none().then_some(event)
+    }
+}
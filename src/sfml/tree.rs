@@ -12,4 +12,8 @@ impl<T: TreeNode + sfml::SfResource> TreeNode for SfBox<T> {
     fn contents_ui(&mut self, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
         self.deref_mut().contents_ui(id, selected, ui);
     }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        self.deref_mut().visit_children(id, f)
+    }
 }
@@ -11,10 +11,7 @@ pub fn map(
 }
 
 /// Fits an object with a certain aspect ratio into a rect with the size given, and returns its final size.
-pub fn fit_aspect_ratio_in_size(
-    aspect_ratio: f32,
-    available_size: egui::Vec2,
-) -> egui::Vec2 {
+pub fn fit_aspect_ratio_in_size(aspect_ratio: f32, available_size: egui::Vec2) -> egui::Vec2 {
     if available_size.y > available_size.x / aspect_ratio
         && available_size.x < available_size.y * aspect_ratio
     {
@@ -0,0 +1,407 @@
+//! Headless harness for testing [`Inspectable`] and [`TreeNode`] implementations.
+//!
+//! A [`Harness`] runs the inspector or the tree of an object inside an [`egui::Context`] without
+//! any window, keeping track of the fields and tree nodes rendered on each frame. Tests can then
+//! simulate clicks and drags on them by name and check the resulting values:
+//!
+//! ```
+//! use guiedit::{testing::Harness, Inspectable};
+//!
+//! #[derive(Inspectable)]
+//! struct Player {
+//!     health: i32,
+//!     alive: bool,
+//! }
+//!
+//! let mut harness = Harness::inspector(Player { health: 100, alive: true });
+//! harness.click("alive");
+//! // Integers change by a quarter of the distance dragged
+//! harness.drag("health", guiedit::egui::vec2(-40., 0.));
+//! assert!(!harness.state().alive);
+//! assert_eq!(harness.state().health, 90);
+//! ```
+//!
+//! Fields are registered by [`labeled_ui`](crate::inspectable::labeled_ui), which is used by the
+//! default `inspect_ui_outside` implementation, and tree nodes by
+//! [`default_node_ui`](crate::tree::default_node_ui) and
+//! [`default_parent_node_ui`](crate::tree::default_parent_node_ui).
+//!
+//! These widgets only report themselves to the harness with the `testing` feature, which is
+//! meant to be enabled for tests alone:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! guiedit = { version = "0.1", features = ["testing"] }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use egui::{Context, Event, Id, Modifiers, PointerButton, Pos2, RawInput, Rect, Vec2};
+
+use crate::{inspectable::Inspectable, tree::TreeNode};
+
+/// The size of the screen the harness renders to.
+const SCREEN_SIZE: Vec2 = Vec2::new(1024., 768.);
+/// The time between frames run by the harness.
+const FRAME_DURATION: f64 = 1. / 60.;
+
+/// A field rendered by the inspector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedField {
+    pub name: String,
+    /// The area covered by the widgets of the field, excluding its label.
+    pub rect: Rect,
+}
+
+/// A node rendered in the tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedNode {
+    pub name: String,
+    pub id: u64,
+    /// The number of parents of this node shown in the tree; Zero for the root.
+    pub depth: usize,
+    pub selected: bool,
+    /// Whether the children of this node are shown, or `None` if it cannot have any.
+    pub expanded: Option<bool>,
+    /// The area covered by the selectable label of the node.
+    pub rect: Rect,
+    /// The area covered by the button that expands or collapses the node, if any.
+    pub toggle_rect: Option<Rect>,
+}
+
+/// A button rendered by the inspector, e.g. for `#[inspectable(buttons(...))]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedButton {
+    pub text: String,
+    pub rect: Rect,
+}
+
+#[derive(Default)]
+struct Recording {
+    fields: Vec<RenderedField>,
+    nodes: Vec<RenderedNode>,
+    buttons: Vec<RenderedButton>,
+    depth: usize,
+}
+
+fn recording_id() -> Id {
+    Id::new("guiedit::testing::recording")
+}
+
+/// Calls `f` with the recording of the current frame, if the context given is being run by a
+/// harness.
+fn with_recording<R>(ctx: &Context, f: impl FnOnce(&mut Recording) -> R) -> Option<R> {
+    let recording = ctx
+        .data()
+        .get_temp::<Arc<Mutex<Recording>>>(recording_id())?;
+    let mut recording = recording.lock().unwrap();
+    Some(f(&mut recording))
+}
+
+/// Records a field before its contents are shown, returning its index so that the area covered by
+/// them can be recorded afterwards.
+pub(crate) fn record_field(ctx: &Context, name: &str) -> Option<usize> {
+    with_recording(ctx, |recording| {
+        recording.fields.push(RenderedField {
+            name: name.to_owned(),
+            rect: Rect::NOTHING,
+        });
+        recording.fields.len() - 1
+    })
+}
+
+pub(crate) fn record_field_rect(ctx: &Context, index: Option<usize>, rect: Rect) {
+    if let Some(index) = index {
+        with_recording(ctx, |recording| recording.fields[index].rect = rect);
+    }
+}
+
+pub(crate) fn record_button(ctx: &Context, text: &str, rect: Rect) {
+    with_recording(ctx, |recording| {
+        recording.buttons.push(RenderedButton {
+            text: text.to_owned(),
+            rect,
+        })
+    });
+}
+
+/// Records a node, returning its index so that its toggle button can be recorded afterwards.
+pub(crate) fn record_node(
+    ctx: &Context,
+    name: &str,
+    id: u64,
+    selected: bool,
+    expanded: Option<bool>,
+    rect: Rect,
+) -> Option<usize> {
+    with_recording(ctx, |recording| {
+        recording.nodes.push(RenderedNode {
+            name: name.to_owned(),
+            id,
+            depth: recording.depth,
+            selected,
+            expanded,
+            rect,
+            toggle_rect: None,
+        });
+        recording.nodes.len() - 1
+    })
+}
+
+pub(crate) fn record_node_toggle(ctx: &Context, index: Option<usize>, rect: Rect) {
+    if let Some(index) = index {
+        with_recording(ctx, |recording| {
+            recording.nodes[index].toggle_rect = Some(rect)
+        });
+    }
+}
+
+/// Marks the start of the children of the last node recorded.
+pub(crate) fn enter_node(ctx: &Context) {
+    with_recording(ctx, |recording| recording.depth += 1);
+}
+
+/// Marks the end of the children of a node.
+pub(crate) fn exit_node(ctx: &Context) {
+    with_recording(ctx, |recording| recording.depth -= 1);
+}
+
+type ShowFn<'a, S> = Box<dyn FnMut(&mut S, &Context) + 'a>;
+
+/// Runs the UI of an object without a window, simulating user input on it.
+///
+/// Each action given to the harness (e.g. [`click`](Self::click)) runs the frames required for
+/// it to be processed, plus a final frame so that the fields and nodes reported reflect its result.
+pub struct Harness<'a, S> {
+    ctx: Context,
+    state: S,
+    show: ShowFn<'a, S>,
+    time: f64,
+    /// The modifier keys held down during the frames run.
+    modifiers: Modifiers,
+    fields: Vec<RenderedField>,
+    nodes: Vec<RenderedNode>,
+    buttons: Vec<RenderedButton>,
+}
+
+impl<'a, S> Harness<'a, S> {
+    /// Creates a harness showing `show` in a central panel, and runs its first frame.
+    pub fn new(state: S, mut show: impl FnMut(&mut S, &mut egui::Ui) + 'a) -> Self {
+        Self::with_context(state, move |state, ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| show(state, ui));
+        })
+    }
+
+    /// Creates a harness that lays out its UI on the context itself, e.g. to use several panels,
+    /// and runs its first frame.
+    pub fn with_context(state: S, show: impl FnMut(&mut S, &Context) + 'a) -> Self {
+        let ctx = Context::default();
+        // Collapsing headers are expanded and collapsed instantly, instead of over several frames
+        let mut style = (*ctx.style()).clone();
+        style.animation_time = 0.;
+        ctx.set_style(style);
+
+        let mut harness = Self {
+            ctx,
+            state,
+            show: Box::new(show),
+            time: 0.,
+            modifiers: Modifiers::default(),
+            fields: vec![],
+            nodes: vec![],
+            buttons: vec![],
+        };
+        harness.run();
+        harness
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The fields rendered on the last frame, in order.
+    pub fn fields(&self) -> &[RenderedField] {
+        &self.fields
+    }
+
+    /// The tree nodes rendered on the last frame, in order.
+    pub fn nodes(&self) -> &[RenderedNode] {
+        &self.nodes
+    }
+
+    /// The buttons rendered on the last frame, in order.
+    pub fn buttons(&self) -> &[RenderedButton] {
+        &self.buttons
+    }
+
+    /// The names of the tree nodes rendered on the last frame, in order.
+    pub fn node_names(&self) -> Vec<&str> {
+        self.nodes.iter().map(|node| node.name.as_str()).collect()
+    }
+
+    /// The node selected on the last frame, if any.
+    pub fn selected_node(&self) -> Option<&RenderedNode> {
+        self.nodes.iter().find(|node| node.selected)
+    }
+
+    /// Runs a frame without any input.
+    pub fn run(&mut self) {
+        self.run_with_events(vec![]);
+    }
+
+    /// Runs a frame with the events given as input.
+    pub fn run_with_events(&mut self, events: Vec<Event>) {
+        self.time += FRAME_DURATION;
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, SCREEN_SIZE)),
+            time: Some(self.time),
+            predicted_dt: FRAME_DURATION as f32,
+            modifiers: self.modifiers,
+            events,
+            ..Default::default()
+        };
+
+        let recording = Arc::new(Mutex::new(Recording::default()));
+        self.ctx
+            .data()
+            .insert_temp(recording_id(), recording.clone());
+        let (state, show) = (&mut self.state, &mut self.show);
+        let _ = self.ctx.run(input, |ctx| show(state, ctx));
+        self.ctx
+            .data()
+            .remove::<Arc<Mutex<Recording>>>(recording_id());
+
+        let mut recording = recording.lock().unwrap();
+        self.fields = std::mem::take(&mut recording.fields);
+        self.nodes = std::mem::take(&mut recording.nodes);
+        self.buttons = std::mem::take(&mut recording.buttons);
+    }
+
+    /// Clicks the first field, tree node or button with the name given, in that order.
+    ///
+    /// # Panics
+    /// Panics if no field, node or button with that name was rendered on the last frame.
+    pub fn click(&mut self, name: &str) {
+        self.click_at(self.rect_of(name).center());
+    }
+
+    /// Clicks the first field, tree node or button with the name given while holding down the
+    /// modifier keys given, e.g. to select several nodes.
+    ///
+    /// # Panics
+    /// Panics if no field, node or button with that name was rendered on the last frame.
+    pub fn click_with_modifiers(&mut self, name: &str, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+        self.click(name);
+        self.modifiers = Modifiers::default();
+    }
+
+    /// Drags the first field or tree node with the name given by the amount of points given.
+    ///
+    /// # Panics
+    /// Panics if no field or node with that name was rendered on the last frame.
+    pub fn drag(&mut self, name: &str, delta: Vec2) {
+        let from = self.rect_of(name).center();
+        let to = from + delta;
+        // Moving onto the widget in the same frame it is pressed would count as dragging it
+        self.run_with_events(vec![Event::PointerMoved(from)]);
+        self.run_with_events(vec![pointer_button(from, true)]);
+        self.run_with_events(vec![Event::PointerMoved(to)]);
+        self.run_with_events(vec![pointer_button(to, false)]);
+        self.run();
+    }
+
+    /// Expands or collapses the first tree node with the name given.
+    ///
+    /// # Panics
+    /// Panics if no node with that name was rendered on the last frame, or if it cannot have
+    /// children.
+    pub fn toggle(&mut self, name: &str) {
+        let node = self
+            .nodes
+            .iter()
+            .find(|node| node.name == name)
+            .unwrap_or_else(|| panic!("no node named {:?} was rendered", name));
+        let pos = node
+            .toggle_rect
+            .unwrap_or_else(|| panic!("node {:?} cannot be expanded", name))
+            .center();
+        self.click_at(pos);
+    }
+
+    fn click_at(&mut self, pos: Pos2) {
+        self.run_with_events(vec![Event::PointerMoved(pos), pointer_button(pos, true)]);
+        self.run_with_events(vec![pointer_button(pos, false)]);
+        self.run();
+    }
+
+    fn rect_of(&self, name: &str) -> Rect {
+        let field = self.fields.iter().find(|field| field.name == name);
+        let node = self.nodes.iter().find(|node| node.name == name);
+        let button = self.buttons.iter().find(|button| button.text == name);
+        match (field, node, button) {
+            (Some(field), _, _) => field.rect,
+            (None, Some(node), _) => node.rect,
+            (None, None, Some(button)) => button.rect,
+            (None, None, None) => panic!(
+                "no field, node or button named {:?} was rendered; fields: {:?}, nodes: {:?}, \
+                 buttons: {:?}",
+                name,
+                self.fields
+                    .iter()
+                    .map(|field| &field.name)
+                    .collect::<Vec<_>>(),
+                self.node_names(),
+                self.buttons
+                    .iter()
+                    .map(|button| &button.text)
+                    .collect::<Vec<_>>(),
+            ),
+        }
+    }
+}
+
+impl<'a, S: Inspectable + 'a> Harness<'a, S> {
+    /// Creates a harness showing the inspector of the object given.
+    pub fn inspector(state: S) -> Self {
+        Self::new(state, |state, ui| state.inspect_ui(ui))
+    }
+}
+
+impl<'a, S: TreeNode + 'a> Harness<'a, S> {
+    /// Creates a harness showing the tree with the object given as its root node, along with the
+    /// inspector of the node selected, like the editor does.
+    pub fn tree(root: S) -> Self {
+        let mut selected = None;
+        Self::with_context(root, move |root, ctx| {
+            egui::SidePanel::left("tree").show(ctx, |ui| {
+                root.node_ui("root", crate::tree::ROOT_ID, &mut selected, ui)
+            });
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let selected = crate::tree::selected_nodes(ctx, selected);
+                crate::inspector::inspect_nodes(root, &selected, ui);
+            });
+        })
+    }
+}
+
+fn pointer_button(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::default(),
+    }
+}
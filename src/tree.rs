@@ -2,6 +2,9 @@ use std::{hash::Hasher, ops::DerefMut};
 
 use crate::inspectable::Inspectable;
 
+/// The ID of the root node of the tree.
+pub const ROOT_ID: u64 = 0;
+
 pub trait TreeNode: Inspectable {
     /// Searches for an object with the ID given in this element and its children, and calls its
     /// inspect_ui function if it is found.
@@ -20,6 +23,253 @@ pub trait TreeNode: Inspectable {
     }
 
     fn contents_ui(&mut self, _id: u64, _selected: &mut Option<u64>, _ui: &mut egui::Ui) {}
+
+    /// Calls `f` with the name, ID and value of each of the children of this node, given the ID
+    /// of this node.
+    ///
+    /// The children and IDs given must match the ones shown by `contents_ui`. Every feature that
+    /// finds nodes by their path relies on this, i.e. pins, watches, the selection restored on
+    /// startup, remote inspection and editing several nodes at once, so manual implementations
+    /// of nodes with children must override it. The default implementation has no children.
+    fn visit_children(&mut self, _id: u64, _f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {}
+
+    /// The name of the type of this node. Only nodes of the same type can be edited together when
+    /// several of them are selected.
+    fn node_type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Shows a node in the tree without any of its children, e.g. for `#[tree_node(leaf)]` fields.
+pub struct Leaf<'n, T: TreeNode + ?Sized>(pub &'n mut T);
+
+impl<T: TreeNode + ?Sized> Inspectable for Leaf<'_, T> {
+    fn inspect_ui_outside(&mut self, name: &str, ui: &mut egui::Ui) {
+        self.0.inspect_ui_outside(name, ui)
+    }
+
+    fn inspect_ui(&mut self, ui: &mut egui::Ui) {
+        self.0.inspect_ui(ui)
+    }
+
+    fn value(&self) -> Option<crate::inspectable::Value> {
+        self.0.value()
+    }
+
+    fn set_value(&mut self, value: crate::inspectable::Value) -> bool {
+        self.0.set_value(value)
+    }
+
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        self.0.visit_fields(f)
+    }
+
+    #[cfg(feature = "asset-reload")]
+    fn reload_if_changed(&mut self) -> bool {
+        self.0.reload_if_changed()
+    }
+}
+
+impl<T: TreeNode + ?Sized> TreeNode for Leaf<'_, T> {
+    fn inspect_child(&mut self, this_id: u64, search_id: u64, ui: &mut egui::Ui) {
+        if this_id == search_id {
+            self.0.inspect_ui(ui);
+        }
+    }
+
+    fn node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
+        default_node_ui(std::any::type_name::<T>(), name, id, selected, ui);
+    }
+
+    fn node_type_name(&self) -> &'static str {
+        self.0.node_type_name()
+    }
+}
+
+/// Searches for the node with the ID given in the node given, with the ID given, and its children,
+/// and calls `f` with it if it is found. Returns whether it was found.
+pub fn find_node(
+    node: &mut dyn TreeNode,
+    id: u64,
+    search_id: u64,
+    f: &mut dyn FnMut(&mut dyn TreeNode),
+) -> bool {
+    if id == search_id {
+        f(node);
+        return true;
+    }
+
+    let mut found = false;
+    node.visit_children(id, &mut |_, child_id, child| {
+        if !found {
+            found = find_node(child, child_id, search_id, f);
+        }
+    });
+    found
+}
+
+/// Returns the names of the nodes leading from the node given, with the ID given, to the node with
+/// the ID searched (excluding the former), as given by [`TreeNode::visit_children`].
+///
+/// Returns `None` if the node searched is not found.
+pub fn path_to(node: &mut dyn TreeNode, id: u64, search_id: u64) -> Option<Vec<String>> {
+    if id == search_id {
+        return Some(vec![]);
+    }
+
+    let mut found = None;
+    node.visit_children(id, &mut |name, child_id, child| {
+        if found.is_none() {
+            found = path_to(child, child_id, search_id).map(|mut path| {
+                path.insert(0, name.to_owned());
+                path
+            });
+        }
+    });
+    found
+}
+
+/// Returns the ID of the node reached by following the names given from the node given, with the
+/// ID given; The inverse of [`path_to`].
+pub fn id_at_path(node: &mut dyn TreeNode, id: u64, path: &[String]) -> Option<u64> {
+    let Some((name, path)) = path.split_first() else {
+        return Some(id);
+    };
+
+    let mut found = None;
+    node.visit_children(id, &mut |child_name, child_id, child| {
+        if found.is_none() && child_name == name {
+            found = id_at_path(child, child_id, path);
+        }
+    });
+    found
+}
+
+/// A reference to a node of the tree that keeps pointing to the same node across runs, as long as
+/// it can be found through [`TreeNode::visit_children`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeRef {
+    /// The ID the node had when it was referenced, used if it cannot be found by its path.
+    pub id: u64,
+    /// The names of the nodes leading from the root to the node, as returned by [`path_to`], if
+    /// it could be found.
+    pub path: Option<Vec<String>>,
+}
+
+impl NodeRef {
+    /// References the node with the ID given, given the root of the tree.
+    pub fn new(root: &mut dyn TreeNode, id: u64) -> Self {
+        Self {
+            id,
+            path: path_to(root, ROOT_ID, id),
+        }
+    }
+
+    /// Returns the current ID of the node, or `None` if it does not exist anymore.
+    pub fn resolve(&self, root: &mut dyn TreeNode) -> Option<u64> {
+        match &self.path {
+            Some(path) => id_at_path(root, ROOT_ID, path),
+            None => Some(self.id),
+        }
+    }
+
+    /// A name for the node, made out of its path.
+    pub fn title(&self) -> String {
+        match &self.path {
+            Some(path) if path.is_empty() => "root".to_owned(),
+            Some(path) => path.join("/"),
+            None => format!("Node {:016x}", self.id),
+        }
+    }
+}
+
+/// The nodes selected in the tree besides the active one, which is the one passed to
+/// [`TreeNode::node_ui`].
+#[derive(Clone, Default)]
+struct Selection {
+    /// The nodes selected, in the order they were selected. Only used while it contains the active
+    /// node, so that changing the active node from outside the tree selects it alone.
+    nodes: Vec<u64>,
+    /// The node Shift-clicking selects a range from.
+    anchor: Option<u64>,
+    /// The nodes shown in the tree since its root was last shown, in order.
+    shown: Vec<u64>,
+    /// The nodes shown in the tree the previous time, in order.
+    last_shown: Vec<u64>,
+}
+
+impl Selection {
+    fn nodes(&self, active: Option<u64>) -> Vec<u64> {
+        match active {
+            Some(active) if self.nodes.contains(&active) => self.nodes.clone(),
+            _ => active.into_iter().collect(),
+        }
+    }
+}
+
+fn with_selection<R>(ctx: &egui::Context, f: impl FnOnce(&mut Selection) -> R) -> R {
+    f(ctx
+        .data()
+        .get_temp_mut_or_default::<Selection>(egui::Id::new("guiedit::tree::selection")))
+}
+
+/// Returns the nodes selected in the tree shown in the context given, given its active node: The
+/// active node first, followed by the rest of the nodes in the order they were selected.
+///
+/// Several nodes are selected by Ctrl-clicking (Cmd-clicking on macOS) them, or by Shift-clicking
+/// the last node of a range.
+pub fn selected_nodes(ctx: &egui::Context, active: Option<u64>) -> Vec<u64> {
+    let mut nodes = with_selection(ctx, |selection| selection.nodes(active));
+    if let Some(idx) = nodes.iter().position(|&id| Some(id) == active) {
+        nodes[..=idx].rotate_right(1);
+    }
+    nodes
+}
+
+/// Records a node as shown in the tree, returning whether it is selected.
+fn show_node(ctx: &egui::Context, id: u64, selected: Option<u64>) -> bool {
+    with_selection(ctx, |selection| {
+        if id == ROOT_ID {
+            selection.last_shown = std::mem::take(&mut selection.shown);
+        }
+        selection.shown.push(id);
+        selection.nodes(selected).contains(&id)
+    })
+}
+
+/// Updates the selection after the node given is clicked in the tree.
+fn click_node(ui: &egui::Ui, id: u64, selected: &mut Option<u64>) {
+    let modifiers = ui.input().modifiers;
+    with_selection(ui.ctx(), |selection| {
+        let mut nodes = selection.nodes(*selected);
+        let range = selection
+            .anchor
+            .filter(|_| modifiers.shift)
+            .and_then(|anchor| {
+                let start = selection.last_shown.iter().position(|&id| id == anchor)?;
+                let end = selection.last_shown.iter().position(|&shown| shown == id)?;
+                Some(selection.last_shown[start.min(end)..=start.max(end)].to_vec())
+            });
+        if let Some(range) = range {
+            nodes = range;
+            *selected = Some(id);
+        } else if modifiers.command && nodes.contains(&id) {
+            nodes.retain(|&node| node != id);
+            if *selected == Some(id) {
+                *selected = nodes.last().copied();
+            }
+        } else if modifiers.command {
+            nodes.push(id);
+            *selected = Some(id);
+            selection.anchor = Some(id);
+        } else {
+            nodes = vec![id];
+            *selected = Some(id);
+            selection.anchor = Some(id);
+        }
+        selection.nodes = nodes;
+    });
 }
 
 pub fn default_node_ui(
@@ -30,11 +280,12 @@ pub fn default_node_ui(
     ui: &mut egui::Ui,
 ) {
     ui.horizontal(|ui| {
-        if ui
-            .selectable_label(matches!(*selected, Some(i) if i == id), name)
-            .clicked()
-        {
-            *selected = Some(id);
+        let is_selected = show_node(ui.ctx(), id, *selected);
+        let label = ui.selectable_label(is_selected, name);
+        #[cfg(feature = "testing")]
+        crate::testing::record_node(ui.ctx(), name, id, is_selected, None, label.rect);
+        if label.clicked() {
+            click_node(ui, id, selected);
         }
         ui.add_enabled_ui(false, |ui| ui.small(type_name));
     });
@@ -48,21 +299,43 @@ pub fn default_parent_node_ui(
     ui: &mut egui::Ui,
     body: impl FnOnce(u64, &mut Option<u64>, &mut egui::Ui),
 ) {
-    egui::collapsing_header::CollapsingState::load_with_default_open(
+    let state = egui::collapsing_header::CollapsingState::load_with_default_open(
         ui.ctx(),
         ui.make_persistent_id(id),
         false,
-    )
-    .show_header(ui, |ui| {
-        if ui
-            .selectable_label(matches!(*selected, Some(i) if i == id), name)
-            .clicked()
-        {
-            *selected = Some(id);
-        }
-        ui.add_enabled_ui(false, |ui| ui.small(type_name));
-    })
-    .body(|ui| body(id, selected, ui));
+    );
+    #[cfg(feature = "testing")]
+    let (expanded, mut recorded) = (state.is_open(), None);
+    #[cfg_attr(not(feature = "testing"), allow(unused_variables))]
+    let (toggle, ..) = state
+        .show_header(ui, |ui| {
+            let is_selected = show_node(ui.ctx(), id, *selected);
+            let label = ui.selectable_label(is_selected, name);
+            #[cfg(feature = "testing")]
+            {
+                recorded = crate::testing::record_node(
+                    ui.ctx(),
+                    name,
+                    id,
+                    is_selected,
+                    Some(expanded),
+                    label.rect,
+                );
+            }
+            if label.clicked() {
+                click_node(ui, id, selected);
+            }
+            ui.add_enabled_ui(false, |ui| ui.small(type_name));
+        })
+        .body(|ui| {
+            #[cfg(feature = "testing")]
+            crate::testing::enter_node(ui.ctx());
+            body(id, selected, ui);
+            #[cfg(feature = "testing")]
+            crate::testing::exit_node(ui.ctx());
+        });
+    #[cfg(feature = "testing")]
+    crate::testing::record_node_toggle(ui.ctx(), recorded, toggle.rect);
 }
 
 impl<T: TreeNode, const X: usize> TreeNode for [T; X] {
@@ -88,6 +361,15 @@ impl<T: TreeNode, const X: usize> TreeNode for [T; X] {
             element.node_ui(&i.to_string(), hasher.finish(), selected, ui);
         }
     }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        for (i, element) in self.iter_mut().enumerate() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::default();
+            hasher.write_u64(id);
+            hasher.write_u64(i as u64);
+            f(&i.to_string(), hasher.finish(), element);
+        }
+    }
 }
 
 impl<T: TreeNode> TreeNode for Vec<T> {
@@ -113,6 +395,15 @@ impl<T: TreeNode> TreeNode for Vec<T> {
             element.node_ui(&i.to_string(), hasher.finish(), selected, ui);
         }
     }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        for (i, element) in self.iter_mut().enumerate() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::default();
+            hasher.write_u64(id);
+            hasher.write_u64(i as u64);
+            f(&i.to_string(), hasher.finish(), element);
+        }
+    }
 }
 
 impl<T: TreeNode + ?Sized> TreeNode for &mut T {
@@ -127,6 +418,14 @@ impl<T: TreeNode + ?Sized> TreeNode for &mut T {
     fn node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
         (*self).node_ui(name, id, selected, ui)
     }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        (*self).visit_children(id, f)
+    }
+
+    fn node_type_name(&self) -> &'static str {
+        (**self).node_type_name()
+    }
 }
 
 impl<T: TreeNode + ?Sized> TreeNode for Box<T> {
@@ -141,4 +440,12 @@ impl<T: TreeNode + ?Sized> TreeNode for Box<T> {
     fn node_ui(&mut self, name: &str, id: u64, selected: &mut Option<u64>, ui: &mut egui::Ui) {
         self.deref_mut().node_ui(name, id, selected, ui)
     }
+
+    fn visit_children(&mut self, id: u64, f: &mut dyn FnMut(&str, u64, &mut dyn TreeNode)) {
+        self.deref_mut().visit_children(id, f)
+    }
+
+    fn node_type_name(&self) -> &'static str {
+        (**self).node_type_name()
+    }
 }
@@ -0,0 +1,211 @@
+//! Watches plotting how numeric fields evolve over time.
+//!
+//! Fields shown by [`inspect_node`] can be right-clicked and plotted, which makes
+//! [`take_plot_requests`] return a request for them. Their values are then recorded by
+//! [`Watches::record`] into a ring buffer, independently of whether they are being inspected.
+//!
+//! [`inspect_node`]: crate::inspector::inspect_node
+
+use std::collections::VecDeque;
+
+use crate::{
+    inspectable, inspector,
+    tree::{self, NodeRef, TreeNode, ROOT_ID},
+};
+
+/// The number of samples kept by each watch, if no other capacity is given.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// A request to plot a field, made from the inspector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotRequest {
+    /// The ID of the node the field belongs to.
+    pub node: u64,
+    /// The path to the field from its node, as given by [`Inspectable::visit_fields`].
+    ///
+    /// [`Inspectable::visit_fields`]: crate::Inspectable::visit_fields
+    pub field_path: Vec<String>,
+}
+
+fn with_requests<R>(ctx: &egui::Context, f: impl FnOnce(&mut Vec<PlotRequest>) -> R) -> R {
+    f(ctx
+        .data()
+        .get_temp_mut_or_default::<Vec<PlotRequest>>(egui::Id::new("guiedit::watch::requests")))
+}
+
+/// Returns the fields requested to be plotted since the last call.
+pub fn take_plot_requests(ctx: &egui::Context) -> Vec<PlotRequest> {
+    with_requests(ctx, std::mem::take)
+}
+
+/// Requests the field currently being shown by the inspector to be plotted.
+pub(crate) fn request_plot(ctx: &egui::Context) {
+    if let Some((node, field_path)) = inspector::current_field(ctx) {
+        with_requests(ctx, |requests| {
+            requests.push(PlotRequest { node, field_path })
+        });
+    }
+}
+
+/// A numeric field whose values are recorded over time.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub struct Watch {
+    pub node: NodeRef,
+    /// The path to the field from its node, as given by [`Inspectable::visit_fields`].
+    ///
+    /// [`Inspectable::visit_fields`]: crate::Inspectable::visit_fields
+    pub field_path: Vec<String>,
+    /// Whether recording new values is paused.
+    pub paused: bool,
+    /// The time and value of each of the samples recorded.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    samples: VecDeque<[f64; 2]>,
+}
+
+impl Watch {
+    pub fn title(&self) -> String {
+        format!("{}.{}", self.node.title(), self.field_path.join("."))
+    }
+
+    /// The time and value of each of the samples recorded, from oldest to newest.
+    pub fn samples(&self) -> impl Iterator<Item = [f64; 2]> + '_ {
+        self.samples.iter().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Returns the samples recorded as CSV, with a `time` and a `value` column.
+    pub fn to_csv(&self) -> String {
+        self.samples()
+            .fold(String::from("time,value\n"), |mut csv, [time, value]| {
+                csv.push_str(&format!("{},{}\n", time, value));
+                csv
+            })
+    }
+}
+
+/// A list of watches, along with the number of samples each of them keeps.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "persistence",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Watches {
+    watches: Vec<Watch>,
+    capacity: usize,
+}
+
+impl Default for Watches {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl Watches {
+    /// Creates a list of watches that keep the number of samples given each.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            watches: vec![],
+            capacity,
+        }
+    }
+
+    /// Watches the field with the path given of the node with the ID given, given the root of the
+    /// tree. Does nothing if the field is already watched.
+    pub fn add(&mut self, root: &mut dyn TreeNode, node: u64, field_path: Vec<String>) {
+        let node = NodeRef::new(root, node);
+        if !self
+            .watches
+            .iter()
+            .any(|watch| watch.node == node && watch.field_path == field_path)
+        {
+            self.watches.push(Watch {
+                node,
+                field_path,
+                paused: false,
+                samples: VecDeque::new(),
+            });
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Watch> {
+        self.watches.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.watches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watches.is_empty()
+    }
+
+    /// Records the current value of every watched field that is not paused, given the root of
+    /// the tree and the current time in seconds. Fields that cannot be found or are not numeric
+    /// are skipped.
+    pub fn record(&mut self, root: &mut dyn TreeNode, time: f64) {
+        for watch in self.watches.iter_mut().filter(|watch| !watch.paused) {
+            let Some(id) = watch.node.resolve(root) else {
+                continue;
+            };
+            let mut value = None;
+            tree::find_node(root, ROOT_ID, id, &mut |node| {
+                value = inspectable::field_value(node, &watch.field_path);
+            });
+            if let Some(value) = value.as_ref().and_then(inspectable::Value::to_float) {
+                if watch.samples.len() >= self.capacity {
+                    watch.samples.pop_front();
+                }
+                watch.samples.push_back([time, value]);
+            }
+        }
+    }
+
+    /// Shows a plot for each watch, along with controls to pause, export and remove them.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        if self.watches.is_empty() {
+            ui.add_enabled_ui(false, |ui| {
+                ui.label(
+                    "Right-click a numeric field in the inspector and select \"Plot\" to watch it",
+                )
+            });
+            return;
+        }
+
+        self.watches.retain_mut(|watch| {
+            let title = watch.title();
+            let mut keep = true;
+            ui.horizontal(|ui| {
+                ui.strong(&title);
+                if let Some([_, value]) = watch.samples.back() {
+                    ui.label(format!("= {}", value));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    keep = !ui.small_button("×").on_hover_text("Remove").clicked();
+                    if ui
+                        .small_button("Copy CSV")
+                        .on_hover_text("Copy the samples recorded to the clipboard")
+                        .clicked()
+                    {
+                        ui.output().copied_text = watch.to_csv();
+                    }
+                    if ui.small_button("Clear").clicked() {
+                        watch.clear();
+                    }
+                    ui.checkbox(&mut watch.paused, "Pause");
+                });
+            });
+
+            let line = egui::plot::Line::new(watch.samples().collect::<egui::plot::PlotPoints>());
+            egui::plot::Plot::new(("guiedit watch", &title))
+                .height(120.)
+                .show(ui, |plot| plot.line(line));
+            ui.separator();
+            keep
+        });
+    }
+}
@@ -0,0 +1,87 @@
+//! Waveform plots of audio samples, as shown when inspecting sound buffers.
+
+use std::sync::Arc;
+
+/// The maximum number of points plotted, so that long sounds stay cheap to show.
+pub const MAX_POINTS: usize = 4096;
+
+/// Returns the points of the waveform of the samples given, interleaved by channel, with the time
+/// in seconds as X and the mean of the channels, from -1 to 1, as Y.
+///
+/// Long sounds are reduced to [`MAX_POINTS`] points, keeping the minimum and the maximum of each
+/// span of samples so that peaks are still visible.
+pub fn waveform(samples: &[i16], channel_count: usize, sample_rate: u32) -> Vec<[f64; 2]> {
+    let frames = samples.chunks_exact(channel_count.max(1)).map(|frame| {
+        let sum: f64 = frame.iter().map(|&sample| sample as f64).sum();
+        sum / frame.len() as f64 / i16::MAX as f64
+    });
+    let time = |frame: usize| frame as f64 / sample_rate.max(1) as f64;
+
+    let frame_count = samples.len() / channel_count.max(1);
+    if frame_count <= MAX_POINTS {
+        return frames.enumerate().map(|(i, y)| [time(i), y]).collect();
+    }
+    let span = frame_count.div_ceil(MAX_POINTS / 2);
+    let mut points = Vec::with_capacity(MAX_POINTS);
+    let mut frames = frames.enumerate().peekable();
+    while let Some((start, first)) = frames.next() {
+        let (mut min, mut max) = ((start, first), (start, first));
+        while let Some((i, y)) = frames.next_if(|&(i, _)| i < start + span) {
+            if y < min.1 {
+                min = (i, y);
+            }
+            if y > max.1 {
+                max = (i, y);
+            }
+        }
+        let (a, b) = if min.0 <= max.0 {
+            (min, max)
+        } else {
+            (max, min)
+        };
+        points.push([time(a.0), a.1]);
+        points.push([time(b.0), b.1]);
+    }
+    points
+}
+
+/// Plots the waveform of the samples given, interleaved by channel, along with a line at the
+/// position given, in seconds, if any.
+///
+/// The waveform is only computed again when the samples given change.
+pub fn waveform_ui(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    samples: &[i16],
+    channel_count: usize,
+    sample_rate: u32,
+    position: Option<f32>,
+) {
+    type Cached = (usize, usize, Arc<Vec<[f64; 2]>>);
+
+    let id = ui.id().with(id_source);
+    let key = (samples.as_ptr() as usize, samples.len());
+    let cached = ui.data().get_temp::<Cached>(id);
+    let points = match cached {
+        Some((ptr, len, points)) if (ptr, len) == key => points,
+        _ => {
+            let points = Arc::new(waveform(samples, channel_count, sample_rate));
+            ui.data()
+                .insert_temp::<Cached>(id, (key.0, key.1, points.clone()));
+            points
+        }
+    };
+
+    let line = egui::plot::Line::new(egui::plot::PlotPoints::new(points.to_vec()));
+    egui::plot::Plot::new(id)
+        .height(96.)
+        .include_y(-1.)
+        .include_y(1.)
+        .allow_scroll(false)
+        .show(ui, |plot| {
+            plot.line(line);
+            if let Some(position) = position {
+                plot.vline(egui::plot::VLine::new(position));
+            }
+        });
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "asset-reload")]
+
+use std::{
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use guiedit::{
+    assets::{reload_changed, AssetHandle, AssetWatcher},
+    inspectable::Inspectable,
+};
+
+/// A text file reloaded when it changes.
+struct Text {
+    text: String,
+    handle: AssetHandle,
+}
+
+impl Text {
+    fn load(watcher: &AssetWatcher, path: &Path) -> Self {
+        Self {
+            text: std::fs::read_to_string(path).unwrap(),
+            handle: watcher.watch(path, "Text").unwrap(),
+        }
+    }
+}
+
+impl Inspectable for Text {
+    fn reload_if_changed(&mut self) -> bool {
+        if !self.handle.is_changed() {
+            return false;
+        }
+        match std::fs::read_to_string(self.handle.path()) {
+            Ok(text) => {
+                self.text = text;
+                self.handle.reloaded(Ok(()));
+                true
+            }
+            Err(error) => {
+                self.handle.reloaded(Err(error.to_string()));
+                false
+            }
+        }
+    }
+}
+
+struct Level {
+    background: Box<Text>,
+    dialogs: Vec<Text>,
+}
+
+impl Inspectable for Level {
+    fn visit_fields(&mut self, f: &mut dyn FnMut(&str, &mut dyn Inspectable)) {
+        f("background", &mut self.background);
+        f("dialogs", &mut self.dialogs);
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("guiedit-assets-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.canonicalize().unwrap()
+}
+
+/// Waits for the watcher to report changes, failing after a few seconds.
+fn wait_for_changes(watcher: &AssetWatcher) {
+    let start = Instant::now();
+    while !watcher.take_changes() {
+        assert!(start.elapsed() < Duration::from_secs(5), "no changes seen");
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn changed_assets_are_reloaded_in_place() {
+    let dir = temp_dir("reload");
+    let (sky, hello) = (dir.join("sky.txt"), dir.join("hello.txt"));
+    std::fs::write(&sky, "blue").unwrap();
+    std::fs::write(&hello, "Hello").unwrap();
+
+    let watcher = AssetWatcher::default();
+    let mut level = Level {
+        background: Box::new(Text::load(&watcher, &sky)),
+        dialogs: vec![Text::load(&watcher, &hello), Text::load(&watcher, &hello)],
+    };
+    assert!(!watcher.take_changes());
+    assert_eq!(reload_changed(&mut level), 0);
+
+    std::fs::write(&hello, "Hi").unwrap();
+    wait_for_changes(&watcher);
+    assert_eq!(reload_changed(&mut level), 2);
+    assert_eq!(level.dialogs[0].text, "Hi");
+    assert_eq!(level.dialogs[1].text, "Hi");
+    assert_eq!(level.background.text, "blue");
+    assert_eq!(reload_changed(&mut level), 0);
+
+    let assets = watcher.assets();
+    let reloads = assets
+        .iter()
+        .map(|asset| (asset.path.as_path(), asset.reloads));
+    assert_eq!(
+        reloads.collect::<Vec<_>>(),
+        [(hello.as_path(), 2), (sky.as_path(), 0)]
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn assets_are_unwatched_when_dropped_and_report_errors() {
+    let dir = temp_dir("errors");
+    let path = dir.join("dialog.txt");
+    std::fs::write(&path, "Hello").unwrap();
+
+    let watcher = AssetWatcher::default();
+    let mut text = Text::load(&watcher, &path);
+    std::fs::remove_file(&path).unwrap();
+    wait_for_changes(&watcher);
+    assert_eq!(reload_changed(&mut text), 0);
+    assert_eq!(text.text, "Hello");
+    assert!(watcher.assets()[0].error.is_some());
+
+    drop(text);
+    assert!(watcher.assets().is_empty());
+    assert!(watcher.watch(&path, "Text").is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "capture")]
+
+use std::path::PathBuf;
+
+use guiedit::capture::{Captures, Frame, Recording, RecordingFormat};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("guiedit-capture-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A frame of the size given filled with the color given.
+fn frame(width: u32, height: u32, color: [u8; 4]) -> Frame {
+    Frame {
+        width,
+        height,
+        pixels: color.repeat((width * height) as usize),
+    }
+}
+
+fn read_png(path: &std::path::Path) -> (png::OutputInfo, Vec<u8>) {
+    let decoder = png::Decoder::new(std::fs::File::open(path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    (info, pixels)
+}
+
+#[test]
+fn frames_are_saved_as_png() {
+    let dir = temp_dir("png");
+    let path = dir.join("frame.png");
+    let frame = frame(3, 2, [255, 128, 0, 64]);
+    frame.save_png(&path).unwrap();
+
+    let (info, pixels) = read_png(&path);
+    assert_eq!((info.width, info.height), (3, 2));
+    assert_eq!(info.color_type, png::ColorType::Rgba);
+    assert_eq!(pixels, frame.pixels);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn recordings_are_written_as_png_sequences_and_gifs() {
+    let dir = temp_dir("recordings");
+    assert_eq!(
+        RecordingFormat::from_path(&dir.join("clip.GIF")),
+        RecordingFormat::Gif
+    );
+    assert_eq!(
+        RecordingFormat::from_path(&dir.join("clip")),
+        RecordingFormat::PngSequence
+    );
+
+    let mut sequence = Recording::start(dir.join("clip")).unwrap();
+    let mut gif = Recording::start(dir.join("clip.gif")).unwrap();
+    for color in [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]] {
+        sequence.push(frame(4, 4, color));
+        gif.push(frame(4, 4, color));
+    }
+    assert_eq!(sequence.frames(), 3);
+
+    let sequence = sequence.finish().unwrap();
+    let mut files = std::fs::read_dir(&sequence)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(
+        files,
+        ["frame-00000.png", "frame-00001.png", "frame-00002.png"]
+    );
+    assert_eq!(
+        read_png(&sequence.join("frame-00001.png")).1[..4],
+        [0, 255, 0, 255]
+    );
+
+    let gif = gif.finish().unwrap();
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options
+        .read_info(std::fs::File::open(gif).unwrap())
+        .unwrap();
+    let mut colors = vec![];
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        colors.push([frame.buffer[0], frame.buffer[1], frame.buffer[2]]);
+    }
+    assert_eq!(colors.len(), 3);
+    assert!(
+        colors[0][0] > 200 && colors[1][1] > 200 && colors[2][2] > 200,
+        "{:?}",
+        colors
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn recording_gifs_of_frames_of_different_sizes_fails() {
+    let dir = temp_dir("resized");
+    let mut gif = Recording::start(dir.join("clip.gif")).unwrap();
+    gif.push(frame(4, 4, [0; 4]));
+    gif.push(frame(8, 4, [0; 4]));
+    assert!(gif.finish().is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn frames_are_dropped_when_the_writer_falls_behind() {
+    let dir = temp_dir("dropped");
+    let mut gif = Recording::start(dir.join("clip.gif")).unwrap();
+    for _ in 0..100 {
+        gif.push(frame(512, 512, [255, 0, 0, 255]));
+    }
+    assert!(gif.dropped_frames() > 0);
+    assert_eq!(gif.frames() + gif.dropped_frames(), 100);
+
+    let frames = gif.frames();
+    let mut decoder = gif::DecodeOptions::new()
+        .read_info(std::fs::File::open(gif.finish().unwrap()).unwrap())
+        .unwrap();
+    let mut written = 0;
+    while decoder.read_next_frame().unwrap().is_some() {
+        written += 1;
+    }
+    assert_eq!(written, frames);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn captures_are_done_once_a_frame_is_displayed() {
+    let dir = temp_dir("captures");
+    let mut captures = Captures::default();
+    captures.dir = dir.clone();
+    captures.format = RecordingFormat::PngSequence;
+    let mut reads = 0;
+    let mut capture = || {
+        reads += 1;
+        Some(frame(2, 2, [1, 2, 3, 4]))
+    };
+
+    // Frames are only read back when needed
+    captures.frame_displayed(&mut capture);
+    captures.request_screenshot();
+    captures.request_recording_toggle();
+    captures.frame_displayed(&mut capture);
+    captures.frame_displayed(&mut capture);
+    assert_eq!(captures.recording().map(Recording::frames), Some(2));
+    captures.request_recording_toggle();
+    captures.frame_displayed(&mut capture);
+    assert!(captures.recording().is_none());
+    assert_eq!(reads, 2);
+
+    let mut saved = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    saved.sort_by_key(|path| path.is_dir());
+    assert_eq!(saved.len(), 2);
+    assert_eq!(saved[0].extension().unwrap(), "png");
+    assert_eq!(std::fs::read_dir(&saved[1]).unwrap().count(), 2);
+    std::fs::remove_dir_all(dir).unwrap();
+}
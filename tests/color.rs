@@ -0,0 +1,86 @@
+use guiedit::{
+    color::{self, EditColor, Palette},
+    egui::{self, vec2},
+    testing::Harness,
+    Inspectable,
+};
+
+#[derive(Inspectable)]
+struct Light {
+    #[inspectable(color)]
+    color: [f32; 3],
+    #[inspectable(color)]
+    outline: [u8; 4],
+    intensity: f32,
+}
+
+#[test]
+fn hex_codes_round_trip() {
+    assert_eq!(color::to_hex([255, 128, 0, 64], true), "#FF800040");
+    assert_eq!(color::to_hex([255, 128, 0, 64], false), "#FF8000");
+    assert_eq!(color::parse_hex("#FF800040"), Some([255, 128, 0, 64]));
+    assert_eq!(color::parse_hex(" ff8000 "), Some([255, 128, 0, 255]));
+    assert_eq!(color::parse_hex("#FF80"), None);
+    assert_eq!(color::parse_hex("#FF800G"), None);
+    assert_eq!(color::parse_hex("+F+F+F"), None);
+    assert_eq!(color::parse_hex("#é12345"), None);
+}
+
+#[test]
+fn colors_convert_between_srgba_and_linear_rgba() {
+    // Alpha is never premultiplied into the color components
+    let srgba = [255, 128, 0, 64];
+    let rgba = srgba.to_rgba();
+    assert_eq!(rgba[0], 1.);
+    assert!((rgba[1] - 0.2158).abs() < 0.001, "{:?}", rgba);
+    assert!((rgba[3] - 64. / 255.).abs() < 0.001, "{:?}", rgba);
+    let mut converted = [0; 4];
+    converted.set_rgba(rgba);
+    assert_eq!(converted, srgba);
+
+    // HDR colors are clamped when converted to sRGBA
+    assert_eq!(color::srgba_from_rgba([4., 0.5, -1., 2.])[0], 255);
+    assert_eq!(color::srgba_from_rgba([4., 0.5, -1., 2.])[2..], [0, 255]);
+
+    let mut rgb = [0.; 3];
+    rgb.set_rgba([2., 0.5, 0.25, 0.5]);
+    assert_eq!(rgb, [2., 0.5, 0.25]);
+    assert_eq!(rgb.to_rgba(), [2., 0.5, 0.25, 1.]);
+}
+
+#[test]
+fn color_fields_open_the_color_editor() {
+    let mut harness = Harness::inspector(Light {
+        color: [2., 1., 0.5],
+        outline: [0, 0, 0, 255],
+        intensity: 1.,
+    });
+    let names = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["color", "outline", "intensity"]);
+
+    let rect = harness.fields()[1].rect;
+    let popup = harness.context().layer_id_at(rect.max + vec2(10., 10.));
+    assert_ne!(popup.map(|layer| layer.order), Some(egui::Order::Foreground));
+    harness.click("outline");
+    let popup = harness.context().layer_id_at(rect.max + vec2(10., 10.));
+    assert_eq!(popup.map(|layer| layer.order), Some(egui::Order::Foreground));
+
+    // Showing the editor does not change the colors
+    assert_eq!(harness.state().color, [2., 1., 0.5]);
+    assert_eq!(harness.state().outline, [0, 0, 0, 255]);
+}
+
+#[test]
+fn palettes_are_kept_in_memory_and_picks_need_an_editor() {
+    let ctx = egui::Context::default();
+    assert_eq!(Palette::load(&ctx), Palette::default());
+    let palette = Palette {
+        colors: vec![[255, 0, 0, 255], [0, 0, 255, 128]],
+    };
+    palette.clone().store(&ctx);
+    assert_eq!(Palette::load(&ctx), palette);
+
+    assert!(!color::is_picking(&ctx));
+    color::pick(&ctx, [255, 255, 255, 255]);
+    assert!(!color::is_picking(&ctx));
+}
@@ -0,0 +1,121 @@
+use guiedit::commands::{Commands, LineKind};
+
+#[derive(Default)]
+struct Game {
+    enemies: Vec<(String, u32)>,
+    gold: i64,
+}
+
+fn commands() -> Commands {
+    let mut commands = Commands::default();
+    commands
+        .register("spawn", |(kind, count): (String, u32), game: &mut Game| {
+            game.enemies.push((kind, count));
+        })
+        .help("Spawns enemies");
+    commands.register("give_gold", |(gold,): (i64,), game: &mut Game| {
+        if gold < 0 {
+            return Err("Cannot give negative gold");
+        }
+        game.gold += gold;
+        Ok(format!("{} gold", game.gold))
+    });
+    commands.register("ping", |(): (), _: &mut ()| "pong");
+    commands
+}
+
+fn errors(commands: &Commands) -> Vec<&str> {
+    let errors = commands
+        .output()
+        .filter(|line| line.kind == LineKind::Error);
+    errors.map(|line| line.text.as_str()).collect()
+}
+
+#[test]
+fn commands_run_with_parsed_arguments() {
+    let mut commands = commands();
+    let mut game = Game::default();
+    commands.submit("spawn \"big slime\" 3");
+    commands.submit("give_gold 10");
+    commands.submit("give_gold 5");
+    commands.run(&mut game);
+    assert_eq!(game.enemies, [("big slime".to_owned(), 3)]);
+    assert_eq!(game.gold, 15);
+
+    let outputs = commands
+        .output()
+        .filter(|line| line.kind == LineKind::Output)
+        .map(|line| line.text.as_str());
+    assert_eq!(outputs.collect::<Vec<_>>(), ["10 gold", "15 gold"]);
+    assert_eq!(
+        commands.history(),
+        ["spawn \"big slime\" 3", "give_gold 10", "give_gold 5"]
+    );
+}
+
+#[test]
+fn invalid_lines_are_reported_and_not_run() {
+    let mut commands = commands();
+    let mut game = Game::default();
+    commands.submit("spawn slime");
+    commands.submit("spawn slime many");
+    commands.submit("despawn");
+    commands.submit("give_gold -1");
+    commands.run(&mut game);
+    assert!(game.enemies.is_empty());
+    assert_eq!(
+        errors(&commands),
+        [
+            "Expected 2 arguments, got 1",
+            "Argument 2: `many` is not a valid u32",
+            "Unknown command `despawn`",
+            "Cannot give negative gold",
+        ]
+    );
+    assert_eq!(
+        commands.validate("spawn \"slime"),
+        Err("Unterminated string".to_owned())
+    );
+}
+
+#[test]
+fn commands_only_run_with_their_state() {
+    let mut commands = commands();
+    let mut game = Game::default();
+    commands.submit("ping");
+    commands.submit("give_gold 1");
+    commands.run(&mut game);
+    assert_eq!(game.gold, 1);
+    assert!(commands.output().all(|line| line.text != "pong"));
+
+    commands.run(&mut ());
+    assert_eq!(commands.output().last().unwrap().text, "pong");
+}
+
+#[test]
+fn names_are_completed_and_described() {
+    let mut commands = commands();
+    commands.register("spawn_boss", |(): (), _: &mut Game| {});
+    assert_eq!(commands.completions("sp"), ["spawn", "spawn_boss"]);
+    assert_eq!(commands.completions("g"), ["give_gold"]);
+    assert!(commands.completions("spawn ").is_empty());
+    assert_eq!(
+        commands.get("spawn").unwrap().signature(),
+        "spawn <String> <u32>"
+    );
+
+    commands.submit("help");
+    let help = commands.output().skip(1).map(|line| line.text.as_str());
+    assert_eq!(
+        help.collect::<Vec<_>>(),
+        [
+            "give_gold <i64>",
+            "ping",
+            "spawn <String> <u32>: Spawns enemies",
+            "spawn_boss",
+            "clear: Clears the console",
+        ]
+    );
+    commands.submit("clear");
+    assert_eq!(commands.output().count(), 0);
+}
@@ -0,0 +1,82 @@
+use guiedit::console::{Console, Filter, Level};
+
+#[test]
+fn consoles_keep_the_newest_records() {
+    let console = Console::with_capacity(2);
+    console.push(Level::Info, "game", "Starting");
+    console.push(Level::Warn, "game::audio", "No audio device");
+    console.clone().push(Level::Error, "game", "Crashed");
+
+    let records = console.records();
+    let messages = records.iter().map(|record| record.message.as_str());
+    assert_eq!(messages.collect::<Vec<_>>(), ["No audio device", "Crashed"]);
+    assert!(records[0].time <= records[1].time);
+    assert!(records[1].to_line().ends_with("ERROR game Crashed"));
+
+    console.clear();
+    assert!(console.records().is_empty());
+}
+
+#[test]
+fn filters_match_level_target_and_text() {
+    let console = Console::default();
+    console.push(Level::Debug, "game", "Loaded level 1");
+    console.push(Level::Error, "game::audio", "Cannot LOAD music");
+    console.push(Level::Info, "game::audio", "Playing music");
+    let shown = |filter: &Filter| {
+        let records = console.records();
+        let shown = records.iter().filter(|record| filter.matches(record));
+        shown
+            .map(|record| record.message.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let mut filter = Filter::default();
+    assert_eq!(shown(&filter).len(), 3);
+    filter.level = Level::Info;
+    assert_eq!(shown(&filter), ["Cannot LOAD music", "Playing music"]);
+    filter.level = Level::Trace;
+    filter.search = "load".to_owned();
+    assert_eq!(shown(&filter), ["Loaded level 1", "Cannot LOAD music"]);
+    filter.target = Some("game".to_owned());
+    assert_eq!(shown(&filter), ["Loaded level 1"]);
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn log_records_are_buffered() {
+    use log::Log;
+
+    let console = Console::default();
+    console.logger().log(
+        &log::Record::builder()
+            .level(log::Level::Warn)
+            .target("game")
+            .args(format_args!("{} lives left", 2))
+            .build(),
+    );
+    let records = console.records();
+    assert_eq!(
+        (records[0].level, records[0].target.as_str()),
+        (Level::Warn, "game")
+    );
+    assert_eq!(records[0].message, "2 lives left");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_events_are_buffered() {
+    use tracing_subscriber::prelude::*;
+
+    let console = Console::default();
+    let subscriber = tracing_subscriber::registry().with(console.layer());
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "game", lives = 2, "Player died");
+    });
+    let records = console.records();
+    assert_eq!(
+        (records[0].level, records[0].target.as_str()),
+        (Level::Info, "game")
+    );
+    assert_eq!(records[0].message, "Player died lives=2");
+}
@@ -0,0 +1,85 @@
+use guiedit::{
+    debug_draw::{self, DebugDraw, DebugShape, Shape},
+    egui::{self, epaint, pos2, vec2, Color32, Pos2},
+    testing::Harness,
+};
+
+/// Paints the shapes of `draw` offset by (100, 100), and returns the shapes painted.
+fn paint(draw: &DebugDraw) -> Vec<epaint::Shape> {
+    let ctx = egui::Context::default();
+    let output = ctx.run(Default::default(), |ctx| {
+        let painter = ctx.layer_painter(egui::LayerId::background());
+        draw.paint(&painter, |pos| pos + vec2(100., 100.));
+    });
+    output.shapes.into_iter().map(|clipped| clipped.1).collect()
+}
+
+#[test]
+fn shapes_are_collected_per_frame_while_enabled() {
+    // Nothing is collected until the editor is shown
+    let mut draw = DebugDraw::default();
+    debug_draw::line([0., 0.], [1., 1.], [255, 0, 0, 255]);
+    debug_draw::set_enabled(true);
+    draw.collect();
+    assert!(draw.shapes().is_empty());
+
+    debug_draw::arrow((10., 20.), (30., 20.), [0., 1., 0.]);
+    debug_draw::text(Pos2::ZERO, "hello", [255, 255, 255, 128]);
+    debug_draw::category("physics").rect([0., 0.], [4., 2.], [0, 0, 255, 255]);
+    debug_draw::category("physics").circle([0., 0.], 8., [0, 0, 255, 255]);
+    draw.collect();
+    assert_eq!(draw.shapes().len(), 4);
+    assert_eq!(
+        draw.shapes()[0],
+        DebugShape {
+            category: debug_draw::DEFAULT_CATEGORY,
+            shape: Shape::Arrow {
+                from: pos2(10., 20.),
+                to: pos2(30., 20.)
+            },
+            color: Color32::GREEN,
+        }
+    );
+    assert_eq!(
+        draw.shapes()[1].color,
+        Color32::from_rgba_unmultiplied(255, 255, 255, 128)
+    );
+    assert_eq!(
+        draw.categories().collect::<Vec<_>>(),
+        [("default", true), ("physics", true)]
+    );
+
+    // The arrow is drawn as its line and two for its head, mapped to the screen
+    let shapes = paint(&draw);
+    assert_eq!(shapes.len(), 6);
+    assert!(matches!(
+        shapes[0],
+        epaint::Shape::LineSegment { points, .. } if points == [pos2(110., 120.), pos2(130., 120.)]
+    ));
+    draw.set_visible("physics", false);
+    assert_eq!(paint(&draw).len(), 4);
+
+    // Shapes only last for a frame, and are dropped once disabled
+    draw.collect();
+    assert!(draw.shapes().is_empty());
+    debug_draw::line([0., 0.], [1., 1.], [255, 0, 0, 255]);
+    debug_draw::set_enabled(false);
+    draw.collect();
+    assert!(draw.shapes().is_empty());
+    assert!(!draw.is_visible("physics"));
+}
+
+#[test]
+fn categories_can_be_hidden_from_the_editor() {
+    let mut draw = DebugDraw::default();
+    draw.set_visible("physics", true);
+    draw.set_visible("ai", false);
+    let mut harness = Harness::new(draw, |draw, ui| draw.ui(ui));
+    let names = harness.buttons().iter().map(|button| button.text.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["ai", "physics"]);
+
+    harness.click("physics");
+    harness.click("ai");
+    assert!(!harness.state().is_visible("physics"));
+    assert!(harness.state().is_visible("ai"));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "bevy_ecs")]
+
+use bevy_ecs::prelude::*;
+use guiedit::{
+    ecs::{EcsWorld, WorldTree},
+    testing::Harness,
+    Inspectable,
+};
+
+#[derive(Component, Inspectable)]
+struct Health(f32);
+
+#[derive(Component)]
+struct Unregistered;
+
+#[test]
+fn components_are_listed_with_their_registration() {
+    guiedit::ecs::bevy::register_component::<Health>();
+
+    let mut world = World::new();
+    let first = world.spawn((Health(100.), Unregistered)).id();
+    let second = world.spawn(Health(50.)).id();
+    assert_eq!(EcsWorld::all_entities(&world), vec![first, second]);
+
+    let mut components = world.entity_components(first);
+    components.sort_by_key(|component| component.registered);
+    assert_eq!(components.len(), 2);
+    assert!(components[0].type_name.ends_with("Unregistered"));
+    assert!(!components[0].registered);
+    assert!(components[1].type_name.ends_with("Health"));
+    assert!(components[1].registered);
+}
+
+#[test]
+fn world_tree_shows_entities() {
+    guiedit::ecs::bevy::register_component::<Health>();
+
+    let mut world = World::new();
+    let entity = world.spawn((Health(100.), Unregistered)).id();
+    world.spawn(Health(50.));
+    let mut harness = Harness::tree(WorldTree::new(&mut world));
+    harness.toggle("root");
+    let entity_name = format!("Entity {}v{}", entity.index(), entity.generation());
+    assert_eq!(harness.node_names(), ["root", &entity_name, "Entity 1v0"]);
+
+    harness.toggle(&entity_name);
+    let mut names = harness.node_names();
+    names[2..4].sort();
+    assert_eq!(
+        names,
+        ["root", &entity_name, "Health", "Unregistered", "Entity 1v0"]
+    );
+
+    // Selecting an entity inspects all of its registered components
+    harness.click(&entity_name);
+    let fields = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(fields.collect::<Vec<_>>(), ["_0"]);
+    harness.drag("_0", guiedit::egui::vec2(10., 0.));
+    drop(harness);
+    assert!(world.get::<Health>(entity).unwrap().0 > 100.);
+}
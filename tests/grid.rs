@@ -0,0 +1,99 @@
+use guiedit::{
+    egui::{self, epaint, pos2, vec2, Pos2, Rect},
+    grid::{self, Grid, ViewMapping},
+};
+
+/// A viewport of 400x200 points at (100, 100) showing the world from (-100, -50) to (100, 50).
+const MAPPING: ViewMapping = ViewMapping {
+    screen: Rect {
+        min: pos2(100., 100.),
+        max: pos2(500., 300.),
+    },
+    world: Rect {
+        min: pos2(-100., -50.),
+        max: pos2(100., 50.),
+    },
+};
+
+/// Paints the grid given over [`MAPPING`], and returns the vertical and horizontal lines painted.
+fn lines(grid: &Grid) -> (Vec<f32>, Vec<f32>) {
+    let ctx = egui::Context::default();
+    let output = ctx.run(Default::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| grid.paint(ui, &MAPPING));
+    });
+    let (mut vertical, mut horizontal) = (vec![], vec![]);
+    for shape in output.shapes {
+        if let epaint::Shape::LineSegment { points: [a, b], .. } = shape.1 {
+            if a.x == b.x {
+                vertical.push(a.x);
+            } else {
+                horizontal.push(a.y);
+            }
+        }
+    }
+    (vertical, horizontal)
+}
+
+#[test]
+fn positions_map_between_the_world_and_the_screen() {
+    assert_eq!(MAPPING.to_screen(Pos2::ZERO), pos2(300., 200.));
+    assert_eq!(MAPPING.to_world(pos2(500., 100.)), pos2(100., -50.));
+
+    // Flipped views show the world upside down
+    let flipped = ViewMapping {
+        world: Rect::from_center_size(Pos2::ZERO, vec2(200., -100.)),
+        ..MAPPING
+    };
+    assert_eq!(flipped.to_screen(pos2(0., 50.)), pos2(300., 100.));
+    assert_eq!(flipped.to_world(pos2(300., 300.)), pos2(0., -50.));
+}
+
+#[test]
+fn positions_are_snapped_to_the_grid_when_enabled() {
+    let mut grid = Grid {
+        spacing: 16.,
+        ..Default::default()
+    };
+    assert_eq!(grid.snap(pos2(7., -9.)), pos2(7., -9.));
+    grid.snap = true;
+    assert_eq!(grid.snap(pos2(7., -9.)), pos2(0., -16.));
+    assert_eq!(grid.snap(pos2(40., 25.)), pos2(48., 32.));
+}
+
+#[test]
+fn grid_lines_are_coarser_when_zoomed_out() {
+    // Two points per world unit
+    let mut grid = Grid {
+        show_grid: true,
+        spacing: 50.,
+        show_cursor_position: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        lines(&grid),
+        (vec![100., 200., 300., 400., 500.], vec![100., 200., 300.])
+    );
+
+    // Lines one point apart would be too close to each other
+    grid.spacing = 0.5;
+    let (vertical, horizontal) = lines(&grid);
+    assert_eq!(vertical.len(), 51);
+    assert_eq!(vertical[1] - vertical[0], 8.);
+    assert_eq!(horizontal.len(), 25);
+
+    grid.show_grid = false;
+    assert_eq!(lines(&grid), (vec![], vec![]));
+}
+
+#[test]
+fn ruler_ticks_are_round_numbers() {
+    assert_eq!(grid::ruler_step(2.), 50.);
+    assert_eq!(grid::ruler_step(1.), 100.);
+    assert_eq!(grid::ruler_step(0.5), 200.);
+    assert!((grid::ruler_step(100.) - 1.).abs() < 1e-6);
+    assert!((grid::ruler_step(1000.) - 0.1).abs() < 1e-6);
+
+    assert_eq!(grid::ruler_label(-150., 50.), "-150");
+    assert_eq!(grid::ruler_label(0.3000001, 0.1), "0.3");
+    assert_eq!(grid::ruler_label(0.25, 0.05), "0.25");
+}
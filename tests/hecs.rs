@@ -0,0 +1,114 @@
+#![cfg(feature = "hecs")]
+
+use guiedit::{
+    ecs::{EcsWorld, WorldTree},
+    inspectable::{self, Value},
+    testing::Harness,
+    tree::{self, ROOT_ID},
+    Inspectable, TreeNode,
+};
+use hecs::World;
+
+#[derive(Inspectable)]
+struct Health(f32);
+
+struct Unregistered;
+
+#[test]
+fn only_registered_components_are_listed() {
+    guiedit::ecs::hecs::register_component::<Health>();
+
+    let mut world = World::new();
+    let first = world.spawn((Health(100.), Unregistered));
+    let second = world.spawn((Health(50.),));
+    assert_eq!(EcsWorld::all_entities(&world), vec![first, second]);
+
+    let components = world.entity_components(first);
+    assert_eq!(components.len(), 1);
+    assert!(components[0].type_name.ends_with("Health"));
+    assert!(components[0].registered);
+    assert!(world.entity_components(world.reserve_entity()).is_empty());
+}
+
+#[test]
+fn world_tree_shows_entities() {
+    guiedit::ecs::hecs::register_component::<Health>();
+
+    let mut world = World::new();
+    let first = world.spawn((Health(100.), Unregistered));
+    let second = world.spawn((Health(50.),));
+    let names = [first, second].map(|entity| format!("Entity {:?}", entity));
+    let mut harness = Harness::tree(WorldTree::new(&mut world));
+    harness.toggle("root");
+    assert_eq!(harness.node_names(), ["root", &names[0], &names[1]]);
+
+    harness.toggle(&names[0]);
+    assert_eq!(
+        harness.node_names(),
+        ["root", &names[0], "Health", &names[1]]
+    );
+
+    // Components can be edited both from their own node and from their entity
+    harness.click("Health");
+    harness.drag("_0", guiedit::egui::vec2(10., 0.));
+    harness.click(&names[1]);
+    harness.drag("_0", guiedit::egui::vec2(10., 0.));
+    drop(harness);
+    assert!(world.get::<&Health>(first).unwrap().0 > 100.);
+    assert!(world.get::<&Health>(second).unwrap().0 > 50.);
+}
+
+/// Returns the name and ID of the node given and of all of its descendants, in the tree's order.
+fn visit(name: &str, id: u64, node: &mut dyn TreeNode, nodes: &mut Vec<(String, u64)>) {
+    nodes.push((name.to_owned(), id));
+    node.visit_children(id, &mut |name, id, child| visit(name, id, child, nodes));
+}
+
+#[test]
+fn entities_and_components_are_visited_with_the_ids_shown() {
+    guiedit::ecs::hecs::register_component::<Health>();
+
+    let mut world = World::new();
+    let entity = world.spawn((Health(100.), Unregistered));
+    let name = format!("Entity {:?}", entity);
+    let mut harness = Harness::tree(WorldTree::new(&mut world));
+    harness.toggle("root");
+    harness.toggle(&name);
+    let shown = harness
+        .nodes()
+        .iter()
+        .map(|node| (node.name.clone(), node.id))
+        .collect::<Vec<_>>();
+    drop(harness);
+
+    let mut world_tree = WorldTree::new(&mut world);
+    let mut visited = vec![];
+    visit("root", ROOT_ID, &mut world_tree, &mut visited);
+    assert_eq!(visited, shown);
+
+    // Components are found by their path, and edited through reflection from their node or from
+    // the node of their entity
+    let (entity_id, health_id) = (shown[1].1, shown[2].1);
+    assert_eq!(
+        tree::path_to(&mut world_tree, ROOT_ID, health_id),
+        Some(vec![name, "Health".to_owned()])
+    );
+    assert!(tree::find_node(
+        &mut world_tree,
+        ROOT_ID,
+        health_id,
+        &mut |node| assert!(inspectable::set_field_value(
+            node,
+            &["_0".to_owned()],
+            Value::Float(5.)
+        ))
+    ));
+    let mut values = vec![];
+    tree::find_node(&mut world_tree, ROOT_ID, entity_id, &mut |node| {
+        values = inspectable::field_values(node)
+    });
+    assert_eq!(
+        values,
+        [(vec!["Health".to_owned(), "_0".to_owned()], Value::Float(5.))]
+    );
+}
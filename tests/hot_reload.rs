@@ -0,0 +1,143 @@
+#![cfg(feature = "hot-reload")]
+
+use std::{
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use guiedit::hot_reload::{HotLibrary, Status, CHECK_INTERVAL};
+
+/// Returns the path of a library named `name` in a directory of its own.
+fn library_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("guiedit-test-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file_name = format!(
+        "{}{}{}",
+        std::env::consts::DLL_PREFIX,
+        name,
+        std::env::consts::DLL_SUFFIX
+    );
+    dir.join(file_name)
+}
+
+/// Builds a library from the source given, and sets its modification time to the one given.
+fn build(path: &Path, source: &str, modified: SystemTime) {
+    let source_path = path.with_extension("rs");
+    std::fs::write(&source_path, source).unwrap();
+    let status = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned()))
+        .args(["--crate-type", "cdylib", "-o"])
+        .arg(path)
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+fn version_source(version: u32) -> String {
+    format!(
+        "#[no_mangle] pub fn add_version(total: &mut u32) {{ *total += {} }}",
+        version
+    )
+}
+
+/// Calls `reload_if_changed` once enough time passed for the library to be checked again.
+fn check(library: &mut HotLibrary) -> bool {
+    thread::sleep(CHECK_INTERVAL + Duration::from_millis(10));
+    library.reload_if_changed()
+}
+
+#[test]
+fn missing_libraries_are_reported() {
+    let path = library_path("missing");
+    let mut library = HotLibrary::load(&path);
+    assert!(!library.is_loaded());
+    assert_eq!(library.generation(), 0);
+    assert!(matches!(library.status(), Status::Failed { .. }));
+
+    // SAFETY: Nothing is loaded, so no symbol is used
+    let error = unsafe { library.call("add_version", &mut 0u32) }.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    assert!(!check(&mut library));
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn missing_symbols_are_reported() {
+    let path = library_path("no_symbols");
+    build(&path, "pub fn unexported() {}", SystemTime::now());
+    let library = HotLibrary::load(&path);
+    assert_eq!(library.status(), &Status::Loaded { generation: 1 });
+
+    // SAFETY: The symbol does not exist, so it is never called
+    let error = unsafe { library.call("add_version", &mut 0u32) }.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    drop(library);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn libraries_are_reloaded_once_they_stop_changing() {
+    let path = library_path("versions");
+    let start = SystemTime::now() - Duration::from_secs(60);
+    build(&path, &version_source(1), start);
+    let mut library = HotLibrary::load(&path);
+    assert_eq!(library.status(), &Status::Loaded { generation: 1 });
+    let mut total = 0;
+    // SAFETY: Every version of the library exports `add_version` as a `fn(&mut u32)`
+    unsafe { library.call("add_version", &mut total) }.unwrap();
+    assert_eq!(total, 1);
+
+    // Libraries whose modification time did not change are not reloaded
+    assert!(!check(&mut library));
+    assert_eq!(library.generation(), 1);
+
+    // Changes are only loaded once the file is seen with the same modification time twice
+    build(&path, &version_source(2), start + Duration::from_secs(1));
+    assert!(!check(&mut library));
+    assert_eq!(library.status(), &Status::Pending);
+    assert!(check(&mut library));
+    assert_eq!(library.status(), &Status::Loaded { generation: 2 });
+    unsafe { library.call("add_version", &mut total) }.unwrap();
+    assert_eq!(total, 3);
+
+    // Broken builds keep the previous version running, and are not retried until they change
+    std::fs::write(&path, b"not a library").unwrap();
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(start + Duration::from_secs(2))
+        .unwrap();
+    assert!(!check(&mut library));
+    assert!(!check(&mut library));
+    assert!(matches!(library.status(), Status::Failed { .. }));
+    assert!(library.is_loaded());
+    assert!(!check(&mut library));
+    assert!(matches!(library.status(), Status::Failed { .. }));
+    unsafe { library.call("add_version", &mut total) }.unwrap();
+    assert_eq!(total, 5);
+
+    // The copies loaded are removed once unloaded
+    drop(library);
+    let copies = std::fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.starts_with(&format!("guiedit-{}-", std::process::id()))
+                && name.contains("versions")
+        })
+        .count();
+    assert_eq!(copies, 0);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
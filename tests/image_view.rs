@@ -0,0 +1,171 @@
+use guiedit::{
+    egui::{self, Color32, Event, Pos2, Vec2},
+    image_view::{self, Channels, ImageView},
+    testing::Harness,
+};
+
+#[test]
+fn channels_mask_pixels() {
+    let pixel = [10, 20, 30, 40];
+    assert_eq!(Channels::ALL.apply(pixel), pixel);
+    let red = Channels {
+        g: false,
+        b: false,
+        ..Channels::ALL
+    };
+    assert_eq!(red.apply(pixel), [10, 0, 0, 40]);
+    let opaque = Channels {
+        a: false,
+        ..Channels::ALL
+    };
+    assert_eq!(opaque.apply(pixel), [10, 20, 30, 255]);
+    let alpha = Channels {
+        r: false,
+        g: false,
+        b: false,
+        a: true,
+    };
+    assert_eq!(alpha.apply(pixel), [40, 40, 40, 255]);
+
+    let pixels = [1, 2, 3, 4, 5, 6, 7, 8];
+    let image = image_view::masked_image([2, 1], &pixels, alpha);
+    assert_eq!(image.pixels[1], egui::Color32::from_gray(8));
+    assert_eq!(image_view::pixel_at(&pixels, 2, [1, 0]), Some([5, 6, 7, 8]));
+    assert_eq!(image_view::pixel_at(&pixels, 2, [2, 0]), None);
+    assert_eq!(image_view::pixel_at(&pixels, 2, [0, 1]), None);
+}
+
+/// A viewer of a 100x50 image, keeping track of the pixel hovered.
+fn harness<'a>() -> Harness<'a, (ImageView, Option<[u32; 2]>, egui::Rect)> {
+    Harness::new(
+        (ImageView::default(), None, egui::Rect::NOTHING),
+        |(view, hovered, rect), ui| {
+            let texture = egui::TextureId::Managed(0);
+            let viewer = ui.scope(|ui| view.image_ui(ui, texture, [100, 50]));
+            (*hovered, *rect) = (viewer.inner, viewer.response.rect);
+        },
+    )
+}
+
+#[test]
+fn images_fit_and_report_the_pixel_hovered() {
+    let mut harness = harness();
+    let rect = harness.state().2;
+    let zoom = ImageView::fit_zoom(Vec2::new(100., 50.), rect.size());
+    assert!(rect.height() <= image_view::MAX_HEIGHT);
+
+    let pointer = rect.center() + (Vec2::new(10.5, 20.5) - Vec2::new(50., 25.)) * zoom;
+    harness.run_with_events(vec![Event::PointerMoved(pointer)]);
+    assert_eq!(harness.state().1, Some([10, 20]));
+    harness.run_with_events(vec![Event::PointerMoved(Pos2::new(1000., 1000.))]);
+    assert_eq!(harness.state().1, None);
+}
+
+#[test]
+fn dragging_pans_the_image() {
+    let mut harness = harness();
+    let rect = harness.state().2;
+    let zoom = ImageView::fit_zoom(Vec2::new(100., 50.), rect.size());
+    let from = rect.center();
+    let to = from + Vec2::new(10. * zoom, 0.);
+    harness.run_with_events(vec![Event::PointerMoved(from)]);
+    harness.run_with_events(vec![pointer_button(from, true)]);
+    harness.run_with_events(vec![Event::PointerMoved(to)]);
+    harness.run_with_events(vec![pointer_button(to, false)]);
+
+    let view = harness.state().0;
+    assert_eq!(view.zoom, Some(zoom));
+    assert!((view.pan.x + 10.).abs() < 0.01, "{:?}", view.pan);
+    // The center of the image is now under the pointer
+    harness.run_with_events(vec![Event::PointerMoved(to)]);
+    assert_eq!(harness.state().1, Some([50, 25]));
+}
+
+/// Runs a frame at the time given showing a copy of the 2×1 image given, returning the texture
+/// shown and the pixels uploaded to each texture during the frame.
+fn copy_frame(
+    ctx: &egui::Context,
+    time: f64,
+    source: u64,
+    pixels: &[[u8; 4]; 2],
+    channels: Channels,
+    reads: &mut usize,
+) -> (egui::TextureId, Vec<(egui::TextureId, Vec<Color32>)>) {
+    let input = egui::RawInput {
+        time: Some(time),
+        ..Default::default()
+    };
+    let mut texture = None;
+    let output = ctx.run(input, |ctx| {
+        let id = egui::Id::new("copy");
+        texture = Some(image_view::image_copy(
+            ctx,
+            id,
+            source,
+            [2, 1],
+            channels,
+            || {
+                *reads += 1;
+                Some(pixels.concat())
+            },
+        ));
+    });
+    let uploads = output.textures_delta.set.into_iter();
+    let uploads = uploads.filter_map(|(id, delta)| match delta.image {
+        egui::ImageData::Color(image) => Some((id, image.pixels)),
+        // The font atlas
+        egui::ImageData::Font(_) => None,
+    });
+    (texture.unwrap(), uploads.collect())
+}
+
+#[test]
+fn image_copies_show_their_source_and_follow_its_changes() {
+    let ctx = egui::Context::default();
+    let mut reads = 0;
+    let (red, green) = ([255, 0, 0, 255], [0, 255, 0, 128]);
+    let color = |[r, g, b, a]: [u8; 4]| Color32::from_rgba_unmultiplied(r, g, b, a);
+    let all = Channels::ALL;
+
+    let (texture, uploads) = copy_frame(&ctx, 0., 1, &[red, green], all, &mut reads);
+    assert!(matches!(texture, egui::TextureId::Managed(_)));
+    assert_eq!(uploads, [(texture, vec![color(red), color(green)])]);
+
+    // Copies are only read again once they are due for a refresh, and only uploaded again if their
+    // pixels changed
+    let (shown, uploads) = copy_frame(&ctx, 0.1, 1, &[green, red], all, &mut reads);
+    assert_eq!((shown, uploads.len(), reads), (texture, 0, 1));
+    let time = 0.1 + image_view::COPY_REFRESH_INTERVAL;
+    let (shown, uploads) = copy_frame(&ctx, time, 1, &[green, red], all, &mut reads);
+    assert_eq!(shown, texture);
+    assert_eq!(uploads, [(texture, vec![color(green), color(red)])]);
+    let time = time + image_view::COPY_REFRESH_INTERVAL;
+    let (_, uploads) = copy_frame(&ctx, time, 1, &[green, red], all, &mut reads);
+    assert_eq!((uploads.len(), reads), (0, 3));
+
+    // Changes to the channels shown or to the source are copied at once
+    let alpha = Channels {
+        r: false,
+        g: false,
+        b: false,
+        a: true,
+    };
+    let (shown, uploads) = copy_frame(&ctx, time, 1, &[green, red], alpha, &mut reads);
+    assert_eq!(shown, texture);
+    assert_eq!(
+        uploads,
+        [(texture, vec![Color32::from_gray(128), Color32::WHITE])]
+    );
+    let (other, uploads) = copy_frame(&ctx, time, 2, &[red, green], all, &mut reads);
+    assert_ne!(other, texture);
+    assert_eq!(uploads, [(other, vec![color(red), color(green)])]);
+}
+
+fn pointer_button(pos: Pos2, pressed: bool) -> Event {
+    Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    }
+}
@@ -0,0 +1,71 @@
+use guiedit::{
+    egui,
+    layout::{Dock, Layout, Panel},
+    testing::Harness,
+};
+
+/// Runs a harness showing the layout given, keeping track of the panels whose contents are shown.
+fn harness<'a>(layout: Layout) -> Harness<'a, (Layout, Vec<Panel>)> {
+    Harness::with_context((layout, vec![]), |(layout, shown), ctx| {
+        shown.clear();
+        layout.show(ctx, |panel, ui| {
+            shown.push(panel);
+            ui.label(panel.title());
+        });
+        egui::CentralPanel::default().show(ctx, |_| {});
+    })
+}
+
+#[test]
+fn default_layout_shows_inspector_and_tree() {
+    let harness = harness(Layout::default());
+    assert_eq!(harness.state().1, [Panel::Inspector, Panel::Tree]);
+}
+
+#[test]
+fn hidden_and_collapsed_panels_have_no_contents() {
+    let mut harness = harness(Layout::default());
+    harness.state_mut().0.panel_mut(Panel::Performance).visible = true;
+    harness.state_mut().0.panel_mut(Panel::Tree).collapsed = true;
+    harness.state_mut().0.panel_mut(Panel::Inspector).visible = false;
+    harness.run();
+    assert_eq!(harness.state().1, [Panel::Performance]);
+    assert!(harness.state().0.is_shown(Panel::Performance));
+    assert!(!harness.state().0.is_shown(Panel::Tree));
+}
+
+#[test]
+fn panels_can_be_docked_anywhere() {
+    let mut harness = harness(Layout::default());
+    for dock in Dock::ALL {
+        for panel in Panel::ALL {
+            let layout = harness.state_mut().0.panel_mut(panel);
+            layout.dock = dock;
+            layout.visible = true;
+        }
+        harness.run();
+        assert_eq!(harness.state().1, Panel::ALL);
+
+        for panel in Panel::ALL {
+            harness.state_mut().0.panel_mut(panel).collapsed = true;
+        }
+        harness.run();
+        // Floating panels are collapsed through their window instead
+        if dock == Dock::Floating {
+            assert_eq!(harness.state().1, Panel::ALL);
+        } else {
+            assert!(harness.state().1.is_empty());
+        }
+        for panel in Panel::ALL {
+            harness.state_mut().0.panel_mut(panel).collapsed = false;
+        }
+    }
+}
+
+#[test]
+fn missing_panels_are_restored() {
+    let mut layout = Layout { panels: vec![] };
+    assert_eq!(layout.panel_mut(Panel::Tree).dock, Dock::Bottom);
+    let harness = harness(layout);
+    assert_eq!(harness.state().0.panels.len(), Panel::ALL.len());
+}
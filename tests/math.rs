@@ -0,0 +1,50 @@
+use guiedit::math::{euler_to_quat, quat_to_euler, Trs};
+
+fn assert_close(a: &[f64], b: &[f64]) {
+    assert!(
+        a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9),
+        "{:?} != {:?}",
+        a,
+        b
+    );
+}
+
+#[test]
+fn euler_round_trip() {
+    let euler = [0.3, -0.7, 2.1];
+    assert_close(&quat_to_euler(euler_to_quat(euler)), &euler);
+}
+
+#[test]
+fn euler_single_axis() {
+    let half = std::f64::consts::FRAC_PI_4.sin();
+    assert_close(
+        &euler_to_quat([0., 0., std::f64::consts::FRAC_PI_2]),
+        &[0., 0., half, half],
+    );
+}
+
+#[test]
+fn trs_round_trip() {
+    let trs = Trs {
+        translation: [1., -2., 3.],
+        rotation: euler_to_quat([0.4, 0.2, -1.3]),
+        scale: [-2., 0.5, 3.],
+    };
+    let decomposed = Trs::from_matrix(trs.to_matrix()).unwrap();
+    assert_close(&decomposed.translation, &trs.translation);
+    assert_close(&decomposed.rotation, &trs.rotation);
+    assert_close(&decomposed.scale, &trs.scale);
+}
+
+#[test]
+fn trs_rejects_projections() {
+    let mut matrix = Trs {
+        translation: [0.; 3],
+        rotation: [0., 0., 0., 1.],
+        scale: [1.; 3],
+    }
+    .to_matrix();
+    matrix[11] = -1.;
+    assert!(Trs::from_matrix(matrix).is_none());
+}
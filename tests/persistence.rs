@@ -0,0 +1,89 @@
+#![cfg(feature = "persistence")]
+
+use guiedit::{
+    layout::{Dock, Panel},
+    persistence::EditorState,
+    tree::{self, ROOT_ID},
+    Inspectable, TreeNode,
+};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Player {
+    health: f32,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Game {
+    players: Vec<Player>,
+    boss: Player,
+}
+
+fn game() -> Game {
+    Game {
+        players: vec![Player::default(), Player::default()],
+        boss: Player::default(),
+    }
+}
+
+/// Returns the ID of the node with the path given by searching every node of the tree.
+fn search_id(node: &mut dyn TreeNode, id: u64, path: &[&str]) -> Option<u64> {
+    let Some((name, path)) = path.split_first() else {
+        return Some(id);
+    };
+    let mut found = None;
+    node.visit_children(id, &mut |child_name, child_id, child| {
+        if child_name == *name {
+            found = found.or_else(|| search_id(child, child_id, path));
+        }
+    });
+    found
+}
+
+#[test]
+fn paths_identify_nodes() {
+    let mut game = game();
+    let second = search_id(&mut game, ROOT_ID, &["players", "1"]).unwrap();
+
+    let path = tree::path_to(&mut game, ROOT_ID, second).unwrap();
+    assert_eq!(path, ["players", "1"]);
+    assert_eq!(tree::id_at_path(&mut game, ROOT_ID, &path), Some(second));
+
+    assert_eq!(tree::path_to(&mut game, ROOT_ID, ROOT_ID), Some(vec![]));
+    assert_eq!(tree::path_to(&mut game, ROOT_ID, 42), None);
+    let missing = ["players".to_owned(), "2".to_owned()];
+    assert_eq!(tree::id_at_path(&mut game, ROOT_ID, &missing), None);
+}
+
+#[test]
+fn state_survives_saving_and_loading() {
+    let mut game = game();
+    let boss = search_id(&mut game, ROOT_ID, &["boss"]).unwrap();
+
+    let ctx = guiedit::egui::Context::default();
+    let id = guiedit::egui::Id::new(boss);
+    let mut collapsing =
+        guiedit::egui::collapsing_header::CollapsingState::load_with_default_open(&ctx, id, false);
+    collapsing.set_open(true);
+    collapsing.store(&ctx);
+
+    let mut state = EditorState {
+        memory: ctx.memory().clone(),
+        ..Default::default()
+    };
+    state.layout.panel_mut(Panel::Tree).dock = Dock::Floating;
+    state.set_selected(&mut game, Some(boss));
+
+    let path = std::env::temp_dir().join(format!("guiedit-test-{}/state.ron", std::process::id()));
+    state.save(&path).unwrap();
+    let loaded = EditorState::load(&path).unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    assert_eq!(loaded.selected_path, Some(vec!["boss".to_owned()]));
+    assert_eq!(loaded.layout, state.layout);
+    assert_eq!(loaded.selected(&mut game), Some(boss));
+
+    let ctx = guiedit::egui::Context::default();
+    *ctx.memory() = loaded.memory;
+    let collapsing = guiedit::egui::collapsing_header::CollapsingState::load(&ctx, id).unwrap();
+    assert!(collapsing.is_open());
+}
@@ -0,0 +1,69 @@
+use guiedit::{
+    pins::Pins,
+    testing::Harness,
+    tree::{self, ROOT_ID},
+    Inspectable, TreeNode,
+};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Paddle {
+    y: f32,
+    ai: bool,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Pong {
+    paddles: Vec<Paddle>,
+}
+
+fn pong() -> Pong {
+    Pong {
+        paddles: vec![Paddle::default(), Paddle::default()],
+    }
+}
+
+fn paddle_id(pong: &mut Pong, idx: usize) -> u64 {
+    let path = ["paddles".to_owned(), idx.to_string()];
+    tree::id_at_path(pong, ROOT_ID, &path).unwrap()
+}
+
+#[test]
+fn pins_are_tracked_by_path() {
+    let mut pong = pong();
+    let mut pins = Pins::default();
+    let first = paddle_id(&mut pong, 0);
+    pins.pin(&mut pong, first);
+    pins.pin(&mut pong, first);
+    assert_eq!(pins.len(), 1);
+    assert!(pins.is_pinned(&mut pong, first));
+    assert_eq!(pins.iter().next().unwrap().title(), "paddles/0");
+
+    let second = paddle_id(&mut pong, 1);
+    pins.pin(&mut pong, second);
+    pong.paddles.pop();
+    assert_eq!(pins.iter().nth(1).unwrap().resolve(&mut pong), None);
+
+    pins.unpin(&mut pong, first);
+    assert!(!pins.is_pinned(&mut pong, first));
+    assert_eq!(pins.len(), 1);
+}
+
+#[test]
+fn every_pinned_node_gets_an_inspector_window() {
+    let mut pong = pong();
+    let mut pins = Pins::default();
+    for idx in 0..2 {
+        let id = paddle_id(&mut pong, idx);
+        pins.pin(&mut pong, id);
+    }
+
+    let mut harness = Harness::with_context((pong, pins), |(pong, pins), ctx| {
+        pins.show(ctx, pong);
+    });
+    let names = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["y", "ai", "y", "ai"]);
+
+    harness.click("ai");
+    let ai = harness.state().0.paddles.iter().map(|paddle| paddle.ai);
+    assert_eq!(ai.collect::<Vec<_>>(), [true, false]);
+}
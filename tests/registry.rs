@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use guiedit::Inspectable;
+
+/// A type that cannot implement Inspectable, e.g. because it comes from another crate.
+struct Foreign {
+    value: u32,
+}
+
+static INSPECTED: AtomicU32 = AtomicU32::new(0);
+
+fn run_ui(add_contents: impl FnOnce(&mut egui::Ui)) {
+    let ctx = egui::Context::default();
+    let mut add_contents = Some(add_contents);
+    let _ = ctx.run(egui::RawInput::default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| (add_contents.take().unwrap())(ui));
+    });
+}
+
+#[test]
+fn derived_fields_use_registered_inspectors() {
+    #[derive(Inspectable)]
+    struct State {
+        foreign: Foreign,
+        number: i32,
+    }
+
+    guiedit::registry::register::<Foreign>(|foreign, _ui| {
+        INSPECTED.store(foreign.value, Ordering::SeqCst);
+        foreign.value += 1;
+    });
+    assert!(guiedit::registry::is_registered(std::any::TypeId::of::<
+        Foreign,
+    >()));
+
+    let mut state = State {
+        foreign: Foreign { value: 41 },
+        number: 0,
+    };
+    run_ui(|ui| state.inspect_ui(ui));
+
+    assert_eq!(INSPECTED.load(Ordering::SeqCst), 41);
+    assert_eq!(state.foreign.value, 42);
+    assert_eq!(state.number, 0);
+
+    assert!(guiedit::registry::unregister::<Foreign>());
+    run_ui(|ui| state.inspect_ui(ui));
+    assert_eq!(state.foreign.value, 42);
+}
+
+#[test]
+fn inspect_without_inspector() {
+    struct Unregistered;
+
+    run_ui(|ui| assert!(!guiedit::registry::inspect(&mut Unregistered, ui)));
+}
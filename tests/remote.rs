@@ -0,0 +1,175 @@
+#![cfg(feature = "remote")]
+
+use std::{
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+use guiedit::{
+    inspectable::Value,
+    remote::{Client, Server},
+    tree::ROOT_ID,
+    Inspectable, TreeNode,
+};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Player {
+    name: String,
+    health: f32,
+    alive: bool,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Game {
+    score: u32,
+    players: Vec<Player>,
+    #[tree_node(skip)]
+    hidden: Player,
+}
+
+/// Runs the client function given on another thread while serving the root given over loopback.
+fn serve(root: &mut impl TreeNode, client: impl FnOnce(Client) + Send + 'static) {
+    let mut server = Server::bind("127.0.0.1:0").unwrap();
+    let address = server.local_addr().unwrap();
+
+    let client = std::thread::spawn(move || client(Client::connect(address).unwrap()));
+    while !client.is_finished() {
+        server.poll(root).unwrap();
+        std::thread::yield_now();
+    }
+    if let Err(panic) = client.join() {
+        std::panic::resume_unwind(panic);
+    }
+}
+
+fn game() -> Game {
+    Game {
+        score: 10,
+        players: vec![
+            Player {
+                name: "Alice".to_owned(),
+                health: 100.,
+                alive: true,
+            },
+            Player::default(),
+        ],
+        hidden: Player::default(),
+    }
+}
+
+#[test]
+fn tree_lists_visible_nodes() {
+    serve(&mut game(), |mut client| {
+        let root = client.tree().unwrap();
+        assert_eq!(root.id, ROOT_ID);
+        let children = root.children.iter().map(|node| node.name.as_str());
+        assert_eq!(children.collect::<Vec<_>>(), ["players"]);
+        assert_eq!(root.children[0].children.len(), 2);
+    });
+}
+
+#[test]
+fn fields_expose_values() {
+    serve(&mut game(), |mut client| {
+        let root = client.tree().unwrap();
+        let alice = root.children[0].children[0].id;
+        let fields = client.fields(alice).unwrap();
+        let values = fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.clone().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [
+                ("name", Value::String("Alice".to_owned())),
+                ("health", Value::Float(100.)),
+                ("alive", Value::Bool(true)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn set_edits_values() {
+    let mut game = game();
+    serve(&mut game, |mut client| {
+        client
+            .set(ROOT_ID, vec!["score".to_owned()], Value::UInt(42))
+            .unwrap();
+        let path = vec!["players".to_owned(), "1".to_owned(), "name".to_owned()];
+        client
+            .set(ROOT_ID, path, Value::String("Bob".to_owned()))
+            .unwrap();
+
+        // Invalid edits are reported without dropping the connection
+        assert!(client
+            .set(ROOT_ID, vec!["score".to_owned()], Value::Bool(true))
+            .is_err());
+        assert!(client
+            .set(ROOT_ID, vec!["nothing".to_owned()], Value::Int(0))
+            .is_err());
+        assert!(client.fields(1234).is_err());
+    });
+    assert_eq!(game.score, 42);
+    assert_eq!(game.players[1].name, "Bob");
+}
+
+/// Serves the root given to a client connected through a raw socket on another thread.
+fn serve_raw(root: &mut impl TreeNode, client: impl FnOnce(TcpStream) + Send + 'static) {
+    let mut server = Server::bind("127.0.0.1:0").unwrap();
+    let address = server.local_addr().unwrap();
+
+    let client = std::thread::spawn(move || client(TcpStream::connect(address).unwrap()));
+    while !client.is_finished() {
+        server.poll(root).unwrap();
+        std::thread::yield_now();
+    }
+    if let Err(panic) = client.join() {
+        std::panic::resume_unwind(panic);
+    }
+}
+
+#[test]
+fn clients_sending_endless_requests_are_dropped() {
+    serve_raw(&mut game(), |mut stream| {
+        let request = vec![b' '; 2 << 20];
+        // The server may close the connection before the whole request is sent
+        let _ = stream.write_all(&request);
+        let mut response = vec![];
+        let _ = stream.read_to_end(&mut response);
+        assert!(response.is_empty());
+    });
+}
+
+#[test]
+fn clients_not_reading_responses_are_dropped() {
+    let mut game = game();
+    // Each response lists every player, so they quickly fill the buffers of the connection
+    game.players = (0..10_000).map(|_| Player::default()).collect();
+    serve_raw(&mut game, |mut stream| {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while stream.write_all(b"{\"type\":\"tree\"}\n").is_ok() {
+            assert!(Instant::now() < deadline, "the client was never dropped");
+        }
+    });
+}
+
+#[test]
+fn requests_time_out_while_the_server_is_not_polled() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = Client::connect(listener.local_addr().unwrap()).unwrap();
+    let _connection = listener.accept().unwrap();
+    client
+        .set_timeout(Some(Duration::from_millis(100)))
+        .unwrap();
+
+    let start = Instant::now();
+    assert_eq!(client.tree().unwrap_err().kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(!client.is_connected());
+    assert_eq!(
+        client.tree().unwrap_err().kind(),
+        io::ErrorKind::NotConnected
+    );
+}
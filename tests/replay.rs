@@ -0,0 +1,211 @@
+use guiedit::replay::{InputRecording, InputReplay, RecordedEvent};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Event {
+    Key(char),
+    Click(i32, i32),
+    /// An event consumed by the editor, which is not recorded.
+    Noop,
+}
+
+impl RecordedEvent for Event {
+    fn to_line(&self) -> Option<String> {
+        match self {
+            Event::Key(key) => Some(format!("Key {}", key)),
+            Event::Click(x, y) => Some(format!("Click {} {}", x, y)),
+            Event::Noop => None,
+        }
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split(' ').collect::<Vec<_>>();
+        match fields[..] {
+            ["Key", key] => key.parse().ok().map(Event::Key),
+            ["Click", x, y] => Some(Event::Click(x.parse().ok()?, y.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the events replayed in each of the frames given.
+fn replay_frames(replay: &mut InputReplay<Event>, frames: usize) -> Vec<Vec<Event>> {
+    (0..frames)
+        .map(|_| {
+            let events = std::iter::from_fn(|| replay.next_replayed()).collect();
+            replay.end_frame();
+            events
+        })
+        .collect()
+}
+
+#[test]
+fn recordings_round_trip_through_text() {
+    let recording = InputRecording {
+        events: vec![
+            (0, Event::Key('a')),
+            (0, Event::Click(-4, 12)),
+            (3, Event::Noop),
+            (5, Event::Key('b')),
+        ],
+        frames: 6,
+    };
+    let mut text = vec![];
+    recording.write(&mut text).unwrap();
+    let text = String::from_utf8(text).unwrap();
+    assert_eq!(
+        text,
+        "guiedit input recording 1\nframes 6\n0 Key a\n0 Click -4 12\n5 Key b\n"
+    );
+
+    let read = InputRecording::<Event>::read(text.as_bytes()).unwrap();
+    assert_eq!(read.frames, 6);
+    assert_eq!(
+        read.events,
+        [
+            (0, Event::Key('a')),
+            (0, Event::Click(-4, 12)),
+            (5, Event::Key('b'))
+        ]
+    );
+}
+
+#[test]
+fn invalid_recordings_are_reported_with_their_line() {
+    let read = |text: &str| {
+        InputRecording::<Event>::read(text.as_bytes())
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(read("frames 2\n"), "line 1: not an input recording");
+    assert_eq!(
+        read("guiedit input recording 1\n0 Key a\n"),
+        "line 2: expected the number of frames"
+    );
+    assert_eq!(
+        read("guiedit input recording 1\nframes 2\n0 Key a\n1 Jump\n"),
+        "line 4: invalid event"
+    );
+    assert_eq!(
+        read("guiedit input recording 1\nframes 2\n1 Key a\n0 Key b\n"),
+        "line 4: frame out of order"
+    );
+    assert_eq!(
+        read("guiedit input recording 1\nframes 2\n2 Key a\n"),
+        "line 3: frame out of order"
+    );
+}
+
+#[test]
+fn recorded_events_are_replayed_in_their_frame() {
+    let path = std::env::temp_dir().join(format!("guiedit-replay-{}.txt", std::process::id()));
+    let mut replay = InputReplay::default();
+    replay.start_recording(&path);
+    assert!(replay.is_recording());
+    replay.record(&Event::Key('a'));
+    replay.record(&Event::Noop);
+    replay.end_frame();
+    replay.end_frame();
+    replay.record(&Event::Click(1, 2));
+    replay.record(&Event::Key('b'));
+    replay.end_frame();
+    assert_eq!(replay.stop().unwrap().unwrap(), path);
+    assert!(!replay.is_recording());
+
+    replay.start_replay(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(replay.is_replaying());
+    assert_eq!(replay.recording().unwrap().frames, 3);
+    assert_eq!(
+        replay_frames(&mut replay, 4),
+        [
+            vec![Event::Key('a')],
+            vec![],
+            vec![Event::Click(1, 2), Event::Key('b')],
+            vec![],
+        ]
+    );
+    // Replays pause once they reach their end
+    assert!(replay.is_paused());
+    assert_eq!(replay.frame(), 3);
+
+    // Events are not recorded while replaying
+    replay.record(&Event::Key('c'));
+    assert_eq!(replay.recording().unwrap().events.len(), 3);
+    assert!(replay.stop().is_none());
+}
+
+#[test]
+fn recordings_stopped_mid_frame_keep_the_events_of_that_frame() {
+    let path = std::env::temp_dir().join(format!("guiedit-replay-mid-{}.txt", std::process::id()));
+    let mut replay = InputReplay::default();
+    replay.start_recording(&path);
+    replay.record(&Event::Key('a'));
+    replay.end_frame();
+    replay.record(&Event::Key('b'));
+    assert_eq!(replay.stop().unwrap().unwrap(), path);
+
+    let recording = InputRecording::<Event>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(recording.frames, 2);
+    assert_eq!(
+        recording.events,
+        [(0, Event::Key('a')), (1, Event::Key('b'))]
+    );
+
+    // Frames without events are only counted once they end
+    replay.start_recording(&path);
+    replay.record(&Event::Key('a'));
+    replay.end_frame();
+    replay.stop().unwrap().unwrap();
+    let recording = InputRecording::<Event>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(recording.frames, 1);
+}
+
+#[test]
+fn seeking_replays_frames_until_the_frame_sought() {
+    let mut replay = InputReplay::default();
+    replay.replay(InputRecording {
+        events: vec![
+            (0, Event::Key('a')),
+            (2, Event::Key('b')),
+            (4, Event::Key('c')),
+        ],
+        frames: 6,
+    });
+
+    // Forwards, frames are replayed from the current one and the replay pauses once there
+    replay.seek(3);
+    assert_eq!(
+        replay_frames(&mut replay, 4),
+        [vec![Event::Key('a')], vec![], vec![Event::Key('b')], vec![]]
+    );
+    assert_eq!(replay.frame(), 3);
+    assert!(replay.is_paused());
+    assert!(!replay.take_restart());
+
+    // Backwards, the replay starts over, which the application is told about once
+    replay.seek(1);
+    assert_eq!(replay.frame(), 0);
+    assert!(replay.take_restart());
+    assert!(!replay.take_restart());
+    assert_eq!(
+        replay_frames(&mut replay, 2),
+        [vec![Event::Key('a')], vec![]]
+    );
+    assert_eq!(replay.frame(), 1);
+
+    replay.set_paused(false);
+    assert_eq!(
+        replay_frames(&mut replay, 6),
+        [
+            vec![],
+            vec![Event::Key('b')],
+            vec![],
+            vec![Event::Key('c')],
+            vec![],
+            vec![]
+        ]
+    );
+    assert_eq!(replay.frame(), 6);
+}
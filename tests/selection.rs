@@ -0,0 +1,150 @@
+use guiedit::{
+    egui::{vec2, Modifiers},
+    inspectable::{self, Value},
+    testing::Harness,
+    Inspectable, TreeNode,
+};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Ball {
+    speed: f32,
+    bouncy: bool,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Paddle {
+    height: f32,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Pong {
+    balls: Vec<Ball>,
+    paddle: Paddle,
+}
+
+fn pong() -> Harness<'static, Pong> {
+    let mut harness = Harness::tree(Pong {
+        balls: vec![
+            Ball {
+                speed: 1.,
+                bouncy: true,
+            },
+            Ball {
+                speed: 1.,
+                bouncy: false,
+            },
+            Ball {
+                speed: 3.,
+                bouncy: false,
+            },
+        ],
+        paddle: Paddle { height: 2. },
+    });
+    harness.toggle("root");
+    harness.toggle("balls");
+    harness
+}
+
+fn selected<'h>(harness: &'h Harness<Pong>) -> Vec<&'h str> {
+    let selected = harness.nodes().iter().filter(|node| node.selected);
+    selected.map(|node| node.name.as_str()).collect()
+}
+
+const CTRL: Modifiers = Modifiers::COMMAND;
+const SHIFT: Modifiers = Modifiers::SHIFT;
+
+#[test]
+fn nodes_are_selected_with_ctrl_and_shift() {
+    let mut harness = pong();
+    assert_eq!(
+        harness.node_names(),
+        ["root", "balls", "0", "1", "2", "paddle"]
+    );
+
+    harness.click("0");
+    harness.click_with_modifiers("2", CTRL);
+    assert_eq!(selected(&harness), ["0", "2"]);
+    harness.click_with_modifiers("paddle", CTRL);
+    harness.click_with_modifiers("0", CTRL);
+    assert_eq!(selected(&harness), ["2", "paddle"]);
+
+    // Ranges start from the last node clicked without Shift
+    harness.click("1");
+    harness.click_with_modifiers("paddle", SHIFT);
+    assert_eq!(selected(&harness), ["1", "2", "paddle"]);
+    harness.click_with_modifiers("0", SHIFT);
+    assert_eq!(selected(&harness), ["0", "1"]);
+
+    // Clicking without modifiers selects a single node again
+    harness.click("2");
+    assert_eq!(selected(&harness), ["2"]);
+}
+
+#[test]
+fn nodes_of_the_same_type_are_edited_together() {
+    let mut harness = pong();
+    harness.click("0");
+    harness.click_with_modifiers("1", CTRL);
+    harness.click_with_modifiers("2", CTRL);
+    // The last node selected is shown
+    let names = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["speed", "bouncy"]);
+
+    harness.click("bouncy");
+    let balls = &harness.state().balls;
+    assert!(balls.iter().all(|ball| ball.bouncy));
+    // Fields that were not edited keep their own values
+    assert_eq!(
+        balls.iter().map(|ball| ball.speed).collect::<Vec<_>>(),
+        [1., 1., 3.]
+    );
+
+    harness.drag("speed", vec2(10., 0.));
+    let speeds = harness.state().balls.iter().map(|ball| ball.speed);
+    assert!(
+        speeds.clone().all(|speed| speed > 3.),
+        "{:?}",
+        speeds.collect::<Vec<_>>()
+    );
+
+    // Nodes of different types are not edited together
+    harness.click_with_modifiers("paddle", CTRL);
+    let names = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["height"]);
+    harness.drag("height", vec2(10., 0.));
+    assert!(harness.state().paddle.height > 2.);
+}
+
+#[test]
+fn field_values_are_listed_and_set_by_path() {
+    let mut pong = Pong {
+        balls: vec![Ball::default()],
+        paddle: Paddle { height: 2. },
+    };
+    let path = |path: &[&str]| path.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        inspectable::field_values(&mut pong),
+        [
+            (path(&["balls", "0", "speed"]), Value::Float(0.)),
+            (path(&["balls", "0", "bouncy"]), Value::Bool(false)),
+            (path(&["paddle", "height"]), Value::Float(2.)),
+        ]
+    );
+
+    assert!(inspectable::set_field_value(
+        &mut pong,
+        &path(&["balls", "0", "bouncy"]),
+        Value::Bool(true)
+    ));
+    assert!(!inspectable::set_field_value(
+        &mut pong,
+        &path(&["paddle", "width"]),
+        Value::Float(1.)
+    ));
+    assert!(!inspectable::set_field_value(
+        &mut pong,
+        &path(&["paddle", "height"]),
+        Value::Bool(true)
+    ));
+    assert!(pong.balls[0].bouncy);
+}
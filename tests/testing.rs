@@ -0,0 +1,157 @@
+use guiedit::{egui::vec2, inspectable::ClampedValue, testing::Harness, Inspectable, TreeNode};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Player {
+    name: String,
+    health: i32,
+    alive: bool,
+    lives: Option<u32>,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Game {
+    score: u32,
+    players: Vec<Player>,
+    #[tree_node(skip)]
+    hidden: Player,
+}
+
+fn game() -> Game {
+    Game {
+        score: 10,
+        players: vec![
+            Player {
+                name: "Alice".to_owned(),
+                health: 100,
+                alive: true,
+                lives: None,
+            },
+            Player::default(),
+        ],
+        hidden: Player::default(),
+    }
+}
+
+#[test]
+fn inspector_lists_fields_in_order() {
+    let harness = Harness::inspector(Player::default());
+    let names = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(
+        names.collect::<Vec<_>>(),
+        ["name", "health", "alive", "lives"]
+    );
+}
+
+#[test]
+fn clicks_edit_values() {
+    let mut harness = Harness::inspector(Player::default());
+    harness.click("alive");
+    assert!(harness.state().alive);
+    harness.click("alive");
+    assert!(!harness.state().alive);
+
+    harness.click("lives");
+    assert_eq!(harness.state().lives, Some(0));
+}
+
+#[test]
+fn drags_edit_values() {
+    let mut harness = Harness::inspector(Player::default());
+    // Integers change by a quarter of the distance dragged
+    harness.drag("health", vec2(100., 0.));
+    assert_eq!(harness.state().health, 25);
+    harness.drag("health", vec2(-200., 0.));
+    assert_eq!(harness.state().health, -25);
+}
+
+#[test]
+fn drags_respect_clamped_values() {
+    let mut value = 5.;
+    let mut harness = Harness::new(&mut value, |value, ui| {
+        ClampedValue {
+            range: 0.0..=10.,
+            value: *value,
+            speed: 1.,
+        }
+        .inspect_ui_outside("value", ui)
+    });
+    harness.drag("value", vec2(100., 0.));
+    drop(harness);
+    assert_eq!(value, 10.);
+}
+
+#[test]
+fn tree_lists_visible_nodes() {
+    let mut harness = Harness::tree(game());
+    assert_eq!(harness.node_names(), ["root"]);
+    assert_eq!(harness.nodes()[0].expanded, Some(false));
+
+    harness.toggle("root");
+    assert_eq!(harness.node_names(), ["root", "players"]);
+    harness.toggle("players");
+    assert_eq!(harness.node_names(), ["root", "players", "0", "1"]);
+    let depths = harness.nodes().iter().map(|node| node.depth);
+    assert_eq!(depths.collect::<Vec<_>>(), [0, 1, 2, 2]);
+
+    harness.toggle("players");
+    assert_eq!(harness.node_names(), ["root", "players"]);
+}
+
+#[test]
+fn selecting_nodes_shows_their_inspector() {
+    let mut harness = Harness::tree(game());
+    assert!(harness.selected_node().is_none());
+    assert!(harness.fields().is_empty());
+
+    harness.click("root");
+    assert_eq!(harness.selected_node().unwrap().name, "root");
+    let names = harness.fields().iter().map(|field| field.name.as_str());
+    assert_eq!(
+        names.collect::<Vec<_>>(),
+        [
+            "score", "players", "name", "health", "alive", "lives", "name", "health", "alive",
+            "lives"
+        ]
+    );
+
+    harness.toggle("root");
+    harness.toggle("players");
+    harness.click("0");
+    assert_eq!(harness.selected_node().unwrap().name, "0");
+    harness.click("alive");
+    assert!(!harness.state().players[0].alive);
+    assert!(!harness.state().players[1].alive);
+}
+
+#[derive(Inspectable, Default)]
+#[inspectable(buttons(reset, respawn))]
+struct Enemy {
+    health: i32,
+    respawns: u32,
+}
+
+impl Enemy {
+    fn reset(&mut self) {
+        self.health = 100;
+    }
+
+    fn respawn(&mut self) {
+        self.reset();
+        self.respawns += 1;
+    }
+}
+
+#[test]
+fn buttons_call_methods() {
+    let mut harness = Harness::inspector(Enemy::default());
+    let texts = harness.buttons().iter().map(|button| button.text.as_str());
+    assert_eq!(texts.collect::<Vec<_>>(), ["reset", "respawn"]);
+
+    harness.click("reset");
+    assert_eq!(harness.state().health, 100);
+    assert_eq!(harness.state().respawns, 0);
+    harness.state_mut().health = 0;
+    harness.click("respawn");
+    assert_eq!(harness.state().health, 100);
+    assert_eq!(harness.state().respawns, 1);
+}
@@ -0,0 +1,25 @@
+use guiedit::inspectable::{Inspectable, Value};
+
+#[test]
+fn floats_convert_to_integers_only_when_exact() {
+    assert_eq!(Value::Float(-3.).to_integer::<i8>(), Some(-3));
+    assert_eq!(Value::Float(-3.).to_integer::<u8>(), None);
+    assert_eq!(Value::Float(2.5).to_integer::<i32>(), None);
+    assert_eq!(Value::Float(300.).to_integer::<u8>(), None);
+    assert_eq!(Value::Float(f64::NAN).to_integer::<i64>(), None);
+    assert_eq!(Value::Float(f64::INFINITY).to_integer::<u64>(), None);
+
+    // 2^63 does not fit in an i64, but does in an u64
+    let two_pow_63 = 2f64.powi(63);
+    assert_eq!(
+        Value::Float(-two_pow_63).to_integer::<i64>(),
+        Some(i64::MIN)
+    );
+    assert_eq!(Value::Float(two_pow_63).to_integer::<i64>(), None);
+    assert_eq!(Value::Float(two_pow_63).to_integer::<u64>(), Some(1 << 63));
+    assert_eq!(Value::Float(2f64.powi(64)).to_integer::<u64>(), None);
+
+    let mut value = 5i64;
+    assert!(!value.set_value(Value::Float(two_pow_63)));
+    assert_eq!(value, 5);
+}
@@ -0,0 +1,79 @@
+use guiedit::{
+    inspectable::{self, Value},
+    tree::{self, ROOT_ID},
+    watch::Watches,
+    Inspectable, TreeNode,
+};
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Ball {
+    speed: f32,
+    name: String,
+}
+
+#[derive(Inspectable, TreeNode, Default)]
+struct Pong {
+    balls: Vec<Ball>,
+}
+
+fn path(path: &[&str]) -> Vec<String> {
+    path.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn field_values_are_found_by_path() {
+    let mut pong = Pong {
+        balls: vec![
+            Ball::default(),
+            Ball {
+                speed: 2.,
+                name: "fast".to_owned(),
+            },
+        ],
+    };
+    let speed = inspectable::field_value(&mut pong, &path(&["balls", "1", "speed"]));
+    assert_eq!(speed, Some(Value::Float(2.)));
+    assert_eq!(
+        inspectable::field_value(&mut pong, &path(&["balls", "2", "speed"])),
+        None
+    );
+    assert_eq!(
+        inspectable::field_value(&mut pong.balls[0].speed, &[]),
+        Some(Value::Float(0.))
+    );
+}
+
+#[test]
+fn watches_record_numeric_fields() {
+    let mut pong = Pong {
+        balls: vec![Ball::default()],
+    };
+    let ball = tree::id_at_path(&mut pong, ROOT_ID, &path(&["balls", "0"])).unwrap();
+    let mut watches = Watches::with_capacity(3);
+    watches.add(&mut pong, ball, path(&["speed"]));
+    watches.add(&mut pong, ball, path(&["speed"]));
+    watches.add(&mut pong, ball, path(&["name"]));
+    assert_eq!(watches.len(), 2);
+
+    for frame in 0..5 {
+        pong.balls[0].speed = frame as f32;
+        watches.record(&mut pong, frame as f64 / 2.);
+    }
+    let mut watched = watches.iter();
+    let speed = watched.next().unwrap();
+    assert_eq!(speed.title(), "balls/0.speed");
+    // Only the newest samples are kept
+    assert_eq!(
+        speed.samples().collect::<Vec<_>>(),
+        [[1., 2.], [1.5, 3.], [2., 4.]]
+    );
+    assert_eq!(speed.to_csv(), "time,value\n1,2\n1.5,3\n2,4\n");
+    // Strings cannot be plotted
+    assert_eq!(watched.next().unwrap().samples().count(), 0);
+    drop(watched);
+
+    // Watches of nodes that do not exist anymore keep their samples
+    pong.balls.clear();
+    watches.record(&mut pong, 3.);
+    assert_eq!(watches.iter().next().unwrap().samples().count(), 3);
+}
@@ -0,0 +1,22 @@
+use guiedit::waveform::{waveform, MAX_POINTS};
+
+#[test]
+fn short_waveforms_have_a_point_per_frame() {
+    let samples = [i16::MAX, i16::MAX, 0, i16::MAX, -i16::MAX, -i16::MAX];
+    assert_eq!(
+        waveform(&samples, 2, 4),
+        [[0., 1.], [0.25, 0.5], [0.5, -1.]]
+    );
+}
+
+#[test]
+fn long_waveforms_keep_their_peaks() {
+    let mut samples = vec![0; MAX_POINTS * 10];
+    samples[12_345] = i16::MAX;
+    samples[23_456] = -i16::MAX;
+    let points = waveform(&samples, 1, 1000);
+    assert!(points.len() <= MAX_POINTS);
+    assert!(points.windows(2).all(|pair| pair[0][0] <= pair[1][0]));
+    assert!(points.contains(&[12.345, 1.]));
+    assert!(points.contains(&[23.456, -1.]));
+}